@@ -0,0 +1,20 @@
+//! Offline astronomical calculations used to enrich the time dimension.
+
+pub mod moon;
+pub mod sun;
+
+use time::OffsetDateTime;
+
+/// Julian Date of `instant` (days since noon UTC, 1 January 4713 BC).
+pub fn julian_date(instant: OffsetDateTime) -> f64 {
+    instant.unix_timestamp() as f64 / 86_400.0 + 2_440_587.5
+}
+
+/// Julian centuries since the J2000.0 epoch.
+pub fn julian_centuries(instant: OffsetDateTime) -> f64 {
+    (julian_date(instant) - 2_451_545.0) / 36_525.0
+}
+
+fn normalize_degrees(deg: f64) -> f64 {
+    deg.rem_euclid(360.0)
+}
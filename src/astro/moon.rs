@@ -0,0 +1,101 @@
+//! Lunar phase after Meeus, *Astronomical Algorithms* (2nd ed.), chapter 48.
+//!
+//! Uses the lower-accuracy series for the phase angle (eq. 48.4), which is
+//! good to well under a degree — a few minutes of lunar age — for any date
+//! in the 20th and 21st century.
+
+use super::{julian_centuries, normalize_degrees};
+use time::OffsetDateTime;
+
+/// Mean length of a lunation in days.
+pub const SYNODIC_MONTH_DAYS: f64 = 29.530_588_853;
+
+/// Position of the Moon relative to the Sun as seen from Earth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoonState {
+    /// Sun–Moon elongation in degrees, `[0, 360)`: 0 new, 90 first quarter,
+    /// 180 full, 270 last quarter.
+    pub elongation: f64,
+    /// Phase angle in degrees, `[0, 180]`: 0 full, 180 new.
+    pub phase_angle: f64,
+    /// Illuminated fraction of the disk, `[0, 1]`.
+    pub illumination: f64,
+    /// Days since the last new moon, `[0, SYNODIC_MONTH_DAYS)`.
+    pub age_days: f64,
+}
+
+impl MoonState {
+    /// Computes the state of the Moon at `instant`.
+    pub fn at(instant: OffsetDateTime) -> Self {
+        let t = julian_centuries(instant);
+        let (t2, t3, t4) = (t * t, t * t * t, t * t * t * t);
+
+        // Mean elongation of the Moon (47.2)
+        let d = 297.850_192_1 + 445_267.111_403_4 * t - 0.001_881_9 * t2 + t3 / 545_868.0
+            - t4 / 113_065_000.0;
+        // Mean anomaly of the Sun (47.3)
+        let m = 357.529_109_2 + 35_999.050_290_9 * t - 0.000_153_6 * t2 + t3 / 24_490_000.0;
+        // Mean anomaly of the Moon (47.4)
+        let m_moon = 134.963_396_4 + 477_198.867_505_5 * t + 0.008_741_4 * t2 + t3 / 69_699.0
+            - t4 / 14_712_000.0;
+
+        let (d, m, m_moon) = (d.to_radians(), m.to_radians(), m_moon.to_radians());
+
+        // 48.4, rearranged to the elongation 180° - i
+        let elongation = normalize_degrees(
+            d.to_degrees() + 6.289 * m_moon.sin() - 2.100 * m.sin()
+                + 1.274 * (2.0 * d - m_moon).sin()
+                + 0.658 * (2.0 * d).sin()
+                + 0.214 * (2.0 * m_moon).sin()
+                + 0.110 * d.sin(),
+        );
+        let phase_angle = (180.0 - elongation).abs();
+        let illumination = (1.0 + phase_angle.to_radians().cos()) / 2.0;
+
+        Self {
+            elongation,
+            phase_angle,
+            illumination,
+            age_days: elongation / 360.0 * SYNODIC_MONTH_DAYS,
+        }
+    }
+
+    pub fn is_waxing(&self) -> bool {
+        self.elongation < 180.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    /// Angular distance between two elongations.
+    fn distance(a: f64, b: f64) -> f64 {
+        let d = (a - b).rem_euclid(360.0);
+        d.min(360.0 - d)
+    }
+
+    #[test]
+    fn known_primary_phases() {
+        // USNO: new moon 2016-01-10 01:30 UTC, full moon 2016-01-24 01:46 UTC,
+        // first quarter 2022-12-30 01:21 UTC, last quarter 2019-08-23 14:56 UTC
+        let new = MoonState::at(datetime!(2016-01-10 01:30 UTC));
+        assert!(distance(new.elongation, 0.0) < 1.0, "{new:?}");
+        assert!(new.illumination < 0.01);
+        assert!(new.age_days < 0.1 || new.age_days > SYNODIC_MONTH_DAYS - 0.1);
+
+        let full = MoonState::at(datetime!(2016-01-24 01:46 UTC));
+        assert!(distance(full.elongation, 180.0) < 1.0, "{full:?}");
+        assert!(full.illumination > 0.99);
+
+        let first = MoonState::at(datetime!(2022-12-30 01:21 UTC));
+        assert!(distance(first.elongation, 90.0) < 1.0, "{first:?}");
+        assert!(first.is_waxing());
+        assert!((first.illumination - 0.5).abs() < 0.02);
+
+        let last = MoonState::at(datetime!(2019-08-23 14:56 UTC));
+        assert!(distance(last.elongation, 270.0) < 1.0, "{last:?}");
+        assert!(!last.is_waxing());
+    }
+}
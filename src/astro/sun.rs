@@ -0,0 +1,79 @@
+//! Solar position after the low-accuracy algorithms of Meeus, *Astronomical
+//! Algorithms* (2nd ed.), chapters 12, 22 and 25.
+//!
+//! Good to about 0.01° in declination, which puts sunrise and sunset within
+//! a minute for New York.
+
+use super::{julian_centuries, julian_date, normalize_degrees};
+use time::OffsetDateTime;
+
+/// Altitude of the Sun's centre at sunrise and sunset: the upper limb on the
+/// horizon, corrected for refraction.
+pub const SUNRISE_ALTITUDE: f64 = -0.833;
+
+/// Altitude of the Sun's centre at the end of evening civil twilight.
+pub const CIVIL_TWILIGHT_ALTITUDE: f64 = -6.0;
+
+/// Altitude of the Sun's centre in degrees above the horizon at `instant`,
+/// seen from `latitude` / `longitude` (degrees, east positive).
+pub fn altitude(instant: OffsetDateTime, latitude: f64, longitude: f64) -> f64 {
+    let t = julian_centuries(instant);
+
+    // Geometric mean longitude and mean anomaly (25.2, 25.3)
+    let l0 = 280.466_46 + 36_000.769_83 * t + 0.000_303_2 * t * t;
+    let m = (357.529_11 + 35_999.050_29 * t - 0.000_153_7 * t * t).to_radians();
+    // Equation of the centre
+    let c = (1.914_602 - 0.004_817 * t - 0.000_014 * t * t) * m.sin()
+        + (0.019_993 - 0.000_101 * t) * (2.0 * m).sin()
+        + 0.000_289 * (3.0 * m).sin();
+    // Apparent longitude (25.8)
+    let omega = (125.04 - 1_934.136 * t).to_radians();
+    let lambda = (l0 + c - 0.005_69 - 0.004_78 * omega.sin()).to_radians();
+    // Obliquity of the ecliptic (22.2), corrected for nutation (25.8)
+    let epsilon = (23.0
+        + (26.0 + (21.448 - t * (46.815 + t * (0.000_59 - t * 0.001_813))) / 60.0) / 60.0
+        + 0.002_56 * omega.cos())
+    .to_radians();
+
+    let declination = (epsilon.sin() * lambda.sin()).asin();
+    let right_ascension = (epsilon.cos() * lambda.sin()).atan2(lambda.cos());
+
+    // Greenwich mean sidereal time (12.4)
+    let sidereal = 280.460_618_37
+        + 360.985_647_366_29 * (julian_date(instant) - 2_451_545.0)
+        + 0.000_387_933 * t * t
+        - t * t * t / 38_710_000.0;
+    let hour_angle =
+        normalize_degrees(sidereal + longitude - right_ascension.to_degrees()).to_radians();
+
+    let latitude = latitude.to_radians();
+    (latitude.sin() * declination.sin() + latitude.cos() * declination.cos() * hour_angle.cos())
+        .asin()
+        .to_degrees()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    const NYC: (f64, f64) = (40.7128, -74.0060);
+
+    #[test]
+    fn noon_altitude_follows_the_seasons() {
+        // local solar noon is about 16:57 UTC in June, 16:54 UTC in December
+        let june = altitude(datetime!(2021-06-21 16:57 UTC), NYC.0, NYC.1);
+        assert!((june - 72.7).abs() < 0.2, "{june}");
+        let december = altitude(datetime!(2021-12-21 16:54 UTC), NYC.0, NYC.1);
+        assert!((december - 25.9).abs() < 0.2, "{december}");
+        assert!(altitude(datetime!(2021-06-21 04:57 UTC), NYC.0, NYC.1) < -20.0);
+    }
+
+    #[test]
+    fn sunrise_and_civil_dawn() {
+        // USNO, New York, 1 January 2021: civil dawn 06:49, sunrise 07:20 EST
+        let at = |instant| altitude(instant, NYC.0, NYC.1);
+        assert!((at(datetime!(2021-01-01 12:20 UTC)) - SUNRISE_ALTITUDE).abs() < 0.2);
+        assert!((at(datetime!(2021-01-01 11:49 UTC)) - CIVIL_TWILIGHT_ALTITUDE).abs() < 0.2);
+    }
+}
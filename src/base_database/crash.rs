@@ -1,3 +1,8 @@
+use crate::base_database::location::{Coordinates, Geocoder, Location};
+use crate::base_database::mapping::Mappings;
+use crate::base_database::vehicle::VehicleType;
+use crate::config::CasualtyPlausibilityConfig;
+use crate::data_quality::rejects::{RejectLocation, RejectReason, RejectSource, Rejects};
 use crate::raw::crashes::RawCrashRecord;
 use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
@@ -14,7 +19,15 @@ pub struct Crash {
     pub crash_cyclist_killed: u16,
     pub crash_motorist_injured: u16,
     pub crash_motorist_killed: u16,
+    pub location: Location,
+    /// The first recognised factor in vehicle order.
     pub crash_factor: Option<CrashFactor>,
+    /// Every distinct recognised factor of the crash, in vehicle order.
+    pub crash_factors: Vec<CrashFactor>,
+    /// The factor reported for vehicle 1..5, by position.
+    pub vehicle_factors: [Option<CrashFactor>; 5],
+    /// The type of vehicle 1..5, by position.
+    pub vehicle_types: [Option<VehicleType>; 5],
     pub time_id: Option<u32>,
 }
 
@@ -23,6 +36,30 @@ impl Crash {
         self.time_id = Some(time_id);
         self
     }
+
+    /// A crash without casualties or factors, to be adjusted with struct
+    /// update syntax in tests.
+    #[cfg(test)]
+    pub(crate) fn sample() -> Self {
+        Self {
+            crash_id: 1,
+            crash_timestamp: time::macros::datetime!(2020-01-01 12:00),
+            crash_persons_injured: 0,
+            crash_persons_killed: 0,
+            crash_pedestrians_injured: 0,
+            crash_pedestrians_killed: 0,
+            crash_cyclist_injured: 0,
+            crash_cyclist_killed: 0,
+            crash_motorist_injured: 0,
+            crash_motorist_killed: 0,
+            location: Location::default(),
+            crash_factor: None,
+            crash_factors: vec![],
+            vehicle_factors: [None; 5],
+            vehicle_types: [None; 5],
+            time_id: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -85,100 +122,249 @@ pub enum CrashFactor {
     PavementSlippery,
 }
 
-impl From<RawCrashRecord> for Crash {
-    fn from(raw: RawCrashRecord) -> Self {
+impl Crash {
+    /// Converts `raw`, translating its contributing factors by `mappings`.
+    ///
+    /// Quarantines every non-empty contributing factor string that matches no
+    /// [`CrashFactor`] in the mapping, every non-empty vehicle type code that
+    /// matches no known [`VehicleType`], an unknown borough, coordinates
+    /// outside New York City (`0, 0` is merely unknown) and casualty counts
+    /// above `casualties.max_count`. Counts beyond the range of the crash
+    /// columns are capped at `u16::MAX`.
+    ///
+    /// A missing borough or ZIP code is inferred from the coordinates by
+    /// `geocoder`, if it has the boundaries for it.
+    pub fn from_raw(
+        raw: RawCrashRecord,
+        geocoder: &Geocoder,
+        mappings: &Mappings,
+        casualties: &CasualtyPlausibilityConfig,
+        rejects: &mut Rejects,
+    ) -> Self {
+        if let Some(value) = raw.borough.as_deref()
+            && mappings.borough.get(value).is_none()
+        {
+            let at = RejectLocation {
+                line: raw.line,
+                column: "BOROUGH",
+                value,
+            };
+            rejects.reject_record(
+                RejectSource::Crashes,
+                RejectReason::UnknownCategory,
+                at,
+                &raw,
+            );
+        }
+        if let (Some(latitude), Some(longitude)) = (raw.latitude, raw.longitude)
+            && !Coordinates::is_unknown_marker(latitude, longitude)
+            && Coordinates::in_nyc(latitude, longitude).is_none()
+        {
+            let value = format!("{latitude}, {longitude}");
+            let at = RejectLocation {
+                line: raw.line,
+                column: "LATITUDE",
+                value: &value,
+            };
+            rejects.reject_record(RejectSource::Crashes, RejectReason::OutsideNyc, at, &raw);
+        }
+
+        let counts = [
+            ("NUMBER OF PERSONS INJURED", raw.number_of_persons_injured),
+            ("NUMBER OF PERSONS KILLED", raw.number_of_persons_killed),
+            (
+                "NUMBER OF PEDESTRIANS INJURED",
+                raw.number_of_pedestrians_injured,
+            ),
+            (
+                "NUMBER OF PEDESTRIANS KILLED",
+                raw.number_of_pedestrians_killed,
+            ),
+            ("NUMBER OF CYCLIST INJURED", raw.number_of_cyclist_injured),
+            ("NUMBER OF CYCLIST KILLED", raw.number_of_cyclist_killed),
+            ("NUMBER OF MOTORIST INJURED", raw.number_of_motorist_injured),
+            ("NUMBER OF MOTORIST KILLED", raw.number_of_motorist_killed),
+        ];
+        for (column, count) in counts {
+            if count > u32::from(casualties.max_count) {
+                let value = count.to_string();
+                let at = RejectLocation {
+                    line: raw.line,
+                    column,
+                    value: &value,
+                };
+                rejects.reject_record(
+                    RejectSource::Crashes,
+                    RejectReason::ImplausibleCount,
+                    at,
+                    &raw,
+                );
+            }
+        }
+
+        let factors = [
+            (
+                "CONTRIBUTING FACTOR VEHICLE 1",
+                &raw.contributing_factor_vehicle_1,
+            ),
+            (
+                "CONTRIBUTING FACTOR VEHICLE 2",
+                &raw.contributing_factor_vehicle_2,
+            ),
+            (
+                "CONTRIBUTING FACTOR VEHICLE 3",
+                &raw.contributing_factor_vehicle_3,
+            ),
+            (
+                "CONTRIBUTING FACTOR VEHICLE 4",
+                &raw.contributing_factor_vehicle_4,
+            ),
+            (
+                "CONTRIBUTING FACTOR VEHICLE 5",
+                &raw.contributing_factor_vehicle_5,
+            ),
+        ];
+        let vehicle_types = [
+            ("VEHICLE TYPE CODE 1", &raw.vehicle_type_code_1),
+            ("VEHICLE TYPE CODE 2", &raw.vehicle_type_code_2),
+            ("VEHICLE TYPE CODE 3", &raw.vehicle_type_code_3),
+            ("VEHICLE TYPE CODE 4", &raw.vehicle_type_code_4),
+            ("VEHICLE TYPE CODE 5", &raw.vehicle_type_code_5),
+        ];
+        for (column, value) in factors {
+            if value.eq_ignore_ascii_case(UNSPECIFIED) {
+                rejects.record_explicit_unknown(RejectSource::Crashes, column, value);
+            }
+        }
+        let unmapped = factors
+            .into_iter()
+            .filter(|(_, value)| {
+                !value.eq_ignore_ascii_case(UNSPECIFIED)
+                    && mappings.contributing_factor.get(value).is_none()
+            })
+            .chain(
+                vehicle_types
+                    .into_iter()
+                    .filter(|(_, value)| mappings.vehicle_type.get(value).is_none()),
+            );
+        for (column, value) in unmapped {
+            if !value.is_empty() {
+                let at = RejectLocation {
+                    line: raw.line,
+                    column,
+                    value,
+                };
+                rejects.reject_record(
+                    RejectSource::Crashes,
+                    RejectReason::UnknownCategory,
+                    at,
+                    &raw,
+                );
+            }
+        }
+        let mut crash = Self::convert(raw, mappings);
+        geocoder.fill(&mut crash.location, &mappings.borough);
+        crash
+    }
+}
+
+/// The marker NYC uses for "no contributing factor recorded"; it is an
+/// explicit unknown, not an unmapped category.
+pub(crate) const UNSPECIFIED: &str = "Unspecified";
+
+impl Crash {
+    fn convert(raw: RawCrashRecord, mappings: &Mappings) -> Self {
+        let vehicle_factors = [
+            &raw.contributing_factor_vehicle_1,
+            &raw.contributing_factor_vehicle_2,
+            &raw.contributing_factor_vehicle_3,
+            &raw.contributing_factor_vehicle_4,
+            &raw.contributing_factor_vehicle_5,
+        ]
+        .map(|f| mappings.contributing_factor.get(f));
+        let vehicle_types = [
+            &raw.vehicle_type_code_1,
+            &raw.vehicle_type_code_2,
+            &raw.vehicle_type_code_3,
+            &raw.vehicle_type_code_4,
+            &raw.vehicle_type_code_5,
+        ]
+        .map(|t| mappings.vehicle_type.get(t));
+        let capped = |count: u32| u16::try_from(count).unwrap_or(u16::MAX);
+        let mut crash_factors: Vec<CrashFactor> = Vec::new();
+        for factor in vehicle_factors.into_iter().flatten() {
+            if !crash_factors.contains(&factor) {
+                crash_factors.push(factor);
+            }
+        }
         Self {
             crash_id: raw.collision_id,
             crash_timestamp: PrimitiveDateTime::new(raw.crash_date, raw.crash_time),
-            crash_persons_injured: raw.number_of_persons_injured,
-            crash_persons_killed: raw.number_of_persons_killed,
-            crash_pedestrians_injured: raw.number_of_pedestrians_injured,
-            crash_pedestrians_killed: raw.number_of_pedestrians_killed,
-            crash_cyclist_injured: raw.number_of_cyclist_injured,
-            crash_cyclist_killed: raw.number_of_cyclist_killed,
-            crash_motorist_injured: raw.number_of_motorist_injured,
-            crash_motorist_killed: raw.number_of_motorist_killed,
-            crash_factor: extract_contributing_factor(&raw.contributing_factor_vehicle_1)
-                .or(extract_contributing_factor(
-                    &raw.contributing_factor_vehicle_2,
-                ))
-                .or(extract_contributing_factor(
-                    &raw.contributing_factor_vehicle_3,
-                ))
-                .or(extract_contributing_factor(
-                    &raw.contributing_factor_vehicle_4,
-                ))
-                .or(extract_contributing_factor(
-                    &raw.contributing_factor_vehicle_5,
-                )),
+            crash_persons_injured: capped(raw.number_of_persons_injured),
+            crash_persons_killed: capped(raw.number_of_persons_killed),
+            crash_pedestrians_injured: capped(raw.number_of_pedestrians_injured),
+            crash_pedestrians_killed: capped(raw.number_of_pedestrians_killed),
+            crash_cyclist_injured: capped(raw.number_of_cyclist_injured),
+            crash_cyclist_killed: capped(raw.number_of_cyclist_killed),
+            crash_motorist_injured: capped(raw.number_of_motorist_injured),
+            crash_motorist_killed: capped(raw.number_of_motorist_killed),
+            location: Location {
+                borough: raw.borough.as_deref().and_then(|b| mappings.borough.get(b)),
+                coordinates: raw
+                    .latitude
+                    .zip(raw.longitude)
+                    .and_then(|(lat, lon)| Coordinates::in_nyc(lat, lon)),
+                zip_code: raw.zip_code,
+                on_street_name: raw.on_street_name,
+                cross_street_name: raw.cross_street_name,
+                off_street_name: raw.off_street_name,
+                ..Location::default()
+            }
+            .reported(),
+            crash_factor: crash_factors.first().copied(),
+            crash_factors,
+            vehicle_factors,
+            vehicle_types,
             time_id: None,
         }
     }
 }
 
-fn extract_contributing_factor(contributing_factor: &str) -> Option<CrashFactor> {
-    match contributing_factor.to_lowercase().trim() {
-        "driverless/runaway vehicle" => Some(CrashFactor::DriverlessRunawayVehicle),
-        "listening/using headphones" => Some(CrashFactor::ListeningUsingHeadphones),
-        "eating or drinking" => Some(CrashFactor::EatingOrDrinking),
-        "unsafe lane changing" => Some(CrashFactor::UnsafeLaneChanging),
-        "cell phone (hand-held)" => Some(CrashFactor::CellPhoneHandHeld),
-        "cell phone (hands-free)" => Some(CrashFactor::CellPhoneHandsFree),
-        "drugs (illegal)" => Some(CrashFactor::DrugsIllegal),
-        "texting" => Some(CrashFactor::Texting),
-        "headlights defective" => Some(CrashFactor::HeadlightsDefective),
-        "other lighting defects" => Some(CrashFactor::OtherLightingDefects),
-        "driver inexperience" => Some(CrashFactor::DriverInexperience),
-        "aggressive driving/road rage" => Some(CrashFactor::AggressiveDrivingRoadRage),
-        "unsafe speed" => Some(CrashFactor::UnsafeSpeed),
-        "lane marking improper/inadequate" => Some(CrashFactor::LaneMarkingImproperInadequate),
-        "glare" => Some(CrashFactor::Glare),
-        "traffic control device improper/non-working" => {
-            Some(CrashFactor::TrafficControlDeviceImproperNonWorking)
-        }
-        "passing too closely" => Some(CrashFactor::PassingTooClosely),
-        "accelerator defective" => Some(CrashFactor::AcceleratorDefective),
-        "shoulders defective/improper" => Some(CrashFactor::ShouldersDefectiveImproper),
-        "outside car distraction" => Some(CrashFactor::OutsideCarDistraction),
-        "driver inattention/distraction" => Some(CrashFactor::DriverInattentionDistraction),
-        "tinted windows" => Some(CrashFactor::TintedWindows),
-        "using on board navigation device" => Some(CrashFactor::UsingOnBoardNavigationDevice),
-        "reaction to other uninvolved vehicle" => {
-            Some(CrashFactor::ReactionToOtherUninvolvedVehicle)
-        }
-        "obstruction/debris" => Some(CrashFactor::ObstructionDebris),
-        "prescription medication" => Some(CrashFactor::PrescriptionMedication),
-        "tire failure/inadequate" => Some(CrashFactor::TireFailureInadequate),
-        "fatigued/drowsy" => Some(CrashFactor::FatiguedDrowsy),
-        "passing or lane usage improper" => Some(CrashFactor::PassingOrLaneUsageImproper),
-        "following too closely" => Some(CrashFactor::FollowingTooClosely),
-        "view obstructed/limited" => Some(CrashFactor::ViewObstructedLimited),
-        "oversized vehicle" => Some(CrashFactor::OversizedVehicle),
-        "lost consciousness" => Some(CrashFactor::LostConsciousness),
-        "backing unsafely" => Some(CrashFactor::BackingUnsafely),
-        "other vehicular" => Some(CrashFactor::OtherVehicular),
-        "illness" => Some(CrashFactor::Illness),
-        "windshield inadequate" => Some(CrashFactor::WindshieldInadequate),
-        "fell asleep" => Some(CrashFactor::FellAsleep),
-        "traffic control disregarded" => Some(CrashFactor::TrafficControlDisregarded),
-        "pavement defective" => Some(CrashFactor::PavementDefective),
-        "steering failure" => Some(CrashFactor::SteeringFailure),
-        "passenger distraction" => Some(CrashFactor::PassengerDistraction),
-        "vehicle vandalism" => Some(CrashFactor::VehicleVandalism),
-        "failure to keep right" => Some(CrashFactor::FailureToKeepRight),
-        "brakes defective" => Some(CrashFactor::BrakesDefective),
-        "turning improperly" => Some(CrashFactor::TurningImproperly),
-        "failure to yield right-of-way" => Some(CrashFactor::FailureToYieldRightOfWay),
-        "reaction to uninvolved vehicle" => Some(CrashFactor::ReactionToUninvolvedVehicle),
-        "tow hitch defective" => Some(CrashFactor::TowHitchDefective),
-        "alcohol involvement" => Some(CrashFactor::AlcoholInvolvement),
-        "physical disability" => Some(CrashFactor::PhysicalDisability),
-        "animals action" => Some(CrashFactor::AnimalsAction),
-        "other electronic device" => Some(CrashFactor::OtherElectronicDevice),
-        "pedestrian/bicyclist/other pedestrian error/confusion" => {
-            Some(CrashFactor::PedestrianBicyclistOtherPedestrianErrorConfusion)
-        }
-        "pavement slippery" => Some(CrashFactor::PavementSlippery),
-        _ => None,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw::{ColumnAliases, RawReader};
+
+    fn raw_crash() -> RawCrashRecord {
+        RawCrashRecord::read_csv("data/crashes_preview.csv", &ColumnAliases::default())
+            .and_then(RawReader::strict)
+            .unwrap()
+            .remove(0)
+    }
+
+    #[test]
+    fn implausible_counts_are_quarantined_and_capped() {
+        let raw = RawCrashRecord {
+            number_of_persons_injured: 101,
+            number_of_motorist_injured: 70_000,
+            ..raw_crash()
+        };
+        let mut rejects = Rejects::default();
+        let crash = Crash::from_raw(
+            raw,
+            &Geocoder::default(),
+            &Mappings::default(),
+            &CasualtyPlausibilityConfig::default(),
+            &mut rejects,
+        );
+        assert_eq!(crash.crash_persons_injured, 101);
+        assert_eq!(crash.crash_motorist_injured, u16::MAX);
+        let implausible = rejects
+            .summary()
+            .find(|&(_, reason, _)| reason == RejectReason::ImplausibleCount);
+        assert_eq!(
+            implausible,
+            Some((RejectSource::Crashes, RejectReason::ImplausibleCount, 2))
+        );
     }
 }
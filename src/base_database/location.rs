@@ -0,0 +1,201 @@
+use crate::base_database::mapping::Mapping;
+use crate::geo::Boundaries;
+use serde::{Deserialize, Serialize};
+
+/// Where a crash happened, as far as it was reported.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Location {
+    pub borough: Option<Borough>,
+    pub borough_source: Option<Provenance>,
+    /// Five-digit ZIP code.
+    pub zip_code: Option<String>,
+    pub zip_code_source: Option<Provenance>,
+    pub coordinates: Option<Coordinates>,
+    pub on_street_name: Option<String>,
+    pub cross_street_name: Option<String>,
+    pub off_street_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Borough {
+    Bronx,
+    Brooklyn,
+    Manhattan,
+    Queens,
+    StatenIsland,
+}
+
+/// Where a location attribute came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Provenance {
+    /// Recorded on the crash report.
+    Reported,
+    /// Derived from the coordinates by [`Geocoder`].
+    Inferred,
+}
+
+/// A WGS 84 position inside New York City.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Coordinates {
+    /// Bounding box of the five boroughs, with a small margin.
+    pub const NYC_LATITUDE: (f64, f64) = (40.49, 40.92);
+    pub const NYC_LONGITUDE: (f64, f64) = (-74.27, -73.68);
+
+    /// City Hall, the reference point for city-wide sun positions; sunrise
+    /// differs by well under a minute across the five boroughs.
+    pub const CITY_HALL: Coordinates = Coordinates {
+        latitude: 40.7128,
+        longitude: -74.0060,
+    };
+
+    /// Whether `latitude, longitude` is the `0, 0` that NYPD records for an
+    /// unknown position, as opposed to a real one.
+    pub fn is_unknown_marker(latitude: f64, longitude: f64) -> bool {
+        latitude == 0.0 && longitude == 0.0
+    }
+
+    /// The position, or `None` if it lies outside the NYC bounding box —
+    /// including the [unknown marker](Self::is_unknown_marker).
+    pub fn in_nyc(latitude: f64, longitude: f64) -> Option<Self> {
+        let (south, north) = Self::NYC_LATITUDE;
+        let (west, east) = Self::NYC_LONGITUDE;
+        ((south..=north).contains(&latitude) && (west..=east).contains(&longitude)).then_some(
+            Self {
+                latitude,
+                longitude,
+            },
+        )
+    }
+}
+
+impl Location {
+    /// Location with the reported values marked as such.
+    pub(crate) fn reported(mut self) -> Self {
+        self.borough_source = self.borough.map(|_| Provenance::Reported);
+        self.zip_code_source = self.zip_code.as_ref().map(|_| Provenance::Reported);
+        self
+    }
+}
+
+/// Fills in the borough and ZIP code of crashes that were reported with
+/// coordinates only.
+#[derive(Debug, Clone, Default)]
+pub struct Geocoder {
+    boroughs: Option<Boundaries>,
+    zip_codes: Option<Boundaries>,
+}
+
+impl Geocoder {
+    /// Feature properties holding the borough name, in order of preference.
+    pub const BOROUGH_PROPERTIES: &[&str] = &["boro_name", "BoroName", "borough", "BOROUGH"];
+    /// Feature properties holding the ZIP code, in order of preference.
+    pub const ZIP_CODE_PROPERTIES: &[&str] = &[
+        "modzcta",
+        "MODZCTA",
+        "zcta",
+        "ZIPCODE",
+        "zipcode",
+        "postalcode",
+    ];
+
+    pub fn new(boroughs: Option<Boundaries>, zip_codes: Option<Boundaries>) -> Self {
+        Self {
+            boroughs,
+            zip_codes,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boroughs.is_none() && self.zip_codes.is_none()
+    }
+
+    /// Infers the missing borough and ZIP code of `location` from its
+    /// coordinates, translating the boundary's borough name by `boroughs`;
+    /// reported values are never overwritten.
+    pub fn fill(&self, location: &mut Location, boroughs: &Mapping<Borough>) {
+        let Some(Coordinates {
+            latitude,
+            longitude,
+        }) = location.coordinates
+        else {
+            return;
+        };
+        if location.borough.is_none()
+            && let Some(borough) = self
+                .boroughs
+                .as_ref()
+                .and_then(|b| b.locate(latitude, longitude))
+                .and_then(|name| boroughs.get(name))
+        {
+            location.borough = Some(borough);
+            location.borough_source = Some(Provenance::Inferred);
+        }
+        if location.zip_code.is_none()
+            && let Some(zip_code) = self
+                .zip_codes
+                .as_ref()
+                .and_then(|z| z.locate(latitude, longitude))
+                .filter(|z| z.len() == 5 && z.bytes().all(|b| b.is_ascii_digit()))
+        {
+            location.zip_code = Some(zip_code.into());
+            location.zip_code_source = Some(Provenance::Inferred);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base_database::mapping::Mappings;
+
+    #[test]
+    fn coordinates_outside_nyc_are_dropped() {
+        assert!(Coordinates::in_nyc(40.62179, -73.970024).is_some());
+        assert!(Coordinates::in_nyc(0.0, 0.0).is_none());
+        assert!(Coordinates::is_unknown_marker(0.0, 0.0));
+        assert!(!Coordinates::is_unknown_marker(0.0, -73.9));
+        // Philadelphia
+        assert!(Coordinates::in_nyc(39.95, -75.16).is_none());
+    }
+
+    #[test]
+    fn geocoder_fills_only_missing_values() {
+        let boroughs = Boundaries::from_geojson(
+            r#"{ "type": "FeatureCollection", "features": [
+                { "type": "Feature", "properties": { "boro_name": "Brooklyn" },
+                  "geometry": { "type": "Polygon", "coordinates": [
+                      [[-74.0, 40.6], [-73.9, 40.6], [-73.9, 40.7], [-74.0, 40.7], [-74.0, 40.6]]
+                  ] } }
+            ] }"#,
+            Geocoder::BOROUGH_PROPERTIES,
+        )
+        .unwrap();
+        let geocoder = Geocoder::new(Some(boroughs), None);
+
+        let mut missing = Location {
+            coordinates: Coordinates::in_nyc(40.65, -73.95),
+            ..Location::default()
+        };
+        let mappings = Mappings::default();
+        geocoder.fill(&mut missing, &mappings.borough);
+        assert_eq!(missing.borough, Some(Borough::Brooklyn));
+        assert_eq!(missing.borough_source, Some(Provenance::Inferred));
+        assert_eq!(missing.zip_code, None);
+
+        let mut reported = Location {
+            borough: Some(Borough::Queens),
+            ..missing.clone()
+        }
+        .reported();
+        geocoder.fill(&mut reported, &mappings.borough);
+        assert_eq!(reported.borough, Some(Borough::Queens));
+        assert_eq!(reported.borough_source, Some(Provenance::Reported));
+    }
+}
@@ -0,0 +1,216 @@
+//! Translation of the category strings of the source into enums.
+//!
+//! Each table is a `source,target` CSV whose targets are the enum variants
+//! as written in the data mart (e.g. `FRONT`). The tables in
+//! `src/base_database/mappings/` are built in; a file of the same name in the
+//! configured `mappings_dir` replaces the built-in table, so that mappings can
+//! be fixed without a rebuild. A table is rejected at startup if a target is
+//! no variant of its enum or a source string is mapped twice.
+
+use crate::base_database::crash::CrashFactor;
+use crate::base_database::location::Borough;
+use crate::base_database::person::{
+    BodilyInjury, Ejection, EmotionalStatus, PedAction, PedLocation, PersonInjury,
+    PersonPositionInVehicle, PersonRole, PersonSex, PersonType, SafetyEquipment,
+};
+use crate::base_database::vehicle::VehicleType;
+use anyhow::{Context, Result, ensure};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// One `source,target` table.
+#[derive(Debug, Clone)]
+pub struct Mapping<T> {
+    entries: HashMap<String, T>,
+    /// Source strings are compared ignoring case and surrounding whitespace.
+    case_insensitive: bool,
+}
+
+impl<T: DeserializeOwned + Copy> Mapping<T> {
+    /// Parses the CSV `text`; `origin` names it in errors.
+    pub fn parse(origin: &str, text: &str, case_insensitive: bool) -> Result<Self> {
+        let mut entries = HashMap::new();
+        let mut rdr = csv::Reader::from_reader(text.as_bytes());
+        for (i, record) in rdr.records().enumerate() {
+            // header is line 1
+            let line = i + 2;
+            let record = record.with_context(|| format!("{origin}:{line}"))?;
+            let (Some(source), Some(target), 2) = (record.get(0), record.get(1), record.len())
+            else {
+                anyhow::bail!("{origin}:{line}: expected `source,target`");
+            };
+            let value: T = serde_json::from_value(serde_json::Value::String(target.into()))
+                .with_context(|| format!("{origin}:{line}: {target:?} is no valid target"))?;
+            let key = Self::key(source, case_insensitive);
+            ensure!(
+                entries.insert(key, value).is_none(),
+                "{origin}:{line}: {source:?} is mapped twice"
+            );
+        }
+        Ok(Self {
+            entries,
+            case_insensitive,
+        })
+    }
+
+    pub fn get(&self, source: &str) -> Option<T> {
+        self.entries
+            .get(&Self::key(source, self.case_insensitive))
+            .copied()
+    }
+
+    fn key(source: &str, case_insensitive: bool) -> String {
+        if case_insensitive {
+            source.trim().to_lowercase()
+        } else {
+            source.to_owned()
+        }
+    }
+}
+
+/// All tables used to convert crashes, persons and vehicles.
+#[derive(Debug, Clone)]
+pub struct Mappings {
+    pub borough: Mapping<Borough>,
+    pub contributing_factor: Mapping<CrashFactor>,
+    pub vehicle_type: Mapping<VehicleType>,
+    pub person_sex: Mapping<PersonSex>,
+    pub person_type: Mapping<PersonType>,
+    pub position_in_vehicle: Mapping<PersonPositionInVehicle>,
+    pub person_role: Mapping<PersonRole>,
+    pub person_injury: Mapping<PersonInjury>,
+    pub ejection: Mapping<Ejection>,
+    pub emotional_status: Mapping<EmotionalStatus>,
+    pub bodily_injury: Mapping<BodilyInjury>,
+    pub safety_equipment: Mapping<SafetyEquipment>,
+    pub ped_location: Mapping<PedLocation>,
+    pub ped_action: Mapping<PedAction>,
+}
+
+macro_rules! table {
+    ($dir:expr, $name:literal, $case_insensitive:expr) => {{
+        let builtin = include_str!(concat!("mappings/", $name, ".csv"));
+        match $dir.map(|dir| dir.join(concat!($name, ".csv"))) {
+            Some(path) if path.exists() => {
+                let text = std::fs::read_to_string(&path)
+                    .with_context(|| format!("reading {}", path.display()))?;
+                Mapping::parse(&path.display().to_string(), &text, $case_insensitive)?
+            }
+            _ => Mapping::parse(
+                concat!("built-in ", $name, ".csv"),
+                builtin,
+                $case_insensitive,
+            )?,
+        }
+    }};
+}
+
+impl Mappings {
+    /// The built-in tables, each replaced by `<dir>/<table>.csv` if that
+    /// exists.
+    pub fn load(dir: Option<&Path>) -> Result<Self> {
+        Ok(Self {
+            borough: table!(dir, "borough", true),
+            contributing_factor: table!(dir, "contributing_factor", true),
+            vehicle_type: table!(dir, "vehicle_type", true),
+            person_sex: table!(dir, "person_sex", true),
+            person_type: table!(dir, "person_type", false),
+            position_in_vehicle: table!(dir, "position_in_vehicle", false),
+            person_role: table!(dir, "person_role", false),
+            person_injury: table!(dir, "person_injury", false),
+            ejection: table!(dir, "ejection", false),
+            emotional_status: table!(dir, "emotional_status", false),
+            bodily_injury: table!(dir, "bodily_injury", false),
+            safety_equipment: table!(dir, "safety_equipment", false),
+            ped_location: table!(dir, "ped_location", false),
+            ped_action: table!(dir, "ped_action", false),
+        })
+    }
+}
+
+impl Default for Mappings {
+    fn default() -> Self {
+        Self::load(None).expect("the built-in mappings are valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_tables_load() {
+        let mappings = Mappings::default();
+        assert_eq!(
+            mappings.contributing_factor.get(" Driver Inexperience "),
+            Some(CrashFactor::DriverInexperience)
+        );
+        assert_eq!(
+            mappings
+                .position_in_vehicle
+                .get("If one person is seated on another person's lap"),
+            Some(PersonPositionInVehicle::Lap)
+        );
+        assert_eq!(
+            mappings
+                .vehicle_type
+                .get("Station Wagon/Sport Utility Vehicle"),
+            Some(VehicleType::StationWagonSuv)
+        );
+        assert_eq!(
+            mappings.vehicle_type.get("E-Bike"),
+            Some(VehicleType::EBike)
+        );
+        assert_eq!(
+            mappings.borough.get("STATEN ISLAND"),
+            Some(Borough::StatenIsland)
+        );
+        assert_eq!(mappings.person_sex.get("F"), Some(PersonSex::Female));
+        assert_eq!(mappings.person_sex.get("U"), None);
+        assert_eq!(mappings.person_role.get("driver"), None);
+        assert_eq!(mappings.person_role.get("Driver"), Some(PersonRole::Driver));
+    }
+
+    #[test]
+    fn invalid_tables_are_rejected() {
+        let unknown_variant =
+            Mapping::<PersonRole>::parse("roles.csv", "source,target\nDriver,CHAUFFEUR\n", false);
+        let error = format!("{:#}", unknown_variant.unwrap_err());
+        assert!(error.contains("roles.csv:2"), "{error}");
+
+        let twice = Mapping::<CrashFactor>::parse(
+            "factors.csv",
+            "source,target\nGlare,GLARE\nglare ,GLARE\n",
+            true,
+        );
+        assert!(twice.is_err());
+    }
+
+    #[test]
+    fn a_file_replaces_the_builtin_table() {
+        let dir = std::env::temp_dir().join(format!(
+            "mapping_a_file_replaces_the_builtin_table_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("person_role.csv"),
+            "source,target\nChauffeur,DRIVER\n",
+        )
+        .unwrap();
+        let mappings = Mappings::load(Some(&dir));
+        std::fs::remove_dir_all(&dir).unwrap();
+        let mappings = mappings.unwrap();
+        assert_eq!(
+            mappings.person_role.get("Chauffeur"),
+            Some(PersonRole::Driver)
+        );
+        assert_eq!(mappings.person_role.get("Driver"), None);
+        assert_eq!(
+            mappings.person_type.get("Pedestrian"),
+            Some(PersonType::Pedestrian)
+        );
+    }
+}
@@ -1,3 +1,8 @@
 pub mod crash;
+pub mod location;
+pub mod mapping;
 pub mod person;
 pub mod time;
+pub mod timezone;
+pub mod vehicle;
+pub mod weather_rules;
@@ -1,5 +1,11 @@
+use crate::base_database::crash::{CrashFactor, UNSPECIFIED};
+use crate::base_database::mapping::{Mapping, Mappings};
+use crate::config::AgePlausibilityConfig;
+use crate::data_quality::rejects::{RejectLocation, RejectReason, RejectSource, Rejects};
 use crate::raw::persons::RawPersonRecord;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
 pub struct Person {
@@ -9,7 +15,18 @@ pub struct Person {
     pub person_sex: Option<PersonSex>,
     pub person_position_in_vehicle: Option<PersonPositionInVehicle>,
     pub person_role: Option<PersonRole>,
+    pub person_injury: Option<PersonInjury>,
+    pub ejection: Option<Ejection>,
+    pub emotional_status: Option<EmotionalStatus>,
+    pub bodily_injury: Option<BodilyInjury>,
+    pub safety_equipment: Option<SafetyEquipment>,
+    pub ped_location: Option<PedLocation>,
+    pub ped_action: Option<PedAction>,
     pub crash_id: u32,
+    pub vehicle_id: Option<u32>,
+    /// Factors reported for the person themself (CONTRIBUTING_FACTOR_1/2);
+    /// filled for pedestrians and cyclists, who have no vehicle.
+    pub person_factors: Vec<CrashFactor>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -52,73 +69,436 @@ pub enum PersonRole {
     Pedestrian,
 }
 
-impl From<RawPersonRecord> for Person {
-    fn from(raw: RawPersonRecord) -> Self {
+/// Outcome for the person themself, unlike the crash-level counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PersonInjury {
+    Killed,
+    Injured,
+    /// Involved, but neither injured nor killed.
+    Unspecified,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Ejection {
+    NotEjected,
+    PartiallyEjected,
+    Ejected,
+    Trapped,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EmotionalStatus {
+    Conscious,
+    Shock,
+    Incoherent,
+    SemiConscious,
+    Unconscious,
+    ApparentDeath,
+}
+
+/// Body part of the most severe injury.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BodilyInjury {
+    Head,
+    Face,
+    Eye,
+    Neck,
+    Chest,
+    Back,
+    AbdomenPelvis,
+    ShoulderUpperArm,
+    ElbowLowerArmHand,
+    HipUpperLeg,
+    KneeLowerLegFoot,
+    EntireBody,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SafetyEquipment {
+    NoEquipment,
+    LapBelt,
+    Harness,
+    LapBeltHarness,
+    ChildRestraint,
+    AirBag,
+    AirBagLapBelt,
+    AirBagLapBeltHarness,
+    AirBagChildRestraint,
+    HelmetMotorcycle,
+    HelmetBicycle,
+    HelmetOtherBicycle,
+    PadsBicycle,
+    StoppersBicycle,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PedLocation {
+    AtIntersection,
+    NotAtIntersection,
+}
+
+/// What a pedestrian, cyclist or other non-occupant was doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PedAction {
+    CrossingWithSignal,
+    CrossingAgainstSignal,
+    CrossingNoSignalMarkedCrosswalk,
+    CrossingNoSignalOrCrosswalk,
+    AlongHighwayWithTraffic,
+    AlongHighwayAgainstTraffic,
+    EmergingFromParkedVehicle,
+    GoingToFromSchoolBus,
+    GettingOnOffVehicle,
+    PushingWorkingOnCar,
+    WorkingInRoadway,
+    PlayingInRoadway,
+    OtherActionsInRoadway,
+    NotInRoadway,
+}
+
+impl Person {
+    /// Converts `raw`, translating its category strings by `mappings`.
+    ///
+    /// Quarantines every category string that matches no value in its mapping
+    /// and therefore ends up as unknown, and sets
+    /// ages that break the plausibility rules `ages` to unknown. Age 0 is
+    /// unknown without being checked.
+    pub fn from_raw(
+        raw: RawPersonRecord,
+        mappings: &Mappings,
+        ages: &AgePlausibilityConfig,
+        rejects: &mut Rejects,
+    ) -> Self {
+        let sex = raw.person_sex.map(String::from);
+        let unmapped = [
+            (
+                "PERSON_TYPE",
+                raw.person_type.as_deref(),
+                is_unmapped(raw.person_type.as_deref(), &mappings.person_type),
+            ),
+            (
+                "PERSON_SEX",
+                sex.as_deref(),
+                sex.as_deref()
+                    .is_some_and(|s| s != UNKNOWN_SEX && mappings.person_sex.get(s).is_none()),
+            ),
+            (
+                "POSITION_IN_VEHICLE",
+                raw.person_position_in_vehicle.as_deref(),
+                is_unmapped(
+                    raw.person_position_in_vehicle.as_deref(),
+                    &mappings.position_in_vehicle,
+                ),
+            ),
+            (
+                "PED_ROLE",
+                raw.person_ped_role.as_deref(),
+                is_unmapped(raw.person_ped_role.as_deref(), &mappings.person_role),
+            ),
+            (
+                "PERSON_INJURY",
+                raw.person_injury.as_deref(),
+                is_unmapped(raw.person_injury.as_deref(), &mappings.person_injury),
+            ),
+            (
+                "EJECTION",
+                raw.ejection.as_deref(),
+                is_unmapped(raw.ejection.as_deref(), &mappings.ejection),
+            ),
+            (
+                "EMOTIONAL_STATUS",
+                raw.emotional_status.as_deref(),
+                is_unmapped(raw.emotional_status.as_deref(), &mappings.emotional_status),
+            ),
+            (
+                "BODILY_INJURY",
+                raw.bodily_injury.as_deref(),
+                is_unmapped(raw.bodily_injury.as_deref(), &mappings.bodily_injury),
+            ),
+            (
+                "SAFETY_EQUIPMENT",
+                raw.safety_equipment.as_deref(),
+                is_unmapped(raw.safety_equipment.as_deref(), &mappings.safety_equipment),
+            ),
+            (
+                "PED_LOCATION",
+                raw.ped_location.as_deref(),
+                is_unmapped(raw.ped_location.as_deref(), &mappings.ped_location),
+            ),
+            (
+                "PED_ACTION",
+                raw.ped_action.as_deref(),
+                is_unmapped(raw.ped_action.as_deref(), &mappings.ped_action),
+            ),
+            (
+                "CONTRIBUTING_FACTOR_1",
+                raw.contributing_factor_1.as_deref(),
+                raw.contributing_factor_1
+                    .as_deref()
+                    .is_some_and(|f| is_unmapped_factor(f, &mappings.contributing_factor)),
+            ),
+            (
+                "CONTRIBUTING_FACTOR_2",
+                raw.contributing_factor_2.as_deref(),
+                raw.contributing_factor_2
+                    .as_deref()
+                    .is_some_and(|f| is_unmapped_factor(f, &mappings.contributing_factor)),
+            ),
+        ];
+        let factors = [
+            ("CONTRIBUTING_FACTOR_1", &raw.contributing_factor_1),
+            ("CONTRIBUTING_FACTOR_2", &raw.contributing_factor_2),
+        ];
+        for (column, value) in factors {
+            if let Some(value) = value
+                && value.eq_ignore_ascii_case(UNSPECIFIED)
+            {
+                rejects.record_explicit_unknown(RejectSource::Persons, column, value);
+            }
+        }
+        for (column, value, is_unmapped) in unmapped {
+            let Some(value) = value.filter(|_| is_unmapped) else {
+                continue;
+            };
+            let at = RejectLocation {
+                line: raw.line,
+                column,
+                value,
+            };
+            rejects.reject_record(
+                RejectSource::Persons,
+                RejectReason::UnknownCategory,
+                at,
+                &raw,
+            );
+        }
+
+        let implausible = known_age(raw.person_age).and_then(|age| {
+            implausible_age(
+                age,
+                raw.person_position_in_vehicle
+                    .as_deref()
+                    .and_then(|v| mappings.position_in_vehicle.get(v)),
+                raw.person_ped_role
+                    .as_deref()
+                    .and_then(|v| mappings.person_role.get(v)),
+                ages,
+            )
+        });
+        if let (Some(reason), Some(age)) = (implausible, known_age(raw.person_age)) {
+            let value = age.to_string();
+            let at = RejectLocation {
+                line: raw.line,
+                column: "PERSON_AGE",
+                value: &value,
+            };
+            rejects.reject_record(RejectSource::Persons, reason, at, &raw);
+        }
+        let mut person = Self::convert(raw, mappings);
+        if implausible.is_some() {
+            person.person_age = None;
+        }
+        person
+    }
+}
+
+/// Why `age` is not believable, if it is not: outside the plausible range,
+/// or a driver — by position or role — younger than the minimum driver age.
+fn implausible_age(
+    age: u8,
+    position: Option<PersonPositionInVehicle>,
+    role: Option<PersonRole>,
+    ages: &AgePlausibilityConfig,
+) -> Option<RejectReason> {
+    let is_driver =
+        position == Some(PersonPositionInVehicle::Driver) || role == Some(PersonRole::Driver);
+    if !(ages.min_age..=ages.max_age).contains(&age) {
+        Some(RejectReason::ImplausibleAge)
+    } else if is_driver && age < ages.min_driver_age {
+        Some(RejectReason::UnderageDriver)
+    } else {
+        None
+    }
+}
+
+impl Person {
+    fn convert(raw: RawPersonRecord, mappings: &Mappings) -> Self {
         Person {
-            person_id: raw.unique_id.parse().unwrap(),
-            person_type: raw.person_type.as_deref().and_then(extract_person_type),
-            person_age: raw.person_age,
-            person_sex: raw.person_sex.and_then(extract_sex),
+            person_id: raw.unique_id,
+            person_type: raw
+                .person_type
+                .as_deref()
+                .and_then(|v| mappings.person_type.get(v)),
+            person_age: known_age(raw.person_age),
+            person_sex: raw
+                .person_sex
+                .map(String::from)
+                .and_then(|s| mappings.person_sex.get(&s)),
             person_position_in_vehicle: raw
                 .person_position_in_vehicle
                 .as_deref()
-                .and_then(extract_position_in_vehicle),
-            person_role: raw.person_ped_role.as_deref().and_then(extract_role),
+                .and_then(|v| mappings.position_in_vehicle.get(v)),
+            person_role: raw
+                .person_ped_role
+                .as_deref()
+                .and_then(|v| mappings.person_role.get(v)),
+            person_injury: raw
+                .person_injury
+                .as_deref()
+                .and_then(|v| mappings.person_injury.get(v)),
+            ejection: raw
+                .ejection
+                .as_deref()
+                .and_then(|v| mappings.ejection.get(v)),
+            emotional_status: raw
+                .emotional_status
+                .as_deref()
+                .and_then(|v| mappings.emotional_status.get(v)),
+            bodily_injury: raw
+                .bodily_injury
+                .as_deref()
+                .and_then(|v| mappings.bodily_injury.get(v)),
+            safety_equipment: raw
+                .safety_equipment
+                .as_deref()
+                .and_then(|v| mappings.safety_equipment.get(v)),
+            ped_location: raw
+                .ped_location
+                .as_deref()
+                .and_then(|v| mappings.ped_location.get(v)),
+            ped_action: raw
+                .ped_action
+                .as_deref()
+                .and_then(|v| mappings.ped_action.get(v)),
             crash_id: raw.collision_id,
+            vehicle_id: raw.vehicle_id,
+            person_factors: [&raw.contributing_factor_1, &raw.contributing_factor_2]
+                .into_iter()
+                .flatten()
+                .filter_map(|f| mappings.contributing_factor.get(f))
+                .collect(),
         }
     }
 }
 
-fn extract_person_type(raw_person_type: &str) -> Option<PersonType> {
-    match raw_person_type {
-        "Pedestrian" => Some(PersonType::Pedestrian),
-        "Occupant" => Some(PersonType::Occupant),
-        "Bicyclist" => Some(PersonType::Bicyclist),
-        "Other Motorized" => Some(PersonType::OtherMotorized),
-        _ => None,
-    }
+/// Values NYPD records when an attribute is not known or not applicable,
+/// e.g. the ejection of a pedestrian; they are unknown but not unmapped.
+const PLACEHOLDERS: [&str; 2] = ["Does Not Apply", "Unknown"];
+
+/// The sex NYPD records when the sex is not known.
+const UNKNOWN_SEX: &str = "U";
+
+/// The age NYPD records when the age is not known.
+const UNKNOWN_AGE: u8 = 0;
+
+fn known_age(age: Option<u8>) -> Option<u8> {
+    age.filter(|&age| age != UNKNOWN_AGE)
 }
 
-fn extract_sex(c: char) -> Option<PersonSex> {
-    match c {
-        'F' => Some(PersonSex::Female),
-        'M' => Some(PersonSex::Male),
-        _ => None,
-    }
+fn is_unmapped<T: DeserializeOwned + Copy>(value: Option<&str>, mapping: &Mapping<T>) -> bool {
+    value.is_some_and(|v| !PLACEHOLDERS.contains(&v) && mapping.get(v).is_none())
 }
 
-fn extract_position_in_vehicle(raw_position: &str) -> Option<PersonPositionInVehicle> {
-    match raw_position {
-        "Driver" => Some(PersonPositionInVehicle::Driver),
-        "Front passenger, if two or more persons, including the driver, are in the front seat"
-        | "Middle front seat, or passenger lying across a seat" => {
-            Some(PersonPositionInVehicle::Front)
-        }
-        "Any person in the rear of a station wagon, pick-up truck, all passengers on a bus, etc"
-        | "Middle rear seat, or passenger lying across a seat"
-        | "Right rear passenger or motorcycle sidecar passenger"
-        | "Left rear passenger, or rear passenger on a bicycle, motorcycle, snowmobile" => {
-            Some(PersonPositionInVehicle::Rear)
-        }
-        "If one person is seated on another person&apos;s lap" => {
-            Some(PersonPositionInVehicle::Lap)
+fn is_unmapped_factor(value: &str, mapping: &Mapping<CrashFactor>) -> bool {
+    !value.eq_ignore_ascii_case(UNSPECIFIED) && mapping.get(value).is_none()
+}
+
+/// Position of each vehicle within its crash, used to match a person's
+/// vehicle to the crash's `CONTRIBUTING FACTOR VEHICLE n` columns.
+///
+/// The crash record does not carry vehicle ids, so the position is inferred:
+/// vehicles are numbered in ascending `VEHICLE_ID` order, which follows the
+/// order in which NYPD entered them on the report. Every vehicle of the crash
+/// has to be inserted, also one without persons, or the later ones shift.
+#[derive(Debug, Clone, Default)]
+pub struct VehicleOrdinals {
+    vehicles_by_crash: HashMap<u32, Vec<u32>>,
+}
+
+impl VehicleOrdinals {
+    pub fn insert(&mut self, crash_id: u32, vehicle_id: u32) {
+        let vehicles = self.vehicles_by_crash.entry(crash_id).or_default();
+        if let Err(i) = vehicles.binary_search(&vehicle_id) {
+            vehicles.insert(i, vehicle_id);
         }
-        "Outside" | "Riding/Hanging on Outside" => Some(PersonPositionInVehicle::Outside),
-        _ => None,
+    }
+
+    /// 0-based position of `vehicle_id` among the vehicles of `crash_id`.
+    pub fn ordinal(&self, crash_id: u32, vehicle_id: u32) -> Option<usize> {
+        self.vehicles_by_crash
+            .get(&crash_id)?
+            .binary_search(&vehicle_id)
+            .ok()
     }
 }
 
-fn extract_role(raw_role: &str) -> Option<PersonRole> {
-    match raw_role {
-        "Notified Person" => Some(PersonRole::NotifiedPerson),
-        "Witness" => Some(PersonRole::Witness),
-        "Registrant" => Some(PersonRole::Registrant),
-        "In-Line Skater" => Some(PersonRole::InLineSkater),
-        "Passenger" => Some(PersonRole::Passenger),
-        "Driver" => Some(PersonRole::Driver),
-        "Policy Holder" => Some(PersonRole::PolicyHolder),
-        "Owner" => Some(PersonRole::Owner),
-        "Pedestrian" => Some(PersonRole::Pedestrian),
-        _ => None,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw::{ColumnAliases, RawReader};
+
+    #[test]
+    fn implausible_ages() {
+        let ages = AgePlausibilityConfig::default();
+        let driver = Some(PersonPositionInVehicle::Driver);
+        let rear = Some(PersonPositionInVehicle::Rear);
+        assert_eq!(
+            implausible_age(255, None, Some(PersonRole::Pedestrian), &ages),
+            Some(RejectReason::ImplausibleAge)
+        );
+        assert_eq!(
+            implausible_age(3, driver, None, &ages),
+            Some(RejectReason::UnderageDriver)
+        );
+        assert_eq!(
+            implausible_age(3, None, Some(PersonRole::Driver), &ages),
+            Some(RejectReason::UnderageDriver)
+        );
+        assert_eq!(
+            implausible_age(3, rear, Some(PersonRole::Passenger), &ages),
+            None
+        );
+        assert_eq!(implausible_age(14, driver, None, &ages), None);
+    }
+
+    #[test]
+    fn age_zero_is_unknown_without_a_reject() {
+        let raw = RawPersonRecord::read_csv("data/persons_preview.csv", &ColumnAliases::default())
+            .and_then(RawReader::strict)
+            .unwrap()
+            .remove(0);
+        let ages = AgePlausibilityConfig::default();
+        let implausible_ages = |rejects: &Rejects| {
+            rejects
+                .summary()
+                .filter(|&(_, reason, _)| reason == RejectReason::ImplausibleAge)
+                .map(|(_, _, count)| count)
+                .sum::<u64>()
+        };
+
+        let mut rejects = Rejects::default();
+        let unknown = RawPersonRecord {
+            person_age: Some(0),
+            ..raw.clone()
+        };
+        let person = Person::from_raw(unknown, &Mappings::default(), &ages, &mut rejects);
+        assert_eq!(person.person_age, None);
+        assert_eq!(implausible_ages(&rejects), 0);
+
+        let too_old = RawPersonRecord {
+            person_age: Some(150),
+            ..raw
+        };
+        let person = Person::from_raw(too_old, &Mappings::default(), &ages, &mut rejects);
+        assert_eq!(person.person_age, None);
+        assert_eq!(implausible_ages(&rejects), 1);
     }
 }
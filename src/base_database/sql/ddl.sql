@@ -1,12 +1,22 @@
 CREATE TABLE dbo.[Time] (
     time_id            INT            NOT NULL,
-    [timestamp]        DATETIMEOFFSET(0) NOT NULL, -- RFC3339 compatible
-    moon_phase         VARCHAR(20)    NULL,
+    [timestamp]        DATETIMEOFFSET(0) NOT NULL, -- RFC3339 compatible, New York local time
+    dst_flag           VARCHAR(15)    NOT NULL,
+    moon_phase         VARCHAR(20)    NOT NULL,
+    moon_phase_angle   DECIMAL(6,3)   NOT NULL, -- degrees, 0 = full, 180 = new
+    moon_illumination  DECIMAL(4,3)   NOT NULL,
+    moon_age_days      DECIMAL(4,2)   NOT NULL,
     weather            VARCHAR(20)    NULL,
 
     CONSTRAINT PK_Time PRIMARY KEY CLUSTERED (time_id),
 
-    CONSTRAINT CK_Time_MoonPhase CHECK (moon_phase IS NULL OR moon_phase IN (
+    CONSTRAINT CK_Time_DstFlag CHECK (dst_flag IN (
+        'NORMAL',
+        'AMBIGUOUS',
+        'NON_EXISTENT'
+    )),
+
+    CONSTRAINT CK_Time_MoonPhase CHECK (moon_phase IN (
         'NEW',
         'WAXING_CRESCENT',
         'FIRST_QUARTER',
@@ -45,6 +55,15 @@ CREATE TABLE dbo.Crash (
     crash_cyclist_killed               SMALLINT          NOT NULL,
     crash_motorist_injured             SMALLINT          NOT NULL,
     crash_motorist_killed              SMALLINT          NOT NULL,
+    borough                            VARCHAR(15)       NULL,
+    borough_source                     VARCHAR(10)       NULL,   -- REPORTED / INFERRED
+    zip_code                           CHAR(5)           NULL,
+    zip_code_source                    VARCHAR(10)       NULL,
+    latitude                           DECIMAL(9,6)      NULL,
+    longitude                          DECIMAL(9,6)      NULL,
+    on_street_name                     VARCHAR(100)      NULL,
+    cross_street_name                  VARCHAR(100)      NULL,
+    off_street_name                    VARCHAR(100)      NULL,
     crash_factor                       VARCHAR(60)       NULL,
     time_id                            INT               NULL,
 
@@ -65,6 +84,25 @@ CREATE TABLE dbo.Crash (
         crash_motorist_killed >= 0
     ),
 
+    CONSTRAINT CK_Crash_Borough CHECK (borough IS NULL OR borough IN (
+        'BRONX',
+        'BROOKLYN',
+        'MANHATTAN',
+        'QUEENS',
+        'STATEN_ISLAND'
+    )),
+
+    CONSTRAINT CK_Crash_Provenance CHECK (
+        (borough_source IS NULL OR borough_source IN ('REPORTED', 'INFERRED')) AND
+        (zip_code_source IS NULL OR zip_code_source IN ('REPORTED', 'INFERRED'))
+    ),
+
+    -- NYC bounding box; positions outside it are stored as NULL
+    CONSTRAINT CK_Crash_Coordinates CHECK (
+        (latitude IS NULL AND longitude IS NULL) OR
+        (latitude BETWEEN 40.49 AND 40.92 AND longitude BETWEEN -74.27 AND -73.68)
+    ),
+
     CONSTRAINT CK_Crash_Factor CHECK (crash_factor IS NULL OR crash_factor IN (
         'DRIVERLESS_RUNAWAY_VEHICLE',
         'LISTENING_USING_HEADPHONES',
@@ -138,7 +176,15 @@ CREATE TABLE dbo.Person (
     person_sex                  VARCHAR(10)   NULL,
     person_position_in_vehicle  VARCHAR(20)   NULL,
     person_role                 VARCHAR(30)   NULL,
+    person_injury               VARCHAR(15)   NULL,
+    ejection                    VARCHAR(20)   NULL,
+    emotional_status            VARCHAR(15)   NULL,
+    bodily_injury               VARCHAR(25)   NULL,
+    safety_equipment            VARCHAR(25)   NULL,
+    ped_location                VARCHAR(20)   NULL,
+    ped_action                  VARCHAR(40)   NULL,
     crash_id                    INT           NOT NULL,
+    vehicle_id                  INT           NULL,
 
     CONSTRAINT PK_Person PRIMARY KEY CLUSTERED (person_id),
 
@@ -181,7 +227,130 @@ CREATE TABLE dbo.Person (
         'POLICY_HOLDER',
         'OWNER',
         'PEDESTRIAN'
+    )),
+
+    CONSTRAINT CK_Person_Injury CHECK (person_injury IS NULL OR person_injury IN (
+        'KILLED',
+        'INJURED',
+        'UNSPECIFIED'
+    )),
+
+    CONSTRAINT CK_Person_Ejection CHECK (ejection IS NULL OR ejection IN (
+        'NOT_EJECTED',
+        'PARTIALLY_EJECTED',
+        'EJECTED',
+        'TRAPPED'
+    )),
+
+    CONSTRAINT CK_Person_EmotionalStatus CHECK (emotional_status IS NULL OR emotional_status IN (
+        'CONSCIOUS',
+        'SHOCK',
+        'INCOHERENT',
+        'SEMI_CONSCIOUS',
+        'UNCONSCIOUS',
+        'APPARENT_DEATH'
+    )),
+
+    CONSTRAINT CK_Person_BodilyInjury CHECK (bodily_injury IS NULL OR bodily_injury IN (
+        'HEAD',
+        'FACE',
+        'EYE',
+        'NECK',
+        'CHEST',
+        'BACK',
+        'ABDOMEN_PELVIS',
+        'SHOULDER_UPPER_ARM',
+        'ELBOW_LOWER_ARM_HAND',
+        'HIP_UPPER_LEG',
+        'KNEE_LOWER_LEG_FOOT',
+        'ENTIRE_BODY'
+    )),
+
+    CONSTRAINT CK_Person_SafetyEquipment CHECK (safety_equipment IS NULL OR safety_equipment IN (
+        'NO_EQUIPMENT',
+        'LAP_BELT',
+        'HARNESS',
+        'LAP_BELT_HARNESS',
+        'CHILD_RESTRAINT',
+        'AIR_BAG',
+        'AIR_BAG_LAP_BELT',
+        'AIR_BAG_LAP_BELT_HARNESS',
+        'AIR_BAG_CHILD_RESTRAINT',
+        'HELMET_MOTORCYCLE',
+        'HELMET_BICYCLE',
+        'HELMET_OTHER_BICYCLE',
+        'PADS_BICYCLE',
+        'STOPPERS_BICYCLE',
+        'OTHER'
+    )),
+
+    CONSTRAINT CK_Person_PedLocation CHECK (ped_location IS NULL OR ped_location IN (
+        'AT_INTERSECTION',
+        'NOT_AT_INTERSECTION'
+    )),
+
+    CONSTRAINT CK_Person_PedAction CHECK (ped_action IS NULL OR ped_action IN (
+        'CROSSING_WITH_SIGNAL',
+        'CROSSING_AGAINST_SIGNAL',
+        'CROSSING_NO_SIGNAL_MARKED_CROSSWALK',
+        'CROSSING_NO_SIGNAL_OR_CROSSWALK',
+        'ALONG_HIGHWAY_WITH_TRAFFIC',
+        'ALONG_HIGHWAY_AGAINST_TRAFFIC',
+        'EMERGING_FROM_PARKED_VEHICLE',
+        'GOING_TO_FROM_SCHOOL_BUS',
+        'GETTING_ON_OFF_VEHICLE',
+        'PUSHING_WORKING_ON_CAR',
+        'WORKING_IN_ROADWAY',
+        'PLAYING_IN_ROADWAY',
+        'OTHER_ACTIONS_IN_ROADWAY',
+        'NOT_IN_ROADWAY'
     ))
 );
 
 CREATE INDEX IX_Person_CrashId ON dbo.Person (crash_id);
+CREATE INDEX IX_Person_VehicleId ON dbo.Person (vehicle_id);
+
+
+------------------
+
+
+CREATE TABLE dbo.Vehicle (
+    vehicle_id                  INT           NOT NULL,
+    crash_id                    INT           NOT NULL,
+    vehicle_type                VARCHAR(20)   NULL,
+
+    CONSTRAINT PK_Vehicle PRIMARY KEY CLUSTERED (vehicle_id),
+
+    CONSTRAINT FK_Vehicle_Crash
+        FOREIGN KEY (crash_id)
+        REFERENCES dbo.Crash(crash_id)
+        ON DELETE CASCADE,
+
+    CONSTRAINT CK_Vehicle_Type CHECK (vehicle_type IS NULL OR vehicle_type IN (
+        'SEDAN',
+        'STATION_WAGON_SUV',
+        'CONVERTIBLE',
+        'TAXI',
+        'VAN',
+        'PICK_UP_TRUCK',
+        'BOX_TRUCK',
+        'TRACTOR_TRUCK',
+        'DUMP_TRUCK',
+        'GARBAGE_TRUCK',
+        'TOW_TRUCK',
+        'TANKER',
+        'FLAT_BED',
+        'OTHER_TRUCK',
+        'BUS',
+        'BICYCLE',
+        'E_BIKE',
+        'E_SCOOTER',
+        'MOPED',
+        'MOTORCYCLE',
+        'AMBULANCE',
+        'FIRE_TRUCK',
+        'PEDICAB'
+    ))
+);
+
+CREATE INDEX IX_Vehicle_CrashId ON dbo.Vehicle (crash_id);
@@ -1,18 +1,41 @@
+use crate::astro::moon::{MoonState, SYNODIC_MONTH_DAYS};
+use crate::astro::sun::{self, CIVIL_TWILIGHT_ALTITUDE, SUNRISE_ALTITUDE};
+use crate::base_database::location::Coordinates;
+use crate::base_database::timezone::{self, DstFlag};
+use crate::base_database::weather_rules::WeatherRules;
+use crate::config::MoonPhaseConfig;
 use crate::raw::{moon::RawMoonRecord, weather::RawWeatherRecord};
-use itertools::Itertools;
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap};
-use time::{Date, Duration, PrimitiveDateTime, macros::date};
+use std::collections::{HashMap, HashSet};
+use time::{Date, Duration, OffsetDateTime, PrimitiveDateTime, UtcOffset};
 
 #[derive(Debug, Clone)]
 pub struct Time {
     pub time_id: u32,
+    /// New York local wall-clock hour, the same clock crash times are reported in.
     pub timestamp: PrimitiveDateTime,
-    pub moon_phase: Option<MoonPhase>,
+    /// Offset of the instant `timestamp` was resolved to (see [`timezone::resolve`]).
+    pub utc_offset: UtcOffset,
+    pub dst_flag: DstFlag,
+    pub moon_phase: MoonPhase,
+    /// Degrees, 0 at full moon and 180 at new moon.
+    pub moon_phase_angle: f64,
+    /// Illuminated fraction of the lunar disk, 0–1.
+    pub moon_illumination: f64,
+    /// Days since the last new moon.
+    pub moon_age_days: f64,
+    /// Degrees the Sun stands above the horizon at City Hall, half past the
+    /// hour.
+    pub sun_altitude: f64,
+    pub light_condition: LightCondition,
     pub weather: Option<Weather>,
+    /// Id of the [`WeatherRules`] rule that classified `weather`.
+    pub weather_rule_id: Option<String>,
+    pub weather_measures: Option<WeatherMeasures>,
+    pub snow_risk: Option<SnowRisk>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum MoonPhase {
     New,
@@ -25,6 +48,44 @@ pub enum MoonPhase {
     WaningCrescent,
 }
 
+/// Natural light during an hour, by the altitude of the Sun's centre.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LightCondition {
+    /// The Sun is up for the whole hour.
+    Daylight,
+    /// Some of the hour is civil twilight — the Sun less than 6° below the
+    /// horizon — or the Sun rises or sets during it.
+    CivilTwilight,
+    /// The Sun is more than 6° below the horizon for the whole hour.
+    Night,
+}
+
+impl LightCondition {
+    /// Classifies the hour starting at `instant` at City Hall.
+    ///
+    /// Within an hour the altitude rises or falls monotonically except around
+    /// noon and midnight, far from the thresholds, so the altitudes at the
+    /// start and end of the hour decide.
+    pub fn of_hour(instant: OffsetDateTime) -> Self {
+        let altitude = |instant| {
+            sun::altitude(
+                instant,
+                Coordinates::CITY_HALL.latitude,
+                Coordinates::CITY_HALL.longitude,
+            )
+        };
+        let (start, end) = (altitude(instant), altitude(instant + Duration::hours(1)));
+        if start.min(end) > SUNRISE_ALTITUDE {
+            LightCondition::Daylight
+        } else if start.max(end) <= CIVIL_TWILIGHT_ALTITUDE {
+            LightCondition::Night
+        } else {
+            LightCondition::CivilTwilight
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Weather {
@@ -37,105 +98,297 @@ pub enum Weather {
     Miscallaneous,
 }
 
-/// | Condition   | Logic                      |
-/// | ----------- | -------------------------- |
-/// | Clear       | Cloud < 20% AND Precip = 0 |
-/// | Cloudy      | Cloud ≥ 60% AND Precip = 0 |
-/// | Rainy Light | Rain ≤ 2.5                 |
-/// | Rainy Heavy | Rain > 2.5                 |
-/// | Stormy      | Rain > 7.5 AND Wind > 40   |
-/// | Windy       | Wind > 40 AND Rain = 0     |
-impl From<&RawWeatherRecord> for Weather {
+/// The hourly observation behind [`Weather`], as reported.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeatherMeasures {
+    /// °C at 2 m.
+    pub temperature: f32,
+    /// mm of rain, showers and snow (water equivalent).
+    pub precipitation: f32,
+    /// mm of rain and showers.
+    pub rain: f32,
+    /// % of the sky.
+    pub cloudcover: f32,
+    /// km/h at 10 m.
+    pub windspeed: f32,
+}
+
+impl From<&RawWeatherRecord> for WeatherMeasures {
     fn from(raw_weather: &RawWeatherRecord) -> Self {
-        if raw_weather.precipitation == 0.0 && raw_weather.rain == 0.0 {
-            if raw_weather.cloudcover < 20.0 {
-                Weather::Clear
-            } else if raw_weather.cloudcover >= 60.0 {
-                Weather::Cloudy
-            } else {
-                Weather::Miscallaneous
-            }
-        } else if raw_weather.rain <= 2.5 {
-            Weather::RainyLight
-        } else if raw_weather.rain > 2.5 {
-            if raw_weather.windspeed > 40.0 {
-                Weather::Stormy
-            } else {
-                Weather::RainyHeavy
-            }
-        } else if raw_weather.windspeed > 40.0 && raw_weather.rain == 0.0 {
-            Weather::Windy
+        WeatherMeasures {
+            temperature: raw_weather.temperature,
+            precipitation: raw_weather.precipitation,
+            rain: raw_weather.rain,
+            cloudcover: raw_weather.cloudcover,
+            windspeed: raw_weather.windspeed,
+        }
+    }
+}
+
+/// Risk of snow or ice on the road, which [`Weather`] does not tell apart
+/// from rain and clear skies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SnowRisk {
+    /// Above freezing, or freezing without precipitation.
+    NoRisk,
+    /// At or below freezing, dry: ice from earlier precipitation.
+    Frost,
+    /// Rain at or below freezing.
+    FreezingRain,
+    /// Part of the precipitation fell as snow.
+    Snow,
+}
+
+/// Temperature at or below which wet roads freeze, °C.
+pub const FREEZING_TEMPERATURE: f32 = 0.0;
+
+/// Precipitation beyond rain that counts as snowfall, mm; smaller
+/// differences are rounding in the source.
+pub const MIN_SNOWFALL: f32 = 0.05;
+
+/// | Condition     | Logic                              |
+/// | ------------- | ---------------------------------- |
+/// | Snow          | Precip − Rain ≥ 0.05               |
+/// | Freezing Rain | Rain > 0 AND Temp ≤ 0              |
+/// | Frost         | Temp ≤ 0                           |
+/// | No Risk       | otherwise                          |
+impl From<&WeatherMeasures> for SnowRisk {
+    fn from(measures: &WeatherMeasures) -> Self {
+        let freezing = measures.temperature <= FREEZING_TEMPERATURE;
+        if measures.precipitation - measures.rain >= MIN_SNOWFALL {
+            SnowRisk::Snow
+        } else if freezing && measures.rain > 0.0 {
+            SnowRisk::FreezingRain
+        } else if freezing {
+            SnowRisk::Frost
         } else {
-            Weather::Miscallaneous
+            SnowRisk::NoRisk
         }
     }
 }
 
 impl Time {
-    pub fn from(raw_weather: Vec<RawWeatherRecord>, raw_moon: Vec<RawMoonRecord>) -> Vec<Time> {
-        let moon = extract_moon_phases(raw_moon);
+    /// Builds one row per New York local hour from `first_day` to `last_day`
+    /// (inclusive); hours without weather data get `None` for the weather,
+    /// its rule, its measures and the snow risk. The weather is classified by
+    /// the first of `weather_rules` that matches.
+    ///
+    /// Weather is reported in UTC and is looked up by the instant each local
+    /// hour resolves to, so the skipped spring-forward hour gets the weather of
+    /// the following UTC hour and the repeated fall-back hour gets the weather
+    /// of its first occurrence. Moon attributes are computed for the same
+    /// instant by [`MoonState`], the light condition for the hour it starts.
+    pub fn from(
+        raw_weather: Vec<RawWeatherRecord>,
+        (first_day, last_day): (Date, Date),
+        moon_config: &MoonPhaseConfig,
+        weather_rules: &WeatherRules,
+    ) -> Vec<Time> {
         let weather = raw_weather
             .into_iter()
-            .map(|raw_weather| {
-                (
-                    PrimitiveDateTime::new(raw_weather.time.date(), raw_weather.time.time()),
-                    raw_weather,
-                )
-            })
+            .map(|raw_weather| (raw_weather.time.to_offset(UtcOffset::UTC), raw_weather))
             .collect::<HashMap<_, _>>();
 
-        let start = PrimitiveDateTime::new(date!(2016 - 01 - 01), time::macros::time!(0:00));
-        let end = PrimitiveDateTime::new(date!(2022 - 12 - 31), time::macros::time!(23:59));
+        let start = PrimitiveDateTime::new(first_day, time::macros::time!(0:00));
+        let end = PrimitiveDateTime::new(last_day, time::macros::time!(23:59));
         std::iter::successors(Some(start), move |&dt| {
             let next = dt + Duration::hours(1);
             if next <= end { Some(next) } else { None }
         })
         .enumerate()
         .map(|(i, timestamp)| {
-            let moon = moon.get(&timestamp.date());
-            let weather = weather.get(&timestamp);
+            let (instant, dst_flag) = timezone::resolve(timestamp);
+            let moon = MoonState::at(instant);
+            let sun_altitude = sun::altitude(
+                instant + Duration::minutes(30),
+                Coordinates::CITY_HALL.latitude,
+                Coordinates::CITY_HALL.longitude,
+            );
+            let weather = weather.get(&instant.to_offset(UtcOffset::UTC));
+            let weather_measures = weather.map(WeatherMeasures::from);
+            let weather_rule = weather_measures
+                .as_ref()
+                .and_then(|m| weather_rules.classify(m));
             Time {
                 time_id: i as u32,
                 timestamp,
-                moon_phase: moon.copied(),
-                weather: weather.map(Weather::from),
+                utc_offset: instant.offset(),
+                dst_flag,
+                moon_phase: MoonPhase::from_state(&moon, moon_config.primary_window_days),
+                moon_phase_angle: moon.phase_angle,
+                moon_illumination: moon.illumination,
+                moon_age_days: moon.age_days,
+                sun_altitude,
+                light_condition: LightCondition::of_hour(instant),
+                weather: weather_rule.map(|rule| rule.weather),
+                weather_rule_id: weather_rule.map(|rule| rule.id.clone()),
+                snow_risk: weather_measures.as_ref().map(SnowRisk::from),
+                weather_measures,
             }
         })
         .collect()
     }
 }
 
-fn extract_moon_phases(raw_moon: Vec<RawMoonRecord>) -> HashMap<Date, MoonPhase> {
-    let moon_change = raw_moon
+impl MoonPhase {
+    /// Classifies the moon into one of the eight phases.
+    ///
+    /// A primary phase (new, first quarter, full, last quarter) covers the
+    /// event day — half a day on either side of the exact event — widened by
+    /// `primary_window_days`; the stretches in between are the intermediate
+    /// phases.
+    pub fn from_state(moon: &MoonState, primary_window_days: u8) -> MoonPhase {
+        const PRIMARY: [(f64, MoonPhase); 5] = [
+            (0.0, MoonPhase::New),
+            (90.0, MoonPhase::FirstQuarter),
+            (180.0, MoonPhase::Full),
+            (270.0, MoonPhase::LastQuarter),
+            (360.0, MoonPhase::New),
+        ];
+        let window = (primary_window_days as f64 + 0.5) * 360.0 / SYNODIC_MONTH_DAYS;
+        if let Some(&(_, phase)) = PRIMARY
+            .iter()
+            .find(|(angle, _)| (moon.elongation - angle).abs() <= window)
+        {
+            return phase;
+        }
+        match moon.elongation {
+            e if e < 90.0 => MoonPhase::WaxingCrescent,
+            e if e < 180.0 => MoonPhase::WaxingGibbous,
+            e if e < 270.0 => MoonPhase::WaningGibbous,
+            _ => MoonPhase::WaningCrescent,
+        }
+    }
+}
+
+/// Outcome of comparing the computed moon phases with the moon CSV.
+#[derive(Debug, Default)]
+pub struct MoonCrossCheck {
+    /// Primary events of the CSV that fall into the time range.
+    pub events: usize,
+    /// Events for which no time row near that date has the listed phase.
+    pub mismatches: Vec<(Date, MoonPhase)>,
+}
+
+/// Checks every primary event of the moon CSV against the computed phases.
+///
+/// The CSV lists dates in UTC+7, so an event counts as confirmed if the phase
+/// occurs on its date or on one of the neighbouring local dates.
+pub fn cross_check_moon(times: &[Time], raw_moon: &[RawMoonRecord]) -> MoonCrossCheck {
+    let computed: HashSet<(Date, MoonPhase)> = times
+        .iter()
+        .map(|t| (t.timestamp.date(), t.moon_phase))
+        .collect();
+    let covered: HashSet<Date> = computed.iter().map(|(date, _)| *date).collect();
+
+    let mut check = MoonCrossCheck::default();
+    let events = raw_moon.iter().flat_map(|r| {
+        [
+            (r.new_moon, MoonPhase::New),
+            (r.first_quarter_moon, MoonPhase::FirstQuarter),
+            (r.full_moon, MoonPhase::Full),
+            (r.third_quarter_moon, MoonPhase::LastQuarter),
+        ]
         .into_iter()
-        .flat_map(|moon_record| {
-            let mut moon_phases = BTreeMap::new();
-            if let Some(date) = moon_record.new_moon {
-                moon_phases.insert(date, MoonPhase::New);
-            }
-            if let Some(date) = moon_record.first_quarter_moon {
-                moon_phases.insert(date, MoonPhase::FirstQuarter);
-            }
-            if let Some(date) = moon_record.full_moon {
-                moon_phases.insert(date, MoonPhase::Full);
-            }
-            if let Some(date) = moon_record.third_quarter_moon {
-                moon_phases.insert(date, MoonPhase::LastQuarter);
-            }
-            moon_phases
-        })
-        .collect::<BTreeMap<_, _>>();
+        .filter_map(|(date, phase)| Some((date?, phase)))
+    });
+    for (date, phase) in events.filter(|(date, _)| covered.contains(date)) {
+        check.events += 1;
+        let confirmed = [date - Duration::days(1), date, date + Duration::days(1)]
+            .iter()
+            .any(|d| computed.contains(&(*d, phase)));
+        if !confirmed {
+            check.mismatches.push((date, phase));
+        }
+    }
+    check
+}
 
-    let mut moon = HashMap::new();
-    for ((date1, phase1), (date2, _)) in moon_change.into_iter().tuple_windows() {
-        std::iter::successors(Some(date1), move |&dt| {
-            let next = dt + Duration::days(1);
-            if next < date2 { Some(next) } else { None }
-        })
-        .for_each(|date| {
-            moon.insert(date, phase1);
-        });
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime};
+
+    #[test]
+    fn phases_follow_the_lunation() {
+        let phase = |instant| MoonPhase::from_state(&MoonState::at(instant), 1);
+        // new moon 2016-01-10 01:30 UTC, first quarter 2016-01-16 23:26 UTC
+        assert_eq!(phase(datetime!(2016-01-10 01:00 UTC)), MoonPhase::New);
+        assert_eq!(phase(datetime!(2016-01-11 12:00 UTC)), MoonPhase::New);
+        assert_eq!(
+            phase(datetime!(2016-01-13 12:00 UTC)),
+            MoonPhase::WaxingCrescent
+        );
+        assert_eq!(
+            phase(datetime!(2016-01-17 00:00 UTC)),
+            MoonPhase::FirstQuarter
+        );
+        assert_eq!(
+            phase(datetime!(2016-01-20 12:00 UTC)),
+            MoonPhase::WaxingGibbous
+        );
     }
 
-    moon
+    #[test]
+    fn light_follows_local_clock_time() {
+        let light = |day| -> Vec<LightCondition> {
+            Time::from(
+                vec![],
+                (day, day),
+                &MoonPhaseConfig::default(),
+                &WeatherRules::default(),
+            )
+            .iter()
+            .map(|t| t.light_condition)
+            .collect()
+        };
+        // civil dawn 06:49, sunrise 07:20 EST
+        let winter = light(date!(2021 - 01 - 01));
+        assert_eq!(winter[5], LightCondition::Night);
+        assert_eq!(winter[6], LightCondition::CivilTwilight);
+        assert_eq!(winter[7], LightCondition::CivilTwilight);
+        assert_eq!(winter[8], LightCondition::Daylight);
+        // civil dawn 04:52, sunrise 05:25, sunset 20:31, civil dusk 21:04 EDT
+        let summer = light(date!(2021 - 06 - 21));
+        assert_eq!(summer[3], LightCondition::Night);
+        assert_eq!(summer[6], LightCondition::Daylight);
+        assert_eq!(summer[21], LightCondition::CivilTwilight);
+        assert_eq!(summer[22], LightCondition::Night);
+    }
+
+    #[test]
+    fn snow_risk_separates_snow_from_rain() {
+        let measures = |temperature, precipitation, rain| WeatherMeasures {
+            temperature,
+            precipitation,
+            rain,
+            cloudcover: 100.0,
+            windspeed: 10.0,
+        };
+        let risk = |m: WeatherMeasures| SnowRisk::from(&m);
+        assert_eq!(risk(measures(-2.0, 1.2, 0.0)), SnowRisk::Snow);
+        // sleet: rain and snow in the same hour
+        assert_eq!(risk(measures(0.5, 1.2, 0.6)), SnowRisk::Snow);
+        assert_eq!(risk(measures(-0.5, 0.4, 0.4)), SnowRisk::FreezingRain);
+        assert_eq!(risk(measures(-8.0, 0.0, 0.0)), SnowRisk::Frost);
+        assert_eq!(risk(measures(3.0, 2.0, 2.0)), SnowRisk::NoRisk);
+    }
+
+    #[test]
+    fn cross_check_accepts_the_moon_csv() {
+        let moon = RawMoonRecord::read_csv("data/moon.csv", &Default::default())
+            .and_then(crate::raw::RawReader::strict)
+            .unwrap();
+        let range = (date!(2016 - 01 - 01), date!(2022 - 12 - 31));
+        let times = Time::from(
+            vec![],
+            range,
+            &MoonPhaseConfig::default(),
+            &WeatherRules::default(),
+        );
+        let check = cross_check_moon(&times, &moon);
+        assert!(check.events > 300);
+        assert_eq!(check.mismatches, vec![]);
+    }
 }
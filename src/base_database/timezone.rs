@@ -0,0 +1,173 @@
+//! Local time in New York (America/New_York).
+//!
+//! Crash timestamps from NYC Open Data are naive local wall-clock times while
+//! the weather export is in UTC. The US daylight saving rules are simple and
+//! stable enough to be encoded here directly:
+//!
+//! | Years     | DST starts (02:00 EST)  | DST ends (02:00 EDT)     |
+//! | --------- | ----------------------- | ------------------------ |
+//! | 1987–2006 | first Sunday of April   | last Sunday of October   |
+//! | 2007–     | second Sunday of March  | first Sunday of November |
+
+use serde::{Deserialize, Serialize};
+use time::{
+    Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, UtcOffset, Weekday,
+    macros::{offset, time},
+};
+
+/// Eastern Standard Time.
+pub const EST: UtcOffset = offset!(-5);
+/// Eastern Daylight Time.
+pub const EDT: UtcOffset = offset!(-4);
+
+/// How a local wall-clock time maps onto UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DstFlag {
+    /// Exactly one instant has this local time.
+    Normal,
+    /// The hour after 01:00 on the day DST ends occurs twice; resolved to the
+    /// first (daylight time) occurrence.
+    Ambiguous,
+    /// The hour after 02:00 on the day DST starts is skipped by the clocks;
+    /// resolved with the standard time offset, i.e. as if the clocks had not
+    /// been moved forward yet.
+    NonExistent,
+}
+
+/// The local dates DST starts and ends on in `year`.
+fn dst_dates(year: i32) -> (Date, Date) {
+    if year >= 2007 {
+        (
+            nth_sunday(year, Month::March, 2),
+            nth_sunday(year, Month::November, 1),
+        )
+    } else {
+        (
+            nth_sunday(year, Month::April, 1),
+            last_sunday(year, Month::October),
+        )
+    }
+}
+
+fn nth_sunday(year: i32, month: Month, n: u8) -> Date {
+    let first = Date::from_calendar_date(year, month, 1).expect("first of month is valid");
+    let first_sunday = if first.weekday() == Weekday::Sunday {
+        first
+    } else {
+        first.next_occurrence(Weekday::Sunday)
+    };
+    first_sunday + Duration::weeks(n as i64 - 1)
+}
+
+fn last_sunday(year: i32, month: Month) -> Date {
+    let last =
+        Date::from_calendar_date(year, month, month.length(year)).expect("last of month is valid");
+    if last.weekday() == Weekday::Sunday {
+        last
+    } else {
+        last.prev_occurrence(Weekday::Sunday)
+    }
+}
+
+/// The New York UTC offset in effect at `instant`.
+pub fn offset_at(instant: OffsetDateTime) -> UtcOffset {
+    let utc = instant.to_offset(UtcOffset::UTC);
+    let (start, end) = dst_dates(utc.year());
+    // 02:00 EST and 02:00 EDT respectively
+    let dst_start = start.with_time(time!(7:00)).assume_utc();
+    let dst_end = end.with_time(time!(6:00)).assume_utc();
+    if dst_start <= utc && utc < dst_end {
+        EDT
+    } else {
+        EST
+    }
+}
+
+/// Converts an instant to New York wall-clock time.
+pub fn to_local(instant: OffsetDateTime) -> PrimitiveDateTime {
+    let local = instant.to_offset(offset_at(instant));
+    PrimitiveDateTime::new(local.date(), local.time())
+}
+
+/// Resolves a New York wall-clock time to a single instant.
+///
+/// Ambiguous and non-existent times are resolved deterministically as
+/// described on [`DstFlag`] and flagged accordingly.
+pub fn resolve(local: PrimitiveDateTime) -> (OffsetDateTime, DstFlag) {
+    let daylight = local.assume_offset(EDT);
+    let standard = local.assume_offset(EST);
+    match (offset_at(daylight) == EDT, offset_at(standard) == EST) {
+        (true, true) => (daylight, DstFlag::Ambiguous),
+        (true, false) => (daylight, DstFlag::Normal),
+        (false, true) => (standard, DstFlag::Normal),
+        (false, false) => (standard, DstFlag::NonExistent),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime};
+
+    #[test]
+    fn transition_dates() {
+        assert_eq!(
+            dst_dates(2016),
+            (date!(2016 - 03 - 13), date!(2016 - 11 - 06))
+        );
+        assert_eq!(
+            dst_dates(2022),
+            (date!(2022 - 03 - 13), date!(2022 - 11 - 06))
+        );
+        assert_eq!(
+            dst_dates(2006),
+            (date!(2006 - 04 - 02), date!(2006 - 10 - 29))
+        );
+    }
+
+    #[test]
+    fn utc_to_local() {
+        assert_eq!(
+            to_local(datetime!(2016-01-01 05:00 UTC)),
+            datetime!(2016-01-01 00:00)
+        );
+        assert_eq!(
+            to_local(datetime!(2016-07-01 04:00 UTC)),
+            datetime!(2016-07-01 00:00)
+        );
+        // both 05:00 and 06:00 UTC are 01:00 local on the day DST ends
+        assert_eq!(
+            to_local(datetime!(2016-11-06 05:00 UTC)),
+            datetime!(2016-11-06 01:00)
+        );
+        assert_eq!(
+            to_local(datetime!(2016-11-06 06:00 UTC)),
+            datetime!(2016-11-06 01:00)
+        );
+    }
+
+    #[test]
+    fn resolve_flags_transitions() {
+        assert_eq!(
+            resolve(datetime!(2016-03-13 01:00)),
+            (datetime!(2016-03-13 06:00 UTC), DstFlag::Normal)
+        );
+        assert_eq!(
+            resolve(datetime!(2016-03-13 02:30)),
+            (datetime!(2016-03-13 07:30 UTC), DstFlag::NonExistent)
+        );
+        assert_eq!(
+            resolve(datetime!(2016-03-13 03:00)),
+            (datetime!(2016-03-13 07:00 UTC), DstFlag::Normal)
+        );
+        assert_eq!(
+            resolve(datetime!(2016-11-06 01:00)),
+            (datetime!(2016-11-06 05:00 UTC), DstFlag::Ambiguous)
+        );
+        assert_eq!(
+            resolve(datetime!(2016-11-06 02:00)),
+            (datetime!(2016-11-06 07:00 UTC), DstFlag::Normal)
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use crate::base_database::mapping::Mappings;
+use crate::data_quality::rejects::{RejectLocation, RejectReason, RejectSource, Rejects};
+use crate::raw::vehicles::RawVehicleRecord;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone)]
+pub struct Vehicle {
+    /// `UNIQUE_ID` of the vehicle record, referenced by `Person::vehicle_id`.
+    pub vehicle_id: u32,
+    pub crash_id: u32,
+    pub vehicle_type: Option<VehicleType>,
+}
+
+/// Normalised vehicle body type.
+///
+/// NYPD recorded a fixed list of codes until 2016 and free text since, so
+/// many spellings map onto each variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum VehicleType {
+    Sedan,
+    StationWagonSuv,
+    Convertible,
+    Taxi,
+    Van,
+    PickUpTruck,
+    BoxTruck,
+    TractorTruck,
+    DumpTruck,
+    GarbageTruck,
+    TowTruck,
+    Tanker,
+    FlatBed,
+    OtherTruck,
+    Bus,
+    Bicycle,
+    EBike,
+    EScooter,
+    Moped,
+    Motorcycle,
+    Ambulance,
+    FireTruck,
+    Pedicab,
+}
+
+impl Vehicle {
+    /// Converts `raw`, translating its vehicle type by `mappings`, and
+    /// quarantines a non-empty vehicle type that matches no [`VehicleType`].
+    pub fn from_raw(raw: RawVehicleRecord, mappings: &Mappings, rejects: &mut Rejects) -> Self {
+        if let Some(value) = raw.vehicle_type.as_deref()
+            && mappings.vehicle_type.get(value).is_none()
+        {
+            let at = RejectLocation {
+                line: raw.line,
+                column: "VEHICLE_TYPE",
+                value,
+            };
+            rejects.reject_record(
+                RejectSource::Vehicles,
+                RejectReason::UnknownCategory,
+                at,
+                &raw,
+            );
+        }
+        Vehicle {
+            vehicle_id: raw.unique_id,
+            crash_id: raw.collision_id,
+            vehicle_type: raw
+                .vehicle_type
+                .as_deref()
+                .and_then(|v| mappings.vehicle_type.get(v)),
+        }
+    }
+}
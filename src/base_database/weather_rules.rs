@@ -0,0 +1,380 @@
+//! Ordered rules that classify an hourly weather observation into a
+//! [`Weather`] class.
+//!
+//! A rule bounds some of the measures; the first rule whose bounds all hold
+//! classifies the hour, and its id is kept on the time row. The rules are
+//! configured as `weather_rules` in `config.json`, e.g.
+//!
+//! ```json
+//! [
+//!   { "id": "WINDY", "weather": "WINDY",
+//!     "precipitation": { "le": 0 }, "windspeed": { "gt": 40 } },
+//!   { "id": "OTHER", "weather": "MISCALLANEOUS" }
+//! ]
+//! ```
+//!
+//! and are validated when loaded: every rule must be reachable, i.e. not
+//! wholly covered by the rules before it, and together they must classify
+//! every observation.
+
+use crate::base_database::time::{Weather, WeatherMeasures};
+use anyhow::{Result, bail, ensure};
+use serde::Deserialize;
+use std::collections::HashSet;
+
+/// Longest rule id, the width of the `weather_rule_id` column.
+pub const MAX_RULE_ID_LEN: usize = 40;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(transparent)]
+pub struct WeatherRules(pub Vec<WeatherRule>);
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WeatherRule {
+    /// Letters, digits and `_`, at most [`MAX_RULE_ID_LEN`] long.
+    pub id: String,
+    pub weather: Weather,
+    #[serde(default)]
+    pub temperature: Bounds,
+    #[serde(default)]
+    pub precipitation: Bounds,
+    #[serde(default)]
+    pub rain: Bounds,
+    #[serde(default)]
+    pub cloudcover: Bounds,
+    #[serde(default)]
+    pub windspeed: Bounds,
+}
+
+/// Bounds on one measure, in its unit (see [`WeatherMeasures`]); absent
+/// bounds do not restrict it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Bounds {
+    pub gt: Option<f32>,
+    pub ge: Option<f32>,
+    pub lt: Option<f32>,
+    pub le: Option<f32>,
+}
+
+impl Bounds {
+    fn contains(&self, v: f32) -> bool {
+        self.gt.is_none_or(|b| v > b)
+            && self.ge.is_none_or(|b| v >= b)
+            && self.lt.is_none_or(|b| v < b)
+            && self.le.is_none_or(|b| v <= b)
+    }
+
+    fn interval(&self) -> Interval {
+        let lower = [self.gt.map(|b| (b, false)), self.ge.map(|b| (b, true))];
+        let upper = [self.lt.map(|b| (b, false)), self.le.map(|b| (b, true))];
+        let lower = lower
+            .into_iter()
+            .flatten()
+            .fold(Interval::ALL, |i, (lo, closed)| {
+                i.intersect(&Interval {
+                    lo,
+                    lo_closed: closed,
+                    ..Interval::ALL
+                })
+            });
+        upper.into_iter().flatten().fold(lower, |i, (hi, closed)| {
+            i.intersect(&Interval {
+                hi,
+                hi_closed: closed,
+                ..Interval::ALL
+            })
+        })
+    }
+}
+
+impl WeatherRule {
+    fn matches(&self, m: &WeatherMeasures) -> bool {
+        self.temperature.contains(m.temperature)
+            && self.precipitation.contains(m.precipitation)
+            && self.rain.contains(m.rain)
+            && self.cloudcover.contains(m.cloudcover)
+            && self.windspeed.contains(m.windspeed)
+    }
+
+    fn region(&self) -> Region {
+        [
+            self.temperature.interval(),
+            self.precipitation.interval(),
+            self.rain.interval(),
+            self.cloudcover.interval(),
+            self.windspeed.interval(),
+        ]
+    }
+}
+
+impl WeatherRules {
+    /// The first rule that matches `measures`.
+    pub fn classify(&self, measures: &WeatherMeasures) -> Option<&WeatherRule> {
+        self.0.iter().find(|rule| rule.matches(measures))
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let mut ids = HashSet::new();
+        for rule in &self.0 {
+            ensure!(
+                !rule.id.is_empty()
+                    && rule.id.len() <= MAX_RULE_ID_LEN
+                    && rule
+                        .id
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_'),
+                "weather rule id {:?} must be 1–{MAX_RULE_ID_LEN} letters, digits or '_'",
+                rule.id
+            );
+            ensure!(
+                ids.insert(rule.id.as_str()),
+                "weather rule id {:?} is used twice",
+                rule.id
+            );
+        }
+
+        let mut covered: Vec<Region> = Vec::new();
+        for rule in &self.0 {
+            let region = rule.region();
+            if region.iter().any(Interval::is_empty) {
+                bail!(
+                    "weather rule {:?} can never match: its bounds contradict",
+                    rule.id
+                );
+            }
+            if remainder(region, &covered).is_empty() {
+                bail!(
+                    "weather rule {:?} is unreachable: the rules before it match everything it does",
+                    rule.id
+                );
+            }
+            covered.push(region);
+        }
+        ensure!(
+            remainder(UNIVERSE, &covered).is_empty(),
+            "the weather rules leave some observations unclassified; end them with a rule without bounds"
+        );
+        Ok(())
+    }
+}
+
+impl Default for WeatherRules {
+    /// | Rule        | Weather       | Logic                      |
+    /// | ----------- | ------------- | -------------------------- |
+    /// | WINDY       | Windy         | Precip = 0 AND Wind > 40   |
+    /// | CLEAR       | Clear         | Precip = 0 AND Cloud < 20% |
+    /// | CLOUDY      | Cloudy        | Precip = 0 AND Cloud ≥ 60% |
+    /// | DRY         | Miscallaneous | Precip = 0                 |
+    /// | RAINY_LIGHT | Rainy Light   | Rain ≤ 2.5                 |
+    /// | STORMY      | Stormy        | Rain > 2.5 AND Wind > 40   |
+    /// | RAINY_HEAVY | Rainy Heavy   | Rain > 2.5                 |
+    fn default() -> Self {
+        serde_json::from_str(DEFAULT_RULES).expect("the default weather rules parse")
+    }
+}
+
+const DEFAULT_RULES: &str = r#"[
+    { "id": "WINDY", "weather": "WINDY",
+      "precipitation": { "le": 0 }, "windspeed": { "gt": 40 } },
+    { "id": "CLEAR", "weather": "CLEAR",
+      "precipitation": { "le": 0 }, "cloudcover": { "lt": 20 } },
+    { "id": "CLOUDY", "weather": "CLOUDY",
+      "precipitation": { "le": 0 }, "cloudcover": { "ge": 60 } },
+    { "id": "DRY", "weather": "MISCALLANEOUS",
+      "precipitation": { "le": 0 } },
+    { "id": "RAINY_LIGHT", "weather": "RAINY_LIGHT",
+      "rain": { "le": 2.5 } },
+    { "id": "STORMY", "weather": "STORMY",
+      "rain": { "gt": 2.5 }, "windspeed": { "gt": 40 } },
+    { "id": "RAINY_HEAVY", "weather": "RAINY_HEAVY",
+      "rain": { "gt": 2.5 } }
+]"#;
+
+/// A set of real numbers between two bounds, each open or closed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Interval {
+    lo: f32,
+    lo_closed: bool,
+    hi: f32,
+    hi_closed: bool,
+}
+
+impl Interval {
+    const ALL: Interval = Interval {
+        lo: f32::NEG_INFINITY,
+        lo_closed: false,
+        hi: f32::INFINITY,
+        hi_closed: false,
+    };
+
+    fn is_empty(&self) -> bool {
+        self.lo > self.hi || (self.lo == self.hi && !(self.lo_closed && self.hi_closed))
+    }
+
+    fn intersect(&self, other: &Interval) -> Interval {
+        let (lo, lo_closed) = match self.lo.partial_cmp(&other.lo) {
+            Some(std::cmp::Ordering::Less) => (other.lo, other.lo_closed),
+            Some(std::cmp::Ordering::Greater) => (self.lo, self.lo_closed),
+            _ => (self.lo, self.lo_closed && other.lo_closed),
+        };
+        let (hi, hi_closed) = match self.hi.partial_cmp(&other.hi) {
+            Some(std::cmp::Ordering::Less) => (self.hi, self.hi_closed),
+            Some(std::cmp::Ordering::Greater) => (other.hi, other.hi_closed),
+            _ => (self.hi, self.hi_closed && other.hi_closed),
+        };
+        Interval {
+            lo,
+            lo_closed,
+            hi,
+            hi_closed,
+        }
+    }
+
+    /// The parts of `self` below and above `other`.
+    fn minus(&self, other: &Interval) -> [Interval; 2] {
+        [
+            self.intersect(&Interval {
+                hi: other.lo,
+                hi_closed: !other.lo_closed,
+                ..Interval::ALL
+            }),
+            self.intersect(&Interval {
+                lo: other.hi,
+                lo_closed: !other.hi_closed,
+                ..Interval::ALL
+            }),
+        ]
+    }
+}
+
+/// The observations a rule matches: one interval per measure, in the order
+/// of [`WeatherRule::region`].
+type Region = [Interval; 5];
+
+const UNIVERSE: Region = [Interval::ALL; 5];
+
+/// The parts of `region` that none of `covered` contains, as disjoint regions.
+fn remainder(region: Region, covered: &[Region]) -> Vec<Region> {
+    covered.iter().fold(vec![region], |left, cover| {
+        left.into_iter()
+            .flat_map(|region| subtract(region, cover))
+            .collect()
+    })
+}
+
+/// `region` without `cover`: for each measure in turn, the slabs of `region`
+/// outside `cover` on that measure and inside it on the ones before.
+fn subtract(region: Region, cover: &Region) -> Vec<Region> {
+    if region
+        .iter()
+        .zip(cover)
+        .any(|(r, c)| r.intersect(c).is_empty())
+    {
+        return vec![region];
+    }
+    let mut pieces = Vec::new();
+    let mut inside = region;
+    for d in 0..region.len() {
+        for outside in region[d].minus(&cover[d]) {
+            if !outside.is_empty() {
+                let mut piece = inside;
+                piece[d] = outside;
+                pieces.push(piece);
+            }
+        }
+        inside[d] = region[d].intersect(&cover[d]);
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measures(precipitation: f32, rain: f32, cloudcover: f32, windspeed: f32) -> WeatherMeasures {
+        WeatherMeasures {
+            temperature: 10.0,
+            precipitation,
+            rain,
+            cloudcover,
+            windspeed,
+        }
+    }
+
+    #[test]
+    fn default_rules_are_valid_and_reach_windy() {
+        let rules = WeatherRules::default();
+        rules.validate().unwrap();
+        let classify = |m| rules.classify(&m).map(|r| (r.id.as_str(), r.weather));
+        assert_eq!(
+            classify(measures(0.0, 0.0, 10.0, 45.0)),
+            Some(("WINDY", Weather::Windy))
+        );
+        assert_eq!(
+            classify(measures(0.0, 0.0, 10.0, 12.0)),
+            Some(("CLEAR", Weather::Clear))
+        );
+        assert_eq!(
+            classify(measures(0.0, 0.0, 40.0, 12.0)),
+            Some(("DRY", Weather::Miscallaneous))
+        );
+        assert_eq!(
+            classify(measures(3.0, 3.0, 100.0, 45.0)),
+            Some(("STORMY", Weather::Stormy))
+        );
+        assert_eq!(
+            classify(measures(3.0, 3.0, 100.0, 20.0)),
+            Some(("RAINY_HEAVY", Weather::RainyHeavy))
+        );
+    }
+
+    #[test]
+    fn unreachable_rules_are_rejected() {
+        let rules: WeatherRules = serde_json::from_str(
+            r#"[
+                { "id": "DRY", "weather": "CLEAR", "precipitation": { "le": 0 } },
+                { "id": "DRY_CALM", "weather": "CLOUDY",
+                  "precipitation": { "le": 0 }, "windspeed": { "lt": 20 } },
+                { "id": "OTHER", "weather": "MISCALLANEOUS" }
+            ]"#,
+        )
+        .unwrap();
+        let error = rules.validate().unwrap_err().to_string();
+        assert!(error.contains("DRY_CALM"), "{error}");
+
+        // covered only by two earlier rules together
+        let rules: WeatherRules = serde_json::from_str(
+            r#"[
+                { "id": "CALM", "weather": "CLEAR", "windspeed": { "le": 20 } },
+                { "id": "BREEZY", "weather": "WINDY", "windspeed": { "gt": 20 } },
+                { "id": "OTHER", "weather": "MISCALLANEOUS" }
+            ]"#,
+        )
+        .unwrap();
+        assert!(rules.validate().unwrap_err().to_string().contains("OTHER"));
+    }
+
+    #[test]
+    fn gaps_and_bad_ids_are_rejected() {
+        let gap: WeatherRules = serde_json::from_str(
+            r#"[
+                { "id": "CALM", "weather": "CLEAR", "windspeed": { "lt": 20 } },
+                { "id": "BREEZY", "weather": "WINDY", "windspeed": { "gt": 20 } }
+            ]"#,
+        )
+        .unwrap();
+        assert!(gap.validate().is_err());
+
+        let bad_id: WeatherRules =
+            serde_json::from_str(r#"[{ "id": "it's", "weather": "CLEAR" }]"#).unwrap();
+        assert!(bad_id.validate().is_err());
+
+        let contradiction: WeatherRules = serde_json::from_str(
+            r#"[{ "id": "NEVER", "weather": "CLEAR", "rain": { "gt": 2, "lt": 1 } }]"#,
+        )
+        .unwrap();
+        assert!(contradiction.validate().is_err());
+    }
+}
@@ -0,0 +1,129 @@
+//! US federal holidays and the New York State public holidays on top of
+//! them, with the observed-day shift of the fixed-date holidays.
+
+use super::{last_weekday, nth_weekday};
+use time::{Date, Duration, Month, Weekday};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Holiday {
+    NewYearsDay,
+    MartinLutherKingJrDay,
+    LincolnsBirthday,
+    WashingtonsBirthday,
+    MemorialDay,
+    Juneteenth,
+    IndependenceDay,
+    LaborDay,
+    ColumbusDay,
+    ElectionDay,
+    VeteransDay,
+    Thanksgiving,
+    ChristmasDay,
+}
+
+impl Holiday {
+    pub const ALL: [Holiday; 13] = [
+        Holiday::NewYearsDay,
+        Holiday::MartinLutherKingJrDay,
+        Holiday::LincolnsBirthday,
+        Holiday::WashingtonsBirthday,
+        Holiday::MemorialDay,
+        Holiday::Juneteenth,
+        Holiday::IndependenceDay,
+        Holiday::LaborDay,
+        Holiday::ColumbusDay,
+        Holiday::ElectionDay,
+        Holiday::VeteransDay,
+        Holiday::Thanksgiving,
+        Holiday::ChristmasDay,
+    ];
+
+    /// Date of the holiday in `year`, or `None` before it was established.
+    pub fn date(self, year: i32) -> Option<Date> {
+        use Holiday::*;
+        let fixed = |month, day| Date::from_calendar_date(year, month, day).ok();
+        match self {
+            NewYearsDay => fixed(Month::January, 1),
+            MartinLutherKingJrDay => {
+                (year >= 1986).then(|| nth_weekday(year, Month::January, Weekday::Monday, 3))
+            }
+            LincolnsBirthday => fixed(Month::February, 12),
+            WashingtonsBirthday => Some(nth_weekday(year, Month::February, Weekday::Monday, 3)),
+            MemorialDay => Some(last_weekday(year, Month::May, Weekday::Monday)),
+            Juneteenth => (year >= 2021).then(|| fixed(Month::June, 19)).flatten(),
+            IndependenceDay => fixed(Month::July, 4),
+            LaborDay => Some(nth_weekday(year, Month::September, Weekday::Monday, 1)),
+            ColumbusDay => Some(nth_weekday(year, Month::October, Weekday::Monday, 2)),
+            // the Tuesday after the first Monday
+            ElectionDay => {
+                Some(nth_weekday(year, Month::November, Weekday::Monday, 1) + Duration::days(1))
+            }
+            VeteransDay => fixed(Month::November, 11),
+            Thanksgiving => Some(nth_weekday(year, Month::November, Weekday::Thursday, 4)),
+            ChristmasDay => fixed(Month::December, 25),
+        }
+    }
+
+    /// Day the holiday is observed in `year`: a fixed-date holiday on a
+    /// Saturday is observed the Friday before, on a Sunday the Monday after.
+    /// The observed New Year's Day can fall on 31 December of the year
+    /// before.
+    pub fn observed(self, year: i32) -> Option<Date> {
+        let date = self.date(year)?;
+        Some(match date.weekday() {
+            Weekday::Saturday => date - Duration::days(1),
+            Weekday::Sunday => date + Duration::days(1),
+            _ => date,
+        })
+    }
+}
+
+/// The holiday that falls on or is observed on `date`.
+pub fn holiday_on(date: Date) -> Option<Holiday> {
+    let years = [date.year(), date.year() + 1];
+    Holiday::ALL.into_iter().find(|h| {
+        years
+            .iter()
+            .any(|&y| h.date(y) == Some(date) || h.observed(y) == Some(date))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn floating_holidays() {
+        assert_eq!(
+            holiday_on(date!(2021 - 11 - 25)),
+            Some(Holiday::Thanksgiving)
+        );
+        assert_eq!(
+            holiday_on(date!(2020 - 11 - 03)),
+            Some(Holiday::ElectionDay)
+        );
+        assert_eq!(
+            holiday_on(date!(2019 - 01 - 21)),
+            Some(Holiday::MartinLutherKingJrDay)
+        );
+        assert_eq!(holiday_on(date!(2021 - 11 - 24)), None);
+    }
+
+    #[test]
+    fn observed_days() {
+        // 4 July 2020 was a Saturday
+        assert_eq!(
+            holiday_on(date!(2020 - 07 - 03)),
+            Some(Holiday::IndependenceDay)
+        );
+        // 1 January 2022 was a Saturday
+        assert_eq!(
+            holiday_on(date!(2021 - 12 - 31)),
+            Some(Holiday::NewYearsDay)
+        );
+        // Juneteenth only from 2021
+        assert_eq!(holiday_on(date!(2020 - 06 - 19)), None);
+        assert_eq!(holiday_on(date!(2021 - 06 - 18)), Some(Holiday::Juneteenth));
+    }
+}
@@ -0,0 +1,48 @@
+//! Calendar calculations used to enrich the time dimension.
+
+pub mod holidays;
+
+use time::{Date, Month, Weekday};
+
+/// The `n`th (1-based) `weekday` of `month`.
+fn nth_weekday(year: i32, month: Month, weekday: Weekday, n: u8) -> Date {
+    let first = Date::from_calendar_date(year, month, 1).expect("the 1st exists in every month");
+    let offset =
+        (weekday.number_days_from_monday() + 7 - first.weekday().number_days_from_monday()) % 7;
+    first + time::Duration::days(i64::from(offset + 7 * (n - 1)))
+}
+
+/// The last `weekday` of `month`.
+fn last_weekday(year: i32, month: Month, weekday: Weekday) -> Date {
+    let last = Date::from_calendar_date(year, month, month.length(year))
+        .expect("a month's length is a valid day");
+    let offset =
+        (last.weekday().number_days_from_monday() + 7 - weekday.number_days_from_monday()) % 7;
+    last - time::Duration::days(i64::from(offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn floating_days() {
+        assert_eq!(
+            nth_weekday(2022, Month::November, Weekday::Thursday, 4),
+            date!(2022 - 11 - 24)
+        );
+        assert_eq!(
+            nth_weekday(2021, Month::September, Weekday::Monday, 1),
+            date!(2021 - 09 - 06)
+        );
+        assert_eq!(
+            last_weekday(2021, Month::May, Weekday::Monday),
+            date!(2021 - 05 - 31)
+        );
+        assert_eq!(
+            last_weekday(2022, Month::May, Weekday::Monday),
+            date!(2022 - 05 - 30)
+        );
+    }
+}
@@ -0,0 +1,535 @@
+//! Run configuration.
+//!
+//! Read from `config.json` in the working directory.  Every section is
+//! optional and falls back to the built-in defaults, so a missing file is
+//! equivalent to `{}`.
+
+use crate::base_database::weather_rules::WeatherRules;
+use crate::raw::ColumnAliases;
+use anyhow::{Context, Result, ensure};
+use serde::Deserialize;
+use std::path::Path;
+use time::Date;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub column_aliases: ColumnAliasConfig,
+    pub moon_phases: MoonPhaseConfig,
+    pub person_ages: PersonAgeConfig,
+    pub age_plausibility: AgePlausibilityConfig,
+    pub casualty_plausibility: CasualtyPlausibilityConfig,
+    pub unmapped_values: UnmappedValueConfig,
+    pub severity: SeverityConfig,
+    /// Local dates covered by the time dimension; derived from the crash
+    /// dates when absent.
+    pub time_range: Option<TimeRangeConfig>,
+    /// Ordered rules classifying the hourly weather; see
+    /// [`weather_rules`](crate::base_database::weather_rules).
+    pub weather_rules: WeatherRules,
+    /// Directory of `<table>.csv` files replacing the built-in source-string
+    /// mappings; see [`mapping`](crate::base_database::mapping).
+    pub mappings_dir: Option<String>,
+}
+
+/// Extra header names per raw source, keyed by canonical column name.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColumnAliasConfig {
+    pub crashes: ColumnAliases,
+    pub persons: ColumnAliases,
+    pub vehicles: ColumnAliases,
+    pub weather: ColumnAliases,
+    pub moon: ColumnAliases,
+}
+
+/// How the eight moon phases are derived from the four primary events.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MoonPhaseConfig {
+    /// Days on either side of a primary event (new, first quarter, full,
+    /// last quarter) that still count as that phase; the days in between are
+    /// the intermediate (crescent / gibbous) phases.
+    pub primary_window_days: u8,
+}
+
+impl MoonPhaseConfig {
+    /// Primary events are about 7.4 days apart; a wider window would leave no
+    /// day for the intermediate phase in between.
+    pub const MAX_PRIMARY_WINDOW_DAYS: u8 = 3;
+}
+
+impl Default for MoonPhaseConfig {
+    fn default() -> Self {
+        Self {
+            primary_window_days: 1,
+        }
+    }
+}
+
+/// Members of the person age dimension and the hierarchies grouping them,
+/// besides the fixed fertile / infertile `person_age_hier_def_group`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PersonAgeConfig {
+    /// Oldest age with a member; ages 1 to `max_age` get one each, older and
+    /// missing ages the unknown member.
+    pub max_age: u8,
+    /// Each becomes a `person_age_hier_<name>` column.
+    pub hierarchies: Vec<AgeHierarchyConfig>,
+}
+
+/// A named grouping of the ages into bands.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AgeHierarchyConfig {
+    /// Lower-case letters, digits and `_`.
+    pub name: String,
+    /// Must give every age from 1 to `max_age` exactly one band; a label may
+    /// be used by several bands.
+    pub bands: Vec<AgeBand>,
+}
+
+/// Ages `from` to `to` (inclusive), or `from` and older when `to` is absent.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AgeBand {
+    pub label: String,
+    pub from: u8,
+    pub to: Option<u8>,
+}
+
+impl PersonAgeConfig {
+    /// Longest band label, the width of the hierarchy columns.
+    pub const MAX_LABEL_LEN: usize = 20;
+    /// Longest hierarchy name; the column name must fit SQL Server's 128
+    /// characters with room to spare.
+    pub const MAX_NAME_LEN: usize = 40;
+}
+
+impl AgeHierarchyConfig {
+    /// Column of the hierarchy in `DimPersonAge`.
+    pub fn column(&self) -> String {
+        format!("person_age_hier_{}", self.name)
+    }
+
+    /// Label of the band containing `age`.
+    pub fn band(&self, age: u8) -> Option<&str> {
+        self.bands
+            .iter()
+            .find(|b| b.from <= age && b.to.is_none_or(|to| age <= to))
+            .map(|b| b.label.as_str())
+    }
+
+    fn new<L: Into<String>>(
+        name: &str,
+        bands: impl IntoIterator<Item = (L, u8, Option<u8>)>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            bands: bands
+                .into_iter()
+                .map(|(label, from, to)| AgeBand {
+                    label: label.into(),
+                    from,
+                    to,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Default for PersonAgeConfig {
+    /// Ages 1–121, in five-year bands, life stages and driving-age buckets
+    /// (New York issues learner permits at 16 and full licences at 18).
+    fn default() -> Self {
+        let five_year = (0..17)
+            .map(|i| {
+                (
+                    format!("{:02}-{:02}", 5 * i, 5 * i + 4),
+                    5 * i,
+                    Some(5 * i + 4),
+                )
+            })
+            .chain([("85+".into(), 85, None)]);
+        Self {
+            max_age: 121,
+            hierarchies: vec![
+                AgeHierarchyConfig::new("five_year", five_year),
+                AgeHierarchyConfig::new(
+                    "life_stage",
+                    [
+                        ("CHILD", 0, Some(12)),
+                        ("TEEN", 13, Some(19)),
+                        ("ADULT", 20, Some(64)),
+                        ("SENIOR", 65, None),
+                    ],
+                ),
+                AgeHierarchyConfig::new(
+                    "driving_age",
+                    [
+                        ("UNDER_16", 0, Some(15)),
+                        ("LEARNER", 16, Some(17)),
+                        ("YOUNG_DRIVER", 18, Some(24)),
+                        ("DRIVER", 25, Some(64)),
+                        ("OLDER_DRIVER", 65, None),
+                    ],
+                ),
+            ],
+        }
+    }
+}
+
+/// Ages that are believed; any other age of a person is set to unknown and
+/// quarantined. Age 0, the source's marker for an unknown age, is never
+/// checked.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AgePlausibilityConfig {
+    pub min_age: u8,
+    pub max_age: u8,
+    /// Youngest plausible age of whoever drives a vehicle.
+    pub min_driver_age: u8,
+}
+
+impl Default for AgePlausibilityConfig {
+    fn default() -> Self {
+        Self {
+            min_age: 1,
+            max_age: 110,
+            min_driver_age: 14,
+        }
+    }
+}
+
+/// The largest injured or killed count a single collision is expected to
+/// report; larger counts are kept but quarantined for review.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CasualtyPlausibilityConfig {
+    pub max_count: u16,
+}
+
+impl Default for CasualtyPlausibilityConfig {
+    fn default() -> Self {
+        Self { max_count: 100 }
+    }
+}
+
+/// What to do about category strings that match no known value; they are
+/// always reported in `unmapped_values.csv`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UnmappedValueConfig {
+    /// Fail the run if a single value makes up more than `max_share` of the
+    /// rows of its source; otherwise such values are only warned about.
+    pub strict: bool,
+    /// Fraction of the rows, 0–1.
+    pub max_share: f64,
+}
+
+impl Default for UnmappedValueConfig {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            max_share: 0.001,
+        }
+    }
+}
+
+/// Weights of the severity index measure: `injured_weight × persons injured
+/// + killed_weight × persons killed`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SeverityConfig {
+    pub injured_weight: u16,
+    pub killed_weight: u16,
+}
+
+impl SeverityConfig {
+    /// Largest index that fits the `INT` column it is stored in.
+    pub const MAX_INDEX: u32 = i32::MAX as u32;
+    /// Largest sum of the two weights for which the index of a crash with
+    /// the most countable casualties still fits [`Self::MAX_INDEX`].
+    pub const MAX_WEIGHT_SUM: u16 = (Self::MAX_INDEX / u16::MAX as u32) as u16;
+}
+
+impl Default for SeverityConfig {
+    /// The weighting of the project's Severity KPI.
+    fn default() -> Self {
+        Self {
+            injured_weight: 1,
+            killed_weight: 10,
+        }
+    }
+}
+
+/// Inclusive range of New York local dates, e.g. `{ "start": "2016-01-01", "end": "2022-12-31" }`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimeRangeConfig {
+    pub start: Date,
+    pub end: Date,
+}
+
+impl Config {
+    /// Loads the configuration from `path`; a missing file yields the defaults.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let json =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let config: Self =
+            serde_json::from_str(&json).with_context(|| format!("parsing {}", path.display()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.moon_phases.primary_window_days <= MoonPhaseConfig::MAX_PRIMARY_WINDOW_DAYS,
+            "moon_phases.primary_window_days must be at most {}, got {}",
+            MoonPhaseConfig::MAX_PRIMARY_WINDOW_DAYS,
+            self.moon_phases.primary_window_days
+        );
+        ensure!(
+            self.severity.killed_weight >= self.severity.injured_weight,
+            "severity.killed_weight ({}) must not be below severity.injured_weight ({})",
+            self.severity.killed_weight,
+            self.severity.injured_weight
+        );
+        ensure!(
+            u32::from(self.severity.injured_weight) + u32::from(self.severity.killed_weight)
+                <= u32::from(SeverityConfig::MAX_WEIGHT_SUM),
+            "severity.injured_weight + severity.killed_weight must be at most {}",
+            SeverityConfig::MAX_WEIGHT_SUM
+        );
+        self.person_ages.validate()?;
+        let ages = self.age_plausibility;
+        ensure!(
+            ages.min_age <= ages.max_age,
+            "age_plausibility.min_age ({}) is above age_plausibility.max_age ({})",
+            ages.min_age,
+            ages.max_age
+        );
+        ensure!(
+            (ages.min_age..=ages.max_age).contains(&ages.min_driver_age),
+            "age_plausibility.min_driver_age ({}) must lie between min_age and max_age",
+            ages.min_driver_age
+        );
+        ensure!(
+            self.casualty_plausibility.max_count >= 1,
+            "casualty_plausibility.max_count must be at least 1"
+        );
+        ensure!(
+            (0.0..=1.0).contains(&self.unmapped_values.max_share),
+            "unmapped_values.max_share must lie between 0 and 1, got {}",
+            self.unmapped_values.max_share
+        );
+        self.weather_rules.validate()?;
+        if let Some(range) = self.time_range {
+            ensure!(
+                range.start <= range.end,
+                "time_range.start ({}) is after time_range.end ({})",
+                range.start,
+                range.end
+            );
+        }
+        Ok(())
+    }
+}
+
+impl PersonAgeConfig {
+    fn validate(&self) -> Result<()> {
+        ensure!(self.max_age >= 1, "person_ages.max_age must be at least 1");
+        let mut names = std::collections::HashSet::new();
+        for h in &self.hierarchies {
+            ensure!(
+                !h.name.is_empty()
+                    && h.name.len() <= Self::MAX_NAME_LEN
+                    && h.name
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+                "age hierarchy name {:?} must be 1–{} lower-case letters, digits or '_'",
+                h.name,
+                Self::MAX_NAME_LEN
+            );
+            ensure!(
+                h.name != "def_group" && names.insert(h.name.as_str()),
+                "age hierarchy name {:?} is used twice",
+                h.name
+            );
+            for band in &h.bands {
+                ensure!(
+                    !band.label.is_empty()
+                        && band.label.len() <= Self::MAX_LABEL_LEN
+                        && band.label != "UNKNOWN"
+                        && band
+                            .label
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || "_-+ ".contains(c)),
+                    "age hierarchy {:?}: band label {:?} must be 1–{} letters, digits, '_', '-', \
+                     '+' or spaces, and not UNKNOWN",
+                    h.name,
+                    band.label,
+                    Self::MAX_LABEL_LEN
+                );
+            }
+            for age in 1..=self.max_age {
+                let bands = h
+                    .bands
+                    .iter()
+                    .filter(|b| b.from <= age && b.to.is_none_or(|to| age <= to))
+                    .count();
+                ensure!(
+                    bands == 1,
+                    "age hierarchy {:?}: age {age} falls into {bands} bands, not one",
+                    h.name
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_object_is_default() {
+        let config: Config = serde_json::from_str("{}").unwrap();
+        assert!(config.column_aliases.weather.0.is_empty());
+    }
+
+    #[test]
+    fn moon_window_is_validated() {
+        let config: Config =
+            serde_json::from_str(r#"{ "moon_phases": { "primary_window_days": 4 } }"#).unwrap();
+        assert!(config.validate().is_err());
+        assert!(Config::default().validate().is_ok());
+    }
+
+    #[test]
+    fn severity_weights_are_validated() {
+        let config: Config =
+            serde_json::from_str(r#"{ "severity": { "killed_weight": 100 } }"#).unwrap();
+        assert_eq!(config.severity.injured_weight, 1);
+        assert!(config.validate().is_ok());
+
+        let config: Config =
+            serde_json::from_str(r#"{ "severity": { "injured_weight": 20 } }"#).unwrap();
+        assert!(config.validate().is_err());
+
+        // the index of a crash with 65 535 victims would not fit an INT
+        let config: Config = serde_json::from_str(
+            r#"{ "severity": { "injured_weight": 10, "killed_weight": 32760 } }"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn time_range_is_parsed_and_validated() {
+        let config: Config = serde_json::from_str(
+            r#"{ "time_range": { "start": "2012-07-01", "end": "2023-12-31" } }"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.time_range.unwrap().start.year(), 2012);
+
+        let config: Config = serde_json::from_str(
+            r#"{ "time_range": { "start": "2023-01-01", "end": "2022-12-31" } }"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn weather_rules_are_validated() {
+        let config: Config = serde_json::from_str(
+            r#"{ "weather_rules": [
+                { "id": "WET", "weather": "RAINY_LIGHT", "precipitation": { "gt": 0 } },
+                { "id": "DRY", "weather": "CLEAR", "precipitation": { "le": 0 } }
+            ] }"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.weather_rules.0.len(), 2);
+
+        let config: Config = serde_json::from_str(
+            r#"{ "weather_rules": [{ "id": "WET", "weather": "RAINY_LIGHT", "rain": { "gt": 0 } }] }"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn age_hierarchies_are_validated() {
+        let defaults = PersonAgeConfig::default();
+        assert!(defaults.validate().is_ok());
+        let five_year = &defaults.hierarchies[0];
+        assert_eq!(five_year.band(1), Some("00-04"));
+        assert_eq!(five_year.band(49), Some("45-49"));
+        assert_eq!(five_year.band(121), Some("85+"));
+
+        let config: Config = serde_json::from_str(
+            r#"{ "person_ages": { "max_age": 99, "hierarchies": [{ "name": "minor", "bands": [
+                { "label": "MINOR", "from": 0, "to": 17 },
+                { "label": "ADULT", "from": 18 }
+            ] }] } }"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        // a gap at 18
+        let config: Config = serde_json::from_str(
+            r#"{ "person_ages": { "hierarchies": [{ "name": "minor", "bands": [
+                { "label": "MINOR", "from": 0, "to": 17 },
+                { "label": "ADULT", "from": 19 }
+            ] }] } }"#,
+        )
+        .unwrap();
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("age 18"), "{error}");
+        assert_eq!(config.person_ages.max_age, 121);
+    }
+
+    #[test]
+    fn age_plausibility_is_validated() {
+        let config: Config =
+            serde_json::from_str(r#"{ "age_plausibility": { "min_driver_age": 16 } }"#).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.age_plausibility.max_age, 110);
+
+        let config: Config =
+            serde_json::from_str(r#"{ "age_plausibility": { "max_age": 12 } }"#).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn casualty_plausibility_is_validated() {
+        let config: Config =
+            serde_json::from_str(r#"{ "casualty_plausibility": { "max_count": 250 } }"#).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(Config::default().casualty_plausibility.max_count, 100);
+
+        let config: Config =
+            serde_json::from_str(r#"{ "casualty_plausibility": { "max_count": 0 } }"#).unwrap();
+        assert!(config.validate().is_err());
+        // the crash columns cannot hold more
+        assert!(
+            serde_json::from_str::<Config>(
+                r#"{ "casualty_plausibility": { "max_count": 65536 } }"#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert!(serde_json::from_str::<Config>(r#"{ "colum_aliases": {} }"#).is_err());
+    }
+}
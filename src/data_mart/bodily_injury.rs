@@ -0,0 +1,110 @@
+use crate::base_database::person;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumCount as EnumCountMacro;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumCountMacro)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BodilyInjury {
+    Head,
+    Face,
+    Eye,
+    Neck,
+    Chest,
+    Back,
+    AbdomenPelvis,
+    ShoulderUpperArm,
+    ElbowLowerArmHand,
+    HipUpperLeg,
+    KneeLowerLegFoot,
+    EntireBody,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BodyRegion {
+    HeadNeck,
+    Torso,
+    UpperExtremity,
+    LowerExtremity,
+    EntireBody,
+    Unknown,
+}
+
+/// Body part of the person's most severe injury; uninjured persons are
+/// Unknown.
+///
+/// Hierarchy:
+///   Level 1 – bodily_injury
+///   Level 2 – bodily_injury_hier_def_region
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BodilyInjuryDim {
+    pub bodily_injury_id: u32,
+    pub bodily_injury: BodilyInjury,
+    pub bodily_injury_hier_def_region: BodyRegion,
+}
+
+impl BodilyInjuryDim {
+    pub fn gen_bodily_injuries() -> Vec<BodilyInjuryDim> {
+        use BodilyInjury as B;
+        use BodyRegion as R;
+        [
+            (B::Unknown, R::Unknown),
+            (B::Head, R::HeadNeck),
+            (B::Face, R::HeadNeck),
+            (B::Eye, R::HeadNeck),
+            (B::Neck, R::HeadNeck),
+            (B::Chest, R::Torso),
+            (B::Back, R::Torso),
+            (B::AbdomenPelvis, R::Torso),
+            (B::ShoulderUpperArm, R::UpperExtremity),
+            (B::ElbowLowerArmHand, R::UpperExtremity),
+            (B::HipUpperLeg, R::LowerExtremity),
+            (B::KneeLowerLegFoot, R::LowerExtremity),
+            (B::EntireBody, R::EntireBody),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (bodily_injury, region))| BodilyInjuryDim {
+            bodily_injury_id: i as u32,
+            bodily_injury,
+            bodily_injury_hier_def_region: region,
+        })
+        .collect()
+    }
+}
+
+impl From<person::BodilyInjury> for BodilyInjury {
+    fn from(b: person::BodilyInjury) -> Self {
+        use person::BodilyInjury as B;
+        match b {
+            B::Head => BodilyInjury::Head,
+            B::Face => BodilyInjury::Face,
+            B::Eye => BodilyInjury::Eye,
+            B::Neck => BodilyInjury::Neck,
+            B::Chest => BodilyInjury::Chest,
+            B::Back => BodilyInjury::Back,
+            B::AbdomenPelvis => BodilyInjury::AbdomenPelvis,
+            B::ShoulderUpperArm => BodilyInjury::ShoulderUpperArm,
+            B::ElbowLowerArmHand => BodilyInjury::ElbowLowerArmHand,
+            B::HipUpperLeg => BodilyInjury::HipUpperLeg,
+            B::KneeLowerLegFoot => BodilyInjury::KneeLowerLegFoot,
+            B::EntireBody => BodilyInjury::EntireBody,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::EnumCount;
+
+    #[test]
+    fn gen_bodily_injuries_length_matches_enum_count() {
+        assert_eq!(
+            BodilyInjuryDim::gen_bodily_injuries().len(),
+            BodilyInjury::COUNT
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::base_database::crash::CrashFactor;
 use serde::{Deserialize, Serialize};
 use strum_macros::EnumCount as EnumCountMacro;
 
@@ -684,6 +685,75 @@ impl ContributingFactorDim {
     }
 }
 
+impl From<CrashFactor> for ContributingFactor {
+    fn from(f: CrashFactor) -> Self {
+        use CrashFactor as B;
+        match f {
+            B::DriverlessRunawayVehicle => ContributingFactor::DriverlessRunawayVehicle,
+            B::ListeningUsingHeadphones => ContributingFactor::ListeningUsingHeadphones,
+            B::EatingOrDrinking => ContributingFactor::EatingOrDrinking,
+            B::UnsafeLaneChanging => ContributingFactor::UnsafeLaneChanging,
+            B::CellPhoneHandHeld => ContributingFactor::CellPhoneHandHeld,
+            B::CellPhoneHandsFree => ContributingFactor::CellPhoneHandsFree,
+            B::DrugsIllegal => ContributingFactor::DrugsIllegal,
+            B::Texting => ContributingFactor::Texting,
+            B::HeadlightsDefective => ContributingFactor::HeadlightsDefective,
+            B::OtherLightingDefects => ContributingFactor::OtherLightingDefects,
+            B::DriverInexperience => ContributingFactor::DriverInexperience,
+            B::AggressiveDrivingRoadRage => ContributingFactor::AggressiveDrivingRoadRage,
+            B::UnsafeSpeed => ContributingFactor::UnsafeSpeed,
+            B::LaneMarkingImproperInadequate => ContributingFactor::LaneMarkingImproperInadequate,
+            B::Glare => ContributingFactor::Glare,
+            B::TrafficControlDeviceImproperNonWorking => {
+                ContributingFactor::TrafficControlDeviceImproperNonWorking
+            }
+            B::PassingTooClosely => ContributingFactor::PassingTooClosely,
+            B::AcceleratorDefective => ContributingFactor::AcceleratorDefective,
+            B::ShouldersDefectiveImproper => ContributingFactor::ShouldersDefectiveImproper,
+            B::OutsideCarDistraction => ContributingFactor::OutsideCarDistraction,
+            B::DriverInattentionDistraction => ContributingFactor::DriverInattentionDistraction,
+            B::TintedWindows => ContributingFactor::TintedWindows,
+            B::UsingOnBoardNavigationDevice => ContributingFactor::UsingOnBoardNavigationDevice,
+            B::ReactionToOtherUninvolvedVehicle => {
+                ContributingFactor::ReactionToOtherUninvolvedVehicle
+            }
+            B::ObstructionDebris => ContributingFactor::ObstructionDebris,
+            B::PrescriptionMedication => ContributingFactor::PrescriptionMedication,
+            B::TireFailureInadequate => ContributingFactor::TireFailureInadequate,
+            B::FatiguedDrowsy => ContributingFactor::FatiguedDrowsy,
+            B::PassingOrLaneUsageImproper => ContributingFactor::PassingOrLaneUsageImproper,
+            B::FollowingTooClosely => ContributingFactor::FollowingTooClosely,
+            B::ViewObstructedLimited => ContributingFactor::ViewObstructedLimited,
+            B::OversizedVehicle => ContributingFactor::OversizedVehicle,
+            B::LostConsciousness => ContributingFactor::LostConsciousness,
+            B::BackingUnsafely => ContributingFactor::BackingUnsafely,
+            B::OtherVehicular => ContributingFactor::OtherVehicular,
+            B::Illness => ContributingFactor::Illness,
+            B::WindshieldInadequate => ContributingFactor::WindshieldInadequate,
+            B::FellAsleep => ContributingFactor::FellAsleep,
+            B::TrafficControlDisregarded => ContributingFactor::TrafficControlDisregarded,
+            B::PavementDefective => ContributingFactor::PavementDefective,
+            B::SteeringFailure => ContributingFactor::SteeringFailure,
+            B::PassengerDistraction => ContributingFactor::PassengerDistraction,
+            B::VehicleVandalism => ContributingFactor::VehicleVandalism,
+            B::FailureToKeepRight => ContributingFactor::FailureToKeepRight,
+            B::BrakesDefective => ContributingFactor::BrakesDefective,
+            B::TurningImproperly => ContributingFactor::TurningImproperly,
+            B::FailureToYieldRightOfWay => ContributingFactor::FailureToYieldRightOfWay,
+            B::ReactionToUninvolvedVehicle => ContributingFactor::ReactionToUninvolvedVehicle,
+            B::TowHitchDefective => ContributingFactor::TowHitchDefective,
+            B::AlcoholInvolvement => ContributingFactor::AlcoholInvolvement,
+            B::PhysicalDisability => ContributingFactor::PhysicalDisability,
+            B::AnimalsAction => ContributingFactor::AnimalsAction,
+            B::OtherElectronicDevice => ContributingFactor::OtherElectronicDevice,
+            B::PedestrianBicyclistOtherPedestrianErrorConfusion => {
+                ContributingFactor::PedestrianBicyclistOtherPedestrianErrorConfusion
+            }
+            B::PavementSlippery => ContributingFactor::PavementSlippery,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
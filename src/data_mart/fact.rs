@@ -1,12 +1,25 @@
-use crate::base_database::{crash::Crash, person::Person};
+use crate::base_database::{
+    crash::{Crash, CrashFactor},
+    person::{self, Person, VehicleOrdinals},
+    vehicle::{self, Vehicle},
+};
+use crate::config::SeverityConfig;
 use crate::data_mart::{
+    bodily_injury::{BodilyInjury, BodilyInjuryDim},
     contributing_factor::{ContributingFactor, ContributingFactorDim},
+    factor_group::{FactorGroupBridge, FactorGroupKeys},
+    location::{LocationDim, LocationKey, location_key},
+    pedestrian_action::{PedestrianAction, PedestrianActionDim, PedestrianLocation},
     person_age::PersonAge,
+    person_injury::{Ejection, EmotionalStatus, InjurySeverity, PersonInjuryDim},
     person_position::{PersonPosition, PersonPositionInVehicle},
     person_role::{PersonPositionRole, PersonRole},
     person_sex::{PersonSex, PersonSexType},
     person_type::{PersonType, PersonTypeType},
+    safety_equipment::{SafetyEquipment, SafetyEquipmentDim},
+    severity::{CrashSeverity, SeverityDim, casualties, severity_index},
     time::Time,
+    vehicle_type::{VehicleType, VehicleTypeDim},
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -19,33 +32,64 @@ pub struct Fact {
     pub fact_id: u32,
 
     // dimensions
+    pub bodily_injury_id: u32,
     pub contributing_factor_id: u32,
+    pub factor_group_id: u32,
+    pub location_id: u32,
+    pub pedestrian_action_id: u32,
     pub person_age_id: u32,
+    pub person_injury_id: u32,
     pub person_position_id: u32,
     pub person_role_id: u32,
     pub person_sex_id: u32,
     pub person_type_id: u32,
+    pub safety_equipment_id: u32,
+    pub severity_id: u32,
     pub time_id: u32,
+    pub vehicle_type_id: u32,
+
+    // measures: the person's own outcome
+    pub is_injured: bool,
+    pub is_killed: bool,
 
-    // measures
-    pub persons_injured: u8,
-    pub persons_killed: u8,
-    pub pedestrians_injured: u8,
-    pub pedestrians_killed: u8,
-    pub cyclist_injured: u8,
-    pub cyclist_killed: u8,
-    pub motorist_injured: u8,
-    pub motorist_killed: u8,
+    // measures: crash totals, repeated for every person of the crash
+    pub persons_injured: u16,
+    pub persons_killed: u16,
+    pub pedestrians_injured: u16,
+    pub pedestrians_killed: u16,
+    pub cyclist_injured: u16,
+    pub cyclist_killed: u16,
+    pub motorist_injured: u16,
+    pub motorist_killed: u16,
 }
 
-impl Fact {
-    /// Build fact rows — one row per Person, joining to their Crash for measures and time.
-    ///
-    /// Dimension lookups use the pre-built dimension tables so that every FK is valid.
+/// Turns persons into fact rows one at a time.
+///
+/// Only the crash index and the dimension lookups are kept in memory, so the
+/// persons themselves can be streamed straight from the raw file to the writers.
+pub struct FactBuilder {
+    crash_by_id: HashMap<u32, Crash>,
+    location_by_crash_id: HashMap<u32, u32>,
+    age_by_age: HashMap<u8, u32>,
+    unknown_age_id: u32,
+    position_by_type: HashMap<PersonPositionInVehicle, u32>,
+    role_by_type: HashMap<PersonRole, u32>,
+    sex_by_type: HashMap<PersonSexType, u32>,
+    type_by_type: HashMap<PersonTypeType, u32>,
+    crash_keys: CrashKeys,
+    vehicle_type_by_type: HashMap<VehicleType, u32>,
+    vehicle_ordinals: VehicleOrdinals,
+    vehicle_type_by_vehicle_id: HashMap<u32, vehicle::VehicleType>,
+    injury_by_key: HashMap<(InjurySeverity, Ejection, EmotionalStatus), u32>,
+    bodily_injury_by_type: HashMap<BodilyInjury, u32>,
+    safety_equipment_by_type: HashMap<SafetyEquipment, u32>,
+    pedestrian_action_by_key: HashMap<(PedestrianLocation, PedestrianAction), u32>,
+}
+
+impl FactBuilder {
     #[allow(clippy::too_many_arguments)]
-    pub fn gen_facts(
-        persons: Vec<Person>,
-        crashes: Vec<Crash>,
+    pub fn new(
+        crashes: impl IntoIterator<Item = Crash>,
         dim_times: &[Time],
         dim_ages: &[PersonAge],
         dim_positions: &[PersonPosition],
@@ -53,7 +97,15 @@ impl Fact {
         dim_sexes: &[PersonSex],
         dim_types: &[PersonType],
         dim_factors: &[ContributingFactorDim],
-    ) -> Vec<Fact> {
+        dim_factor_bridge: &[FactorGroupBridge],
+        dim_vehicle_types: &[VehicleTypeDim],
+        dim_locations: &[LocationDim],
+        dim_injuries: &[PersonInjuryDim],
+        dim_bodily_injuries: &[BodilyInjuryDim],
+        dim_safety_equipment: &[SafetyEquipmentDim],
+        dim_pedestrian_actions: &[PedestrianActionDim],
+        dim_severities: &[SeverityDim],
+    ) -> Self {
         // Index dimension tables by their natural keys for O(1) lookup.
         let age_by_age: HashMap<u8, u32> = dim_ages
             .iter()
@@ -87,13 +139,335 @@ impl Fact {
             .map(|t| (t.person_type, t.person_type_id))
             .collect();
 
+        let vehicle_type_by_type: HashMap<VehicleType, u32> = dim_vehicle_types
+            .iter()
+            .map(|v| (v.vehicle_type, v.vehicle_type_id))
+            .collect();
+
+        let injury_by_key: HashMap<(InjurySeverity, Ejection, EmotionalStatus), u32> = dim_injuries
+            .iter()
+            .map(|i| (i.key(), i.person_injury_id))
+            .collect();
+
+        let bodily_injury_by_type: HashMap<BodilyInjury, u32> = dim_bodily_injuries
+            .iter()
+            .map(|b| (b.bodily_injury, b.bodily_injury_id))
+            .collect();
+
+        let safety_equipment_by_type: HashMap<SafetyEquipment, u32> = dim_safety_equipment
+            .iter()
+            .map(|e| (e.safety_equipment, e.safety_equipment_id))
+            .collect();
+
+        let pedestrian_action_by_key: HashMap<(PedestrianLocation, PedestrianAction), u32> =
+            dim_pedestrian_actions
+                .iter()
+                .map(|p| {
+                    (
+                        (p.pedestrian_location, p.pedestrian_action),
+                        p.pedestrian_action_id,
+                    )
+                })
+                .collect();
+
+        // Index crashes by crash_id.
+        let crash_by_id: HashMap<u32, Crash> =
+            crashes.into_iter().map(|c| (c.crash_id, c)).collect();
+
+        let crash_keys = CrashKeys::new(
+            dim_times,
+            dim_factors,
+            dim_factor_bridge,
+            dim_locations,
+            dim_severities,
+        );
+
+        // Resolve each crash's location once rather than once per person.
+        let location_by_crash_id: HashMap<u32, u32> = crash_by_id
+            .values()
+            .map(|c| (c.crash_id, crash_keys.location_id(c)))
+            .collect();
+
+        Self {
+            crash_by_id,
+            location_by_crash_id,
+            age_by_age,
+            unknown_age_id,
+            position_by_type,
+            role_by_type,
+            sex_by_type,
+            type_by_type,
+            crash_keys,
+            vehicle_type_by_type,
+            vehicle_ordinals: VehicleOrdinals::default(),
+            vehicle_type_by_vehicle_id: HashMap::new(),
+            injury_by_key,
+            bodily_injury_by_type,
+            safety_equipment_by_type,
+            pedestrian_action_by_key,
+        }
+    }
+
+    /// Sets the weights of the severity index; defaults to
+    /// [`SeverityConfig::default`].
+    pub fn with_severity_weights(mut self, weights: SeverityConfig) -> Self {
+        self.crash_keys.severity_weights = weights;
+        self
+    }
+
+    /// Sets the vehicle positions used to attribute each person to the
+    /// contributing factor of their own vehicle; see [`FactBuilder::build`].
+    ///
+    /// Only for runs without the vehicles file, whose positions have to be
+    /// inferred from the vehicles the persons occupy.
+    pub fn with_vehicle_ordinals(mut self, vehicle_ordinals: VehicleOrdinals) -> Self {
+        self.vehicle_ordinals = vehicle_ordinals;
+        self
+    }
+
+    /// Sets the vehicles persons are matched to by `vehicle_id`, for their
+    /// vehicle type and for the slot of their vehicle on the crash record.
+    /// Replaces any [`with_vehicle_ordinals`](Self::with_vehicle_ordinals), so
+    /// that type and factor come from the same vehicle. Persons whose vehicle
+    /// has no type fall back to the `VEHICLE TYPE CODE` of its slot.
+    pub fn with_vehicles(mut self, vehicles: impl IntoIterator<Item = Vehicle>) -> Self {
+        self.vehicle_ordinals = VehicleOrdinals::default();
+        self.vehicle_type_by_vehicle_id.clear();
+        for v in vehicles {
+            self.vehicle_ordinals.insert(v.crash_id, v.vehicle_id);
+            if let Some(vehicle_type) = v.vehicle_type {
+                self.vehicle_type_by_vehicle_id
+                    .insert(v.vehicle_id, vehicle_type);
+            }
+        }
+        self
+    }
+
+    /// Number of crashes in the index.
+    pub fn crash_count(&self) -> usize {
+        self.crash_by_id.len()
+    }
+
+    pub fn contains_crash(&self, crash_id: u32) -> bool {
+        self.crash_by_id.contains_key(&crash_id)
+    }
+
+    /// Builds the fact row for `person`, or `None` if their crash is unknown
+    /// or has no member in the Time dimension.
+    ///
+    /// The contributing factor is the person's own `CONTRIBUTING_FACTOR_1`
+    /// when reported (pedestrians, cyclists), else the factor of their
+    /// vehicle's slot on the crash record, and only for persons whose vehicle
+    /// cannot be placed the first factor of the crash.
+    pub fn build(&self, fact_id: u32, person: &Person) -> Option<Fact> {
+        let crash = self.crash_by_id.get(&person.crash_id)?;
+        let time_id = self.crash_keys.time_id(crash)?;
+
+        // Resolve contributing factor id.
+        let contributing_factor_id = self.crash_keys.factor_id(self.person_factor(person, crash));
+
+        // Resolve factor group id; crashes without a recognised factor are group 0.
+        let factor_group_id = self.crash_keys.factor_group_id(crash);
+
+        // Resolve location id; crashes without any location are location 0.
+        let location_id = *self.location_by_crash_id.get(&crash.crash_id).unwrap_or(&0);
+
+        // Resolve person age id.
+        let person_age_id = person
+            .person_age
+            .and_then(|age| self.age_by_age.get(&age).copied())
+            .unwrap_or(self.unknown_age_id);
+
+        // Resolve person position id.
+        let position_dm = person
+            .person_position_in_vehicle
+            .map(base_position_to_dm)
+            .unwrap_or(PersonPositionInVehicle::Unknown);
+        let person_position_id = *self.position_by_type.get(&position_dm).unwrap_or(&0);
+
+        // Resolve person role id.
+        let role_dm = person
+            .person_role
+            .map(base_role_to_dm)
+            .unwrap_or(PersonRole::Unknown);
+        let person_role_id = *self.role_by_type.get(&role_dm).unwrap_or(&0);
+
+        // Resolve person sex id.
+        let sex_dm = person
+            .person_sex
+            .map(base_sex_to_dm)
+            .unwrap_or(PersonSexType::Unknown);
+        let person_sex_id = *self.sex_by_type.get(&sex_dm).unwrap_or(&0);
+
+        // Resolve person type id.
+        let type_dm = person
+            .person_type
+            .map(base_type_to_dm)
+            .unwrap_or(PersonTypeType::Unknown);
+        let person_type_id = *self.type_by_type.get(&type_dm).unwrap_or(&0);
+
+        // Resolve vehicle type id.
+        let vehicle_type_dm = self
+            .person_vehicle_type(person, crash)
+            .map(VehicleType::from)
+            .unwrap_or(VehicleType::Unknown);
+        let vehicle_type_id = *self
+            .vehicle_type_by_type
+            .get(&vehicle_type_dm)
+            .unwrap_or(&0);
+
+        // Resolve the person's injury outcome ids.
+        let injury_key = (
+            person
+                .person_injury
+                .map_or(InjurySeverity::Unknown, InjurySeverity::from),
+            person.ejection.map_or(Ejection::Unknown, Ejection::from),
+            person
+                .emotional_status
+                .map_or(EmotionalStatus::Unknown, EmotionalStatus::from),
+        );
+        let person_injury_id = *self.injury_by_key.get(&injury_key).unwrap_or(&0);
+
+        let bodily_injury_dm = person
+            .bodily_injury
+            .map_or(BodilyInjury::Unknown, BodilyInjury::from);
+        let bodily_injury_id = *self
+            .bodily_injury_by_type
+            .get(&bodily_injury_dm)
+            .unwrap_or(&0);
+
+        let safety_equipment_dm = person
+            .safety_equipment
+            .map_or(SafetyEquipment::Unknown, SafetyEquipment::from);
+        let safety_equipment_id = *self
+            .safety_equipment_by_type
+            .get(&safety_equipment_dm)
+            .unwrap_or(&0);
+
+        let pedestrian_key = (
+            person
+                .ped_location
+                .map_or(PedestrianLocation::Unknown, PedestrianLocation::from),
+            person
+                .ped_action
+                .map_or(PedestrianAction::Unknown, PedestrianAction::from),
+        );
+        let pedestrian_action_id = *self
+            .pedestrian_action_by_key
+            .get(&pedestrian_key)
+            .unwrap_or(&0);
+
+        // The same totals as the severity class, so that a fatal crash never
+        // reports nobody killed.
+        let (persons_injured, persons_killed) = casualties(crash);
+
+        Some(Fact {
+            fact_id,
+            bodily_injury_id,
+            contributing_factor_id,
+            factor_group_id,
+            location_id,
+            pedestrian_action_id,
+            person_age_id,
+            person_injury_id,
+            person_position_id,
+            person_role_id,
+            person_sex_id,
+            person_type_id,
+            safety_equipment_id,
+            severity_id: self.crash_keys.severity_id(crash),
+            time_id,
+            vehicle_type_id,
+            is_injured: person.person_injury == Some(person::PersonInjury::Injured),
+            is_killed: person.person_injury == Some(person::PersonInjury::Killed),
+            persons_injured,
+            persons_killed,
+            pedestrians_injured: crash.crash_pedestrians_injured,
+            pedestrians_killed: crash.crash_pedestrians_killed,
+            cyclist_injured: crash.crash_cyclist_injured,
+            cyclist_killed: crash.crash_cyclist_killed,
+            motorist_injured: crash.crash_motorist_injured,
+            motorist_killed: crash.crash_motorist_killed,
+        })
+    }
+
+    fn person_factor(&self, person: &Person, crash: &Crash) -> Option<CrashFactor> {
+        if let Some(&factor) = person.person_factors.first() {
+            return Some(factor);
+        }
+        let vehicle_slot = self
+            .vehicle_slot(person)
+            .and_then(|i| crash.vehicle_factors.get(i));
+        match vehicle_slot {
+            // an unspecified slot is the vehicle's factor, not the crash's
+            Some(&factor) => factor,
+            None => crash.crash_factor,
+        }
+    }
+
+    fn person_vehicle_type(&self, person: &Person, crash: &Crash) -> Option<vehicle::VehicleType> {
+        let vehicle_id = person.vehicle_id?;
+        match self.vehicle_type_by_vehicle_id.get(&vehicle_id) {
+            Some(&vehicle_type) => Some(vehicle_type),
+            None => crash.vehicle_types[self.vehicle_slot(person)?],
+        }
+    }
+
+    /// Position of the person's vehicle among the vehicles of their crash.
+    fn vehicle_slot(&self, person: &Person) -> Option<usize> {
+        let ordinal = self
+            .vehicle_ordinals
+            .ordinal(person.crash_id, person.vehicle_id?)?;
+        // the crash record has room for five vehicles
+        (ordinal < 5).then_some(ordinal)
+    }
+}
+
+/// Resolves the dimension keys that depend on the crash alone; shared by the
+/// person-grain [`FactBuilder`] and the crash-grain
+/// [`FactCrashBuilder`](crate::data_mart::fact_crash::FactCrashBuilder).
+pub(crate) struct CrashKeys {
+    factor_by_factor: HashMap<ContributingFactor, u32>,
+    factor_group_keys: FactorGroupKeys,
+    factor_group_by_key: HashMap<Vec<u32>, u32>,
+    location_by_key: HashMap<LocationKey, u32>,
+    dm_time_by_bdb_time_id: HashMap<u32, u32>,
+    dm_time_by_timestamp: HashMap<PrimitiveDateTime, u32>,
+    severity_by_severity: HashMap<CrashSeverity, u32>,
+    pub(crate) severity_weights: SeverityConfig,
+}
+
+impl CrashKeys {
+    pub(crate) fn new(
+        dim_times: &[Time],
+        dim_factors: &[ContributingFactorDim],
+        dim_factor_bridge: &[FactorGroupBridge],
+        dim_locations: &[LocationDim],
+        dim_severities: &[SeverityDim],
+    ) -> Self {
         let factor_by_factor: HashMap<ContributingFactor, u32> = dim_factors
             .iter()
             .map(|f| (f.contributing_factor, f.contributing_factor_id))
             .collect();
 
-        // Index crashes by crash_id.
-        let crash_by_id: HashMap<u32, &Crash> = crashes.iter().map(|c| (c.crash_id, c)).collect();
+        // Factor groups are keyed by the sorted ids of their member factors.
+        let mut factor_group_by_key: HashMap<Vec<u32>, u32> = HashMap::new();
+        let mut members: HashMap<u32, Vec<u32>> = HashMap::new();
+        for b in dim_factor_bridge.iter().filter(|b| b.factor_group_id != 0) {
+            members
+                .entry(b.factor_group_id)
+                .or_default()
+                .push(b.contributing_factor_id);
+        }
+        for (group_id, mut factor_ids) in members {
+            factor_ids.sort_unstable();
+            factor_group_by_key.insert(factor_ids, group_id);
+        }
+
+        let location_by_key: HashMap<LocationKey, u32> = dim_locations
+            .iter()
+            .map(|l| (l.key(), l.location_id))
+            .collect();
 
         // The data-mart Time dimension is 1-indexed and ordered by timestamp.
         // Build a lookup from the base-database time_id → data-mart time_id.
@@ -113,88 +487,73 @@ impl Fact {
         let dm_time_by_timestamp: HashMap<PrimitiveDateTime, u32> =
             dim_times.iter().map(|t| (t.timestamp, t.time_id)).collect();
 
-        persons
-            .into_iter()
-            .enumerate()
-            .filter_map(|(i, person)| {
-                let crash = crash_by_id.get(&person.crash_id)?;
-
-                // Resolve time_id: crash carries a bdb time_id; map to dm time_id.
-                let time_id: u32 = crash
-                    .time_id
-                    .and_then(|bdb_id| dm_time_by_bdb_time_id.get(&bdb_id).copied())
-                    .or_else(|| {
-                        // Fall back: look up the dm time by hour-truncated timestamp (O(1)).
-                        let crash_hour = crash
-                            .crash_timestamp
-                            .replace_minute(0)
-                            .and_then(|t| t.replace_second(0))
-                            .and_then(|t| t.replace_nanosecond(0))
-                            .ok()?;
-                        dm_time_by_timestamp.get(&crash_hour).copied()
-                    })?;
-
-                // Resolve contributing factor id.
-                let cf = crash
-                    .crash_factor
-                    .map(base_crash_factor_to_dm)
-                    .unwrap_or(ContributingFactor::Unknown);
-                let contributing_factor_id = *factor_by_factor.get(&cf).unwrap_or(&0);
-
-                // Resolve person age id.
-                let person_age_id = person
-                    .person_age
-                    .and_then(|age| age_by_age.get(&age).copied())
-                    .unwrap_or(unknown_age_id);
-
-                // Resolve person position id.
-                let position_dm = person
-                    .person_position_in_vehicle
-                    .map(base_position_to_dm)
-                    .unwrap_or(PersonPositionInVehicle::Unknown);
-                let person_position_id = *position_by_type.get(&position_dm).unwrap_or(&0);
-
-                // Resolve person role id.
-                let role_dm = person
-                    .person_role
-                    .map(base_role_to_dm)
-                    .unwrap_or(PersonRole::Unknown);
-                let person_role_id = *role_by_type.get(&role_dm).unwrap_or(&0);
-
-                // Resolve person sex id.
-                let sex_dm = person
-                    .person_sex
-                    .map(base_sex_to_dm)
-                    .unwrap_or(PersonSexType::Unknown);
-                let person_sex_id = *sex_by_type.get(&sex_dm).unwrap_or(&0);
-
-                // Resolve person type id.
-                let type_dm = person
-                    .person_type
-                    .map(base_type_to_dm)
-                    .unwrap_or(PersonTypeType::Unknown);
-                let person_type_id = *type_by_type.get(&type_dm).unwrap_or(&0);
-
-                Some(Fact {
-                    fact_id: i as u32 + 1, // 1-indexed, 0 reserved as uninitialised sentinel
-                    contributing_factor_id,
-                    person_age_id,
-                    person_position_id,
-                    person_role_id,
-                    person_sex_id,
-                    person_type_id,
-                    time_id,
-                    persons_injured: crash.crash_persons_injured.min(u8::MAX as u16) as u8,
-                    persons_killed: crash.crash_persons_killed.min(u8::MAX as u16) as u8,
-                    pedestrians_injured: crash.crash_pedestrians_injured.min(u8::MAX as u16) as u8,
-                    pedestrians_killed: crash.crash_pedestrians_killed.min(u8::MAX as u16) as u8,
-                    cyclist_injured: crash.crash_cyclist_injured.min(u8::MAX as u16) as u8,
-                    cyclist_killed: crash.crash_cyclist_killed.min(u8::MAX as u16) as u8,
-                    motorist_injured: crash.crash_motorist_injured.min(u8::MAX as u16) as u8,
-                    motorist_killed: crash.crash_motorist_killed.min(u8::MAX as u16) as u8,
-                })
+        Self {
+            factor_by_factor,
+            factor_group_keys: FactorGroupKeys::new(dim_factors),
+            factor_group_by_key,
+            location_by_key,
+            dm_time_by_bdb_time_id,
+            dm_time_by_timestamp,
+            severity_by_severity: dim_severities
+                .iter()
+                .map(|s| (s.severity, s.severity_id))
+                .collect(),
+            severity_weights: SeverityConfig::default(),
+        }
+    }
+
+    /// The data-mart time of the crash, or `None` if its hour is not in the
+    /// Time dimension.
+    pub(crate) fn time_id(&self, crash: &Crash) -> Option<u32> {
+        // Resolve time_id: crash carries a bdb time_id; map to dm time_id.
+        crash
+            .time_id
+            .and_then(|bdb_id| self.dm_time_by_bdb_time_id.get(&bdb_id).copied())
+            .or_else(|| {
+                // Fall back: look up the dm time by hour-truncated timestamp (O(1)).
+                let crash_hour = crash
+                    .crash_timestamp
+                    .replace_minute(0)
+                    .and_then(|t| t.replace_second(0))
+                    .and_then(|t| t.replace_nanosecond(0))
+                    .ok()?;
+                self.dm_time_by_timestamp.get(&crash_hour).copied()
             })
-            .collect()
+    }
+
+    /// Id of `factor`; no factor is Unknown.
+    pub(crate) fn factor_id(&self, factor: Option<CrashFactor>) -> u32 {
+        let cf = factor
+            .map(ContributingFactor::from)
+            .unwrap_or(ContributingFactor::Unknown);
+        *self.factor_by_factor.get(&cf).unwrap_or(&0)
+    }
+
+    /// Id of the group of all factors of the crash; crashes without a
+    /// recognised factor are group 0.
+    pub(crate) fn factor_group_id(&self, crash: &Crash) -> u32 {
+        *self
+            .factor_group_by_key
+            .get(&self.factor_group_keys.key(crash))
+            .unwrap_or(&0)
+    }
+
+    /// Id of the crash's severity class.
+    pub(crate) fn severity_id(&self, crash: &Crash) -> u32 {
+        *self
+            .severity_by_severity
+            .get(&CrashSeverity::of(crash))
+            .unwrap_or(&0)
+    }
+
+    /// Weighted severity index of the crash.
+    pub(crate) fn severity_index(&self, crash: &Crash) -> u32 {
+        severity_index(crash, &self.severity_weights)
+    }
+
+    /// Id of the crash's location; crashes without any location are location 0.
+    pub(crate) fn location_id(&self, crash: &Crash) -> u32 {
+        *self.location_by_key.get(&location_key(crash)).unwrap_or(&0)
     }
 }
 
@@ -202,71 +561,6 @@ impl Fact {
 // Mapping helpers: base_database enums → data_mart enums
 // ---------------------------------------------------------------------------
 
-fn base_crash_factor_to_dm(f: crate::base_database::crash::CrashFactor) -> ContributingFactor {
-    use crate::base_database::crash::CrashFactor as B;
-    match f {
-        B::DriverlessRunawayVehicle => ContributingFactor::DriverlessRunawayVehicle,
-        B::ListeningUsingHeadphones => ContributingFactor::ListeningUsingHeadphones,
-        B::EatingOrDrinking => ContributingFactor::EatingOrDrinking,
-        B::UnsafeLaneChanging => ContributingFactor::UnsafeLaneChanging,
-        B::CellPhoneHandHeld => ContributingFactor::CellPhoneHandHeld,
-        B::CellPhoneHandsFree => ContributingFactor::CellPhoneHandsFree,
-        B::DrugsIllegal => ContributingFactor::DrugsIllegal,
-        B::Texting => ContributingFactor::Texting,
-        B::HeadlightsDefective => ContributingFactor::HeadlightsDefective,
-        B::OtherLightingDefects => ContributingFactor::OtherLightingDefects,
-        B::DriverInexperience => ContributingFactor::DriverInexperience,
-        B::AggressiveDrivingRoadRage => ContributingFactor::AggressiveDrivingRoadRage,
-        B::UnsafeSpeed => ContributingFactor::UnsafeSpeed,
-        B::LaneMarkingImproperInadequate => ContributingFactor::LaneMarkingImproperInadequate,
-        B::Glare => ContributingFactor::Glare,
-        B::TrafficControlDeviceImproperNonWorking => {
-            ContributingFactor::TrafficControlDeviceImproperNonWorking
-        }
-        B::PassingTooClosely => ContributingFactor::PassingTooClosely,
-        B::AcceleratorDefective => ContributingFactor::AcceleratorDefective,
-        B::ShouldersDefectiveImproper => ContributingFactor::ShouldersDefectiveImproper,
-        B::OutsideCarDistraction => ContributingFactor::OutsideCarDistraction,
-        B::DriverInattentionDistraction => ContributingFactor::DriverInattentionDistraction,
-        B::TintedWindows => ContributingFactor::TintedWindows,
-        B::UsingOnBoardNavigationDevice => ContributingFactor::UsingOnBoardNavigationDevice,
-        B::ReactionToOtherUninvolvedVehicle => ContributingFactor::ReactionToOtherUninvolvedVehicle,
-        B::ObstructionDebris => ContributingFactor::ObstructionDebris,
-        B::PrescriptionMedication => ContributingFactor::PrescriptionMedication,
-        B::TireFailureInadequate => ContributingFactor::TireFailureInadequate,
-        B::FatiguedDrowsy => ContributingFactor::FatiguedDrowsy,
-        B::PassingOrLaneUsageImproper => ContributingFactor::PassingOrLaneUsageImproper,
-        B::FollowingTooClosely => ContributingFactor::FollowingTooClosely,
-        B::ViewObstructedLimited => ContributingFactor::ViewObstructedLimited,
-        B::OversizedVehicle => ContributingFactor::OversizedVehicle,
-        B::LostConsciousness => ContributingFactor::LostConsciousness,
-        B::BackingUnsafely => ContributingFactor::BackingUnsafely,
-        B::OtherVehicular => ContributingFactor::OtherVehicular,
-        B::Illness => ContributingFactor::Illness,
-        B::WindshieldInadequate => ContributingFactor::WindshieldInadequate,
-        B::FellAsleep => ContributingFactor::FellAsleep,
-        B::TrafficControlDisregarded => ContributingFactor::TrafficControlDisregarded,
-        B::PavementDefective => ContributingFactor::PavementDefective,
-        B::SteeringFailure => ContributingFactor::SteeringFailure,
-        B::PassengerDistraction => ContributingFactor::PassengerDistraction,
-        B::VehicleVandalism => ContributingFactor::VehicleVandalism,
-        B::FailureToKeepRight => ContributingFactor::FailureToKeepRight,
-        B::BrakesDefective => ContributingFactor::BrakesDefective,
-        B::TurningImproperly => ContributingFactor::TurningImproperly,
-        B::FailureToYieldRightOfWay => ContributingFactor::FailureToYieldRightOfWay,
-        B::ReactionToUninvolvedVehicle => ContributingFactor::ReactionToUninvolvedVehicle,
-        B::TowHitchDefective => ContributingFactor::TowHitchDefective,
-        B::AlcoholInvolvement => ContributingFactor::AlcoholInvolvement,
-        B::PhysicalDisability => ContributingFactor::PhysicalDisability,
-        B::AnimalsAction => ContributingFactor::AnimalsAction,
-        B::OtherElectronicDevice => ContributingFactor::OtherElectronicDevice,
-        B::PedestrianBicyclistOtherPedestrianErrorConfusion => {
-            ContributingFactor::PedestrianBicyclistOtherPedestrianErrorConfusion
-        }
-        B::PavementSlippery => ContributingFactor::PavementSlippery,
-    }
-}
-
 fn base_position_to_dm(
     p: crate::base_database::person::PersonPositionInVehicle,
 ) -> PersonPositionInVehicle {
@@ -312,3 +606,203 @@ fn base_type_to_dm(t: crate::base_database::person::PersonType) -> PersonTypeTyp
         B::OtherMotorized => PersonTypeType::OtherMotorized,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_crash() -> Crash {
+        Crash {
+            crash_factor: Some(CrashFactor::UnsafeSpeed),
+            crash_factors: vec![CrashFactor::UnsafeSpeed],
+            ..Crash::sample()
+        }
+    }
+
+    /// A builder over `crash` with empty dimensions.
+    fn builder(crash: &Crash) -> FactBuilder {
+        FactBuilder::new(
+            [crash.clone()],
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
+        )
+    }
+
+    fn person(vehicle_id: Option<u32>, person_factors: Vec<CrashFactor>) -> Person {
+        Person {
+            person_id: 0,
+            person_type: None,
+            person_age: None,
+            person_sex: None,
+            person_position_in_vehicle: None,
+            person_role: None,
+            person_injury: None,
+            ejection: None,
+            emotional_status: None,
+            bodily_injury: None,
+            safety_equipment: None,
+            ped_location: None,
+            ped_action: None,
+            crash_id: 1,
+            vehicle_id,
+            person_factors,
+        }
+    }
+
+    #[test]
+    fn persons_get_their_own_vehicles_factor() {
+        let crash = Crash {
+            vehicle_factors: [Some(CrashFactor::UnsafeSpeed), None, None, None, None],
+            ..sample_crash()
+        };
+        let mut ordinals = VehicleOrdinals::default();
+        ordinals.insert(1, 20);
+        ordinals.insert(1, 10);
+        let builder = builder(&crash).with_vehicle_ordinals(ordinals);
+
+        let first = person(Some(10), vec![]);
+        assert_eq!(
+            builder.person_factor(&first, &crash),
+            Some(CrashFactor::UnsafeSpeed)
+        );
+        // the second vehicle reported no factor, so neither did its driver
+        let second = person(Some(20), vec![]);
+        assert_eq!(builder.person_factor(&second, &crash), None);
+
+        let pedestrian = person(
+            None,
+            vec![CrashFactor::PedestrianBicyclistOtherPedestrianErrorConfusion],
+        );
+        assert_eq!(
+            builder.person_factor(&pedestrian, &crash),
+            Some(CrashFactor::PedestrianBicyclistOtherPedestrianErrorConfusion)
+        );
+        let unplaced = person(Some(30), vec![]);
+        assert_eq!(
+            builder.person_factor(&unplaced, &crash),
+            Some(CrashFactor::UnsafeSpeed)
+        );
+    }
+
+    #[test]
+    fn vehicle_type_prefers_the_vehicles_file() {
+        let crash = Crash {
+            vehicle_types: [Some(vehicle::VehicleType::Sedan), None, None, None, None],
+            ..sample_crash()
+        };
+        let vehicle = |vehicle_id, vehicle_type| Vehicle {
+            vehicle_id,
+            crash_id: 1,
+            vehicle_type,
+        };
+        let builder = builder(&crash).with_vehicles([
+            vehicle(10, None),
+            vehicle(20, Some(vehicle::VehicleType::EBike)),
+        ]);
+
+        let sedan_driver = person(Some(10), vec![]);
+        assert_eq!(
+            builder.person_vehicle_type(&sedan_driver, &crash),
+            Some(vehicle::VehicleType::Sedan)
+        );
+        let rider = person(Some(20), vec![]);
+        assert_eq!(
+            builder.person_vehicle_type(&rider, &crash),
+            Some(vehicle::VehicleType::EBike)
+        );
+        let pedestrian = person(None, vec![]);
+        assert_eq!(builder.person_vehicle_type(&pedestrian, &crash), None);
+        let unlisted = person(Some(30), vec![]);
+        assert_eq!(builder.person_vehicle_type(&unlisted, &crash), None);
+    }
+
+    #[test]
+    fn vehicles_without_persons_keep_their_slot() {
+        let crash = Crash {
+            vehicle_factors: [
+                Some(CrashFactor::UnsafeSpeed),
+                Some(CrashFactor::Glare),
+                Some(CrashFactor::DriverInexperience),
+                None,
+                None,
+            ],
+            vehicle_types: [
+                Some(vehicle::VehicleType::Sedan),
+                Some(vehicle::VehicleType::Taxi),
+                Some(vehicle::VehicleType::Bus),
+                None,
+                None,
+            ],
+            ..sample_crash()
+        };
+        // vehicle 20 is parked and has no person rows
+        let builder = builder(&crash).with_vehicles([10, 20, 30].map(|vehicle_id| Vehicle {
+            vehicle_id,
+            crash_id: 1,
+            vehicle_type: None,
+        }));
+
+        let bus_driver = person(Some(30), vec![]);
+        assert_eq!(
+            builder.person_factor(&bus_driver, &crash),
+            Some(CrashFactor::DriverInexperience)
+        );
+        assert_eq!(
+            builder.person_vehicle_type(&bus_driver, &crash),
+            Some(vehicle::VehicleType::Bus)
+        );
+    }
+
+    #[test]
+    fn fact_csv_round_trips() {
+        let fact = Fact {
+            fact_id: 1,
+            bodily_injury_id: 20,
+            contributing_factor_id: 2,
+            factor_group_id: 17,
+            location_id: 19,
+            pedestrian_action_id: 21,
+            person_age_id: 3,
+            person_injury_id: 22,
+            person_position_id: 4,
+            person_role_id: 5,
+            person_sex_id: 6,
+            person_type_id: 7,
+            safety_equipment_id: 23,
+            severity_id: 24,
+            time_id: 8,
+            vehicle_type_id: 18,
+            is_injured: true,
+            is_killed: false,
+            persons_injured: 9,
+            persons_killed: 10,
+            pedestrians_injured: 11,
+            pedestrians_killed: 12,
+            cyclist_injured: 13,
+            cyclist_killed: 14,
+            motorist_injured: 15,
+            motorist_killed: 16,
+        };
+        let mut wtr = csv::Writer::from_writer(vec![]);
+        wtr.serialize(fact).unwrap();
+        let bytes = wtr.into_inner().unwrap();
+        let read: Vec<Fact> = csv::Reader::from_reader(bytes.as_slice())
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(read, vec![fact]);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::base_database::crash::Crash;
+use crate::config::SeverityConfig;
+use crate::data_mart::{
+    contributing_factor::ContributingFactorDim,
+    fact::CrashKeys,
+    factor_group::FactorGroupBridge,
+    location::LocationDim,
+    severity::{SeverityDim, casualties},
+    time::Time,
+};
+use serde::{Deserialize, Serialize};
+
+/// One row per collision, so that summing the injury counts counts every
+/// victim once; the person-grain [`Fact`](crate::data_mart::fact::Fact)
+/// repeats them for every person of the crash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FactCrash {
+    // degenerate dimension: the NYPD COLLISION_ID
+    pub crash_id: u32,
+
+    // dimensions
+    pub contributing_factor_id: u32,
+    pub factor_group_id: u32,
+    pub location_id: u32,
+    pub severity_id: u32,
+    pub time_id: u32,
+
+    // measures
+    pub persons_injured: u16,
+    pub persons_killed: u16,
+    pub pedestrians_injured: u16,
+    pub pedestrians_killed: u16,
+    pub cyclist_injured: u16,
+    pub cyclist_killed: u16,
+    pub motorist_injured: u16,
+    pub motorist_killed: u16,
+    pub severity_index: u32,
+}
+
+impl FactCrash {
+    /// Build fact rows — one row per crash that has a member in the Time
+    /// dimension.
+    pub fn gen_crash_facts<'a>(
+        crashes: impl IntoIterator<Item = &'a Crash>,
+        dim_times: &[Time],
+        dim_factors: &[ContributingFactorDim],
+        dim_factor_bridge: &[FactorGroupBridge],
+        dim_locations: &[LocationDim],
+        dim_severities: &[SeverityDim],
+        severity_weights: SeverityConfig,
+    ) -> Vec<FactCrash> {
+        let builder = FactCrashBuilder::new(
+            dim_times,
+            dim_factors,
+            dim_factor_bridge,
+            dim_locations,
+            dim_severities,
+        )
+        .with_severity_weights(severity_weights);
+        crashes
+            .into_iter()
+            .filter_map(|c| builder.build(c))
+            .collect()
+    }
+}
+
+/// Turns crashes into crash-grain fact rows.
+pub struct FactCrashBuilder {
+    crash_keys: CrashKeys,
+}
+
+impl FactCrashBuilder {
+    pub fn new(
+        dim_times: &[Time],
+        dim_factors: &[ContributingFactorDim],
+        dim_factor_bridge: &[FactorGroupBridge],
+        dim_locations: &[LocationDim],
+        dim_severities: &[SeverityDim],
+    ) -> Self {
+        Self {
+            crash_keys: CrashKeys::new(
+                dim_times,
+                dim_factors,
+                dim_factor_bridge,
+                dim_locations,
+                dim_severities,
+            ),
+        }
+    }
+
+    /// Sets the weights of the severity index; defaults to
+    /// [`SeverityConfig::default`].
+    pub fn with_severity_weights(mut self, weights: SeverityConfig) -> Self {
+        self.crash_keys.severity_weights = weights;
+        self
+    }
+
+    /// Builds the fact row for `crash`, or `None` if it has no member in the
+    /// Time dimension.
+    ///
+    /// The contributing factor is the first factor of the crash; all of them
+    /// are reachable through the factor group. The person totals are those
+    /// the severity class is derived from.
+    pub fn build(&self, crash: &Crash) -> Option<FactCrash> {
+        let (persons_injured, persons_killed) = casualties(crash);
+        Some(FactCrash {
+            crash_id: crash.crash_id,
+            contributing_factor_id: self.crash_keys.factor_id(crash.crash_factor),
+            factor_group_id: self.crash_keys.factor_group_id(crash),
+            location_id: self.crash_keys.location_id(crash),
+            severity_id: self.crash_keys.severity_id(crash),
+            time_id: self.crash_keys.time_id(crash)?,
+            persons_injured,
+            persons_killed,
+            pedestrians_injured: crash.crash_pedestrians_injured,
+            pedestrians_killed: crash.crash_pedestrians_killed,
+            cyclist_injured: crash.crash_cyclist_injured,
+            cyclist_killed: crash.crash_cyclist_killed,
+            motorist_injured: crash.crash_motorist_injured,
+            motorist_killed: crash.crash_motorist_killed,
+            severity_index: self.crash_keys.severity_index(crash),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base_database;
+    use time::{UtcOffset, macros::datetime};
+
+    fn crash(crash_id: u32, crash_persons_killed: u16) -> Crash {
+        Crash {
+            crash_id,
+            crash_timestamp: datetime!(2020-01-01 12:30),
+            crash_persons_killed,
+            crash_motorist_killed: crash_persons_killed,
+            ..Crash::sample()
+        }
+    }
+
+    fn noon() -> Time {
+        let bdb_time = base_database::time::Time {
+            time_id: 1,
+            timestamp: datetime!(2020-01-01 12:00),
+            utc_offset: UtcOffset::from_hms(-5, 0, 0).unwrap(),
+            dst_flag: base_database::timezone::DstFlag::Normal,
+            moon_phase: base_database::time::MoonPhase::New,
+            moon_phase_angle: 180.0,
+            moon_illumination: 0.0,
+            moon_age_days: 0.0,
+            sun_altitude: 25.9,
+            light_condition: base_database::time::LightCondition::Daylight,
+            weather: None,
+            weather_rule_id: None,
+            weather_measures: None,
+            snow_risk: None,
+        };
+        Time::gen_times(vec![bdb_time]).remove(0)
+    }
+
+    #[test]
+    fn one_row_per_crash_with_a_time() {
+        let outside = Crash {
+            crash_timestamp: datetime!(2021-01-01 12:00),
+            ..crash(2, 1)
+        };
+        let crashes = [crash(1, 2), outside];
+
+        let facts = FactCrash::gen_crash_facts(
+            &crashes,
+            &[noon()],
+            &[],
+            &[],
+            &[],
+            &[],
+            SeverityConfig::default(),
+        );
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].crash_id, 1);
+        assert_eq!(facts[0].time_id, 1);
+        // counted once, however many persons were involved
+        assert_eq!(facts[0].persons_killed, 2);
+    }
+
+    #[test]
+    fn counts_above_u8_are_not_truncated() {
+        let facts = FactCrash::gen_crash_facts(
+            &[crash(1, 300)],
+            &[noon()],
+            &[],
+            &[],
+            &[],
+            &[],
+            SeverityConfig::default(),
+        );
+        assert_eq!(facts[0].persons_killed, 300);
+        assert_eq!(facts[0].motorist_killed, 300);
+    }
+
+    #[test]
+    fn person_totals_match_the_severity_class() {
+        // the person totals were left at zero
+        let crash = Crash {
+            crash_persons_killed: 0,
+            crash_pedestrians_killed: 1,
+            ..crash(1, 1)
+        };
+        let facts = FactCrash::gen_crash_facts(
+            &[crash],
+            &[noon()],
+            &[],
+            &[],
+            &[],
+            &[],
+            SeverityConfig::default(),
+        );
+        assert_eq!(facts[0].persons_killed, 2);
+        assert_eq!(facts[0].motorist_killed, 1);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::base_database::crash::Crash;
+use crate::data_mart::contributing_factor::{ContributingFactor, ContributingFactorDim};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// A distinct combination of contributing factors that occurs on some crash.
+///
+/// Fact rows reference the group of their crash; the group fans out to its
+/// factors through [`FactorGroupBridge`]. Group 0 is the Unknown group of
+/// crashes without any recognised factor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FactorGroup {
+    pub factor_group_id: u32,
+    pub factor_count: u8,
+    /// The member factors, e.g. `ALCOHOL_INVOLVEMENT + UNSAFE_SPEED`.
+    pub factor_group_label: String,
+}
+
+/// Links a [`FactorGroup`] to each of its contributing factors.
+///
+/// Summing a measure over the bridge with `weighting_factor` (`1 / n` for a
+/// group of `n` factors) allocates it across the factors without
+/// double-counting; ignoring the weight counts it in full under every factor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FactorGroupBridge {
+    pub factor_group_id: u32,
+    pub contributing_factor_id: u32,
+    pub weighting_factor: f64,
+}
+
+impl FactorGroup {
+    /// Builds one group per distinct factor combination of `crashes`, plus the
+    /// Unknown group, and the bridge rows linking them to `dim_factors`.
+    pub fn gen_groups<'a>(
+        crashes: impl IntoIterator<Item = &'a Crash>,
+        dim_factors: &[ContributingFactorDim],
+    ) -> (Vec<FactorGroup>, Vec<FactorGroupBridge>) {
+        let factor_by_id: HashMap<u32, ContributingFactor> = dim_factors
+            .iter()
+            .map(|f| (f.contributing_factor_id, f.contributing_factor))
+            .collect();
+        let lookup = FactorGroupKeys::new(dim_factors);
+
+        let combinations: BTreeSet<Vec<u32>> = crashes
+            .into_iter()
+            .map(|c| lookup.key(c))
+            .filter(|key| !key.is_empty())
+            .collect();
+
+        let mut groups = vec![FactorGroup {
+            factor_group_id: 0,
+            factor_count: 0,
+            factor_group_label: "UNKNOWN".into(),
+        }];
+        let mut bridge = vec![FactorGroupBridge {
+            factor_group_id: 0,
+            contributing_factor_id: lookup.unknown_id,
+            weighting_factor: 1.0,
+        }];
+
+        for (i, factor_ids) in combinations.into_iter().enumerate() {
+            let factor_group_id = i as u32 + 1;
+            let label = factor_ids
+                .iter()
+                .map(|id| factor_name(factor_by_id[id]))
+                .collect::<Vec<_>>()
+                .join(" + ");
+            let weighting_factor = 1.0 / factor_ids.len() as f64;
+            groups.push(FactorGroup {
+                factor_group_id,
+                factor_count: factor_ids.len() as u8,
+                factor_group_label: label,
+            });
+            bridge.extend(
+                factor_ids
+                    .into_iter()
+                    .map(|contributing_factor_id| FactorGroupBridge {
+                        factor_group_id,
+                        contributing_factor_id,
+                        weighting_factor,
+                    }),
+            );
+        }
+
+        (groups, bridge)
+    }
+}
+
+/// Maps a crash to the key of its factor group: the sorted ids of its factors.
+pub(crate) struct FactorGroupKeys {
+    factor_by_factor: HashMap<ContributingFactor, u32>,
+    unknown_id: u32,
+}
+
+impl FactorGroupKeys {
+    pub(crate) fn new(dim_factors: &[ContributingFactorDim]) -> Self {
+        let factor_by_factor: HashMap<ContributingFactor, u32> = dim_factors
+            .iter()
+            .map(|f| (f.contributing_factor, f.contributing_factor_id))
+            .collect();
+        let unknown_id = *factor_by_factor
+            .get(&ContributingFactor::Unknown)
+            .unwrap_or(&0);
+        Self {
+            factor_by_factor,
+            unknown_id,
+        }
+    }
+
+    pub(crate) fn key(&self, crash: &Crash) -> Vec<u32> {
+        let ids: BTreeSet<u32> = crash
+            .crash_factors
+            .iter()
+            .filter_map(|f| self.factor_by_factor.get(&ContributingFactor::from(*f)))
+            .copied()
+            .collect();
+        ids.into_iter().collect()
+    }
+}
+
+fn factor_name(factor: ContributingFactor) -> String {
+    serde_json::to_value(factor)
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base_database::crash::CrashFactor;
+
+    fn crash(crash_id: u32, crash_factors: Vec<CrashFactor>) -> Crash {
+        Crash {
+            crash_id,
+            crash_factor: crash_factors.first().copied(),
+            crash_factors,
+            ..Crash::sample()
+        }
+    }
+
+    #[test]
+    fn groups_are_distinct_combinations_with_allocated_weights() {
+        let crashes = [
+            crash(
+                1,
+                vec![CrashFactor::UnsafeSpeed, CrashFactor::AlcoholInvolvement],
+            ),
+            crash(
+                2,
+                vec![CrashFactor::AlcoholInvolvement, CrashFactor::UnsafeSpeed],
+            ),
+            crash(3, vec![CrashFactor::UnsafeSpeed]),
+            crash(4, vec![]),
+        ];
+        let dim_factors = ContributingFactorDim::gen_factors();
+        let (groups, bridge) = FactorGroup::gen_groups(&crashes, &dim_factors);
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].factor_group_id, 0);
+        let pair = groups.iter().find(|g| g.factor_count == 2).unwrap();
+        assert!(pair.factor_group_label.contains("UNSAFE_SPEED"));
+        assert!(pair.factor_group_label.contains("ALCOHOL_INVOLVEMENT"));
+
+        for group in &groups {
+            let weights: f64 = bridge
+                .iter()
+                .filter(|b| b.factor_group_id == group.factor_group_id)
+                .map(|b| b.weighting_factor)
+                .sum();
+            assert!((weights - 1.0).abs() < 1e-9);
+        }
+    }
+}
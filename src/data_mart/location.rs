@@ -0,0 +1,211 @@
+use crate::base_database::{crash::Crash, location};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Edge length of a grid cell in degrees; 0.01° is about 1.1 km north–south
+/// and 0.85 km east–west at the latitude of New York.
+pub const GRID_CELL_DEGREES: f64 = 0.01;
+
+const UNKNOWN: &str = "UNKNOWN";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Borough {
+    Bronx,
+    Brooklyn,
+    Manhattan,
+    Queens,
+    StatenIsland,
+    Unknown,
+}
+
+/// Whether the borough or ZIP code was on the crash report or inferred from
+/// the coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LocationSource {
+    Reported,
+    Inferred,
+    Unknown,
+}
+
+/// A distinct borough / ZIP code / grid cell combination that occurs on some
+/// crash. Location 0 is the fully Unknown location.
+///
+/// Hierarchy:
+///   Level 1 – location_hier_def_grid_cell
+///   Level 2 – location_hier_def_zip_code
+///   Level 3 – location_hier_def_borough
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocationDim {
+    pub location_id: u32,
+    pub location_hier_def_borough: Borough,
+    pub location_borough_source: LocationSource,
+    /// Five-digit ZIP code, or `UNKNOWN`.
+    pub location_hier_def_zip_code: String,
+    pub location_zip_code_source: LocationSource,
+    /// South-west corner of the cell as `<latitude>_<longitude>`, e.g.
+    /// `40.62_-73.98`, or `UNKNOWN`.
+    pub location_hier_def_grid_cell: String,
+    /// Centre of the grid cell.
+    pub grid_cell_latitude: Option<f64>,
+    pub grid_cell_longitude: Option<f64>,
+}
+
+/// Natural key of a [`LocationDim`] row.
+pub(crate) type LocationKey = (Borough, LocationSource, String, LocationSource, String);
+
+/// Borough, ZIP code and grid cell indices, before formatting.
+type RawLocation = (
+    Borough,
+    LocationSource,
+    Option<String>,
+    LocationSource,
+    Option<(i32, i32)>,
+);
+
+impl LocationDim {
+    /// Builds one row per distinct location of `crashes`, plus the Unknown
+    /// location.
+    pub fn gen_locations<'a>(crashes: impl IntoIterator<Item = &'a Crash>) -> Vec<LocationDim> {
+        let mut cells: BTreeSet<RawLocation> = crashes
+            .into_iter()
+            .map(|c| {
+                (
+                    c.location.borough.map_or(Borough::Unknown, Borough::from),
+                    source(c.location.borough_source),
+                    c.location.zip_code.clone(),
+                    source(c.location.zip_code_source),
+                    c.location.coordinates.map(grid_cell),
+                )
+            })
+            .collect();
+        let unknown = (
+            Borough::Unknown,
+            LocationSource::Unknown,
+            None,
+            LocationSource::Unknown,
+            None,
+        );
+        cells.remove(&unknown);
+
+        std::iter::once(unknown)
+            .chain(cells)
+            .enumerate()
+            .map(
+                |(i, (borough, borough_source, zip_code, zip_code_source, cell))| LocationDim {
+                    location_id: i as u32,
+                    location_hier_def_borough: borough,
+                    location_borough_source: borough_source,
+                    location_hier_def_zip_code: zip_code.unwrap_or_else(|| UNKNOWN.into()),
+                    location_zip_code_source: zip_code_source,
+                    location_hier_def_grid_cell: cell.map_or_else(|| UNKNOWN.into(), cell_label),
+                    grid_cell_latitude: cell.map(|(lat, _)| (lat as f64 + 0.5) * GRID_CELL_DEGREES),
+                    grid_cell_longitude: cell
+                        .map(|(_, lon)| (lon as f64 + 0.5) * GRID_CELL_DEGREES),
+                },
+            )
+            .collect()
+    }
+
+    pub(crate) fn key(&self) -> LocationKey {
+        (
+            self.location_hier_def_borough,
+            self.location_borough_source,
+            self.location_hier_def_zip_code.clone(),
+            self.location_zip_code_source,
+            self.location_hier_def_grid_cell.clone(),
+        )
+    }
+}
+
+/// The key of the [`LocationDim`] row of `crash`.
+pub(crate) fn location_key(crash: &Crash) -> LocationKey {
+    let location = &crash.location;
+    (
+        location.borough.map_or(Borough::Unknown, Borough::from),
+        source(location.borough_source),
+        location.zip_code.clone().unwrap_or_else(|| UNKNOWN.into()),
+        source(location.zip_code_source),
+        location
+            .coordinates
+            .map(grid_cell)
+            .map_or_else(|| UNKNOWN.into(), cell_label),
+    )
+}
+
+fn source(provenance: Option<location::Provenance>) -> LocationSource {
+    match provenance {
+        Some(location::Provenance::Reported) => LocationSource::Reported,
+        Some(location::Provenance::Inferred) => LocationSource::Inferred,
+        None => LocationSource::Unknown,
+    }
+}
+
+/// Indices of the grid cell containing `coordinates`.
+fn grid_cell(coordinates: location::Coordinates) -> (i32, i32) {
+    (
+        (coordinates.latitude / GRID_CELL_DEGREES).floor() as i32,
+        (coordinates.longitude / GRID_CELL_DEGREES).floor() as i32,
+    )
+}
+
+fn cell_label((lat, lon): (i32, i32)) -> String {
+    format!(
+        "{:.2}_{:.2}",
+        lat as f64 * GRID_CELL_DEGREES,
+        lon as f64 * GRID_CELL_DEGREES
+    )
+}
+
+impl From<location::Borough> for Borough {
+    fn from(b: location::Borough) -> Self {
+        use location::Borough as B;
+        match b {
+            B::Bronx => Borough::Bronx,
+            B::Brooklyn => Borough::Brooklyn,
+            B::Manhattan => Borough::Manhattan,
+            B::Queens => Borough::Queens,
+            B::StatenIsland => Borough::StatenIsland,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base_database::location::{Coordinates, Location};
+
+    fn crash(location: Location) -> Crash {
+        Crash {
+            location,
+            ..Crash::sample()
+        }
+    }
+
+    #[test]
+    fn locations_are_keyed_by_borough_zip_and_cell() {
+        let brooklyn = Location {
+            borough: Some(location::Borough::Brooklyn),
+            borough_source: Some(location::Provenance::Reported),
+            zip_code: Some("11230".into()),
+            coordinates: Coordinates::in_nyc(40.62179, -73.970024),
+            ..Location::default()
+        };
+        let crashes = [
+            crash(Location::default()),
+            crash(brooklyn.clone()),
+            crash(brooklyn),
+        ];
+        let dim = LocationDim::gen_locations(&crashes);
+
+        assert_eq!(dim.len(), 2);
+        assert_eq!(dim[0].location_hier_def_borough, Borough::Unknown);
+        assert_eq!(dim[1].location_borough_source, LocationSource::Reported);
+        assert_eq!(dim[1].location_zip_code_source, LocationSource::Unknown);
+        assert_eq!(dim[1].location_hier_def_grid_cell, "40.62_-73.98");
+        assert_eq!(dim[1].key(), location_key(&crashes[1]));
+        let centre = dim[1].grid_cell_latitude.unwrap();
+        assert!((centre - 40.625).abs() < 1e-9);
+    }
+}
@@ -1,8 +1,17 @@
+pub mod bodily_injury;
 pub mod contributing_factor;
 pub mod fact;
+pub mod fact_crash;
+pub mod factor_group;
+pub mod location;
+pub mod pedestrian_action;
 pub mod person_age;
+pub mod person_injury;
 pub mod person_position;
 pub mod person_role;
 pub mod person_sex;
 pub mod person_type;
+pub mod safety_equipment;
+pub mod severity;
 pub mod time;
+pub mod vehicle_type;
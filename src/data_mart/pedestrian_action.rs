@@ -0,0 +1,124 @@
+use crate::base_database::person;
+use itertools::iproduct;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumCount as EnumCountMacro;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumCountMacro)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PedestrianLocation {
+    AtIntersection,
+    NotAtIntersection,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumCountMacro)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PedestrianAction {
+    CrossingWithSignal,
+    CrossingAgainstSignal,
+    CrossingNoSignalMarkedCrosswalk,
+    CrossingNoSignalOrCrosswalk,
+    AlongHighwayWithTraffic,
+    AlongHighwayAgainstTraffic,
+    EmergingFromParkedVehicle,
+    GoingToFromSchoolBus,
+    GettingOnOffVehicle,
+    PushingWorkingOnCar,
+    WorkingInRoadway,
+    PlayingInRoadway,
+    OtherActionsInRoadway,
+    NotInRoadway,
+    Unknown,
+}
+
+/// Junk dimension of where a pedestrian, cyclist or other non-occupant was
+/// and what they were doing. Occupants are row 0, all Unknown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PedestrianActionDim {
+    pub pedestrian_action_id: u32,
+    pub pedestrian_location: PedestrianLocation,
+    pub pedestrian_action: PedestrianAction,
+}
+
+impl PedestrianActionDim {
+    pub fn gen_pedestrian_actions() -> Vec<PedestrianActionDim> {
+        use PedestrianAction as A;
+        use PedestrianLocation as L;
+        let locations = [L::Unknown, L::AtIntersection, L::NotAtIntersection];
+        let actions = [
+            A::Unknown,
+            A::CrossingWithSignal,
+            A::CrossingAgainstSignal,
+            A::CrossingNoSignalMarkedCrosswalk,
+            A::CrossingNoSignalOrCrosswalk,
+            A::AlongHighwayWithTraffic,
+            A::AlongHighwayAgainstTraffic,
+            A::EmergingFromParkedVehicle,
+            A::GoingToFromSchoolBus,
+            A::GettingOnOffVehicle,
+            A::PushingWorkingOnCar,
+            A::WorkingInRoadway,
+            A::PlayingInRoadway,
+            A::OtherActionsInRoadway,
+            A::NotInRoadway,
+        ];
+        iproduct!(locations, actions)
+            .enumerate()
+            .map(|(i, (location, action))| PedestrianActionDim {
+                pedestrian_action_id: i as u32,
+                pedestrian_location: location,
+                pedestrian_action: action,
+            })
+            .collect()
+    }
+}
+
+impl From<person::PedLocation> for PedestrianLocation {
+    fn from(l: person::PedLocation) -> Self {
+        use person::PedLocation as B;
+        match l {
+            B::AtIntersection => PedestrianLocation::AtIntersection,
+            B::NotAtIntersection => PedestrianLocation::NotAtIntersection,
+        }
+    }
+}
+
+impl From<person::PedAction> for PedestrianAction {
+    fn from(a: person::PedAction) -> Self {
+        use person::PedAction as B;
+        match a {
+            B::CrossingWithSignal => PedestrianAction::CrossingWithSignal,
+            B::CrossingAgainstSignal => PedestrianAction::CrossingAgainstSignal,
+            B::CrossingNoSignalMarkedCrosswalk => PedestrianAction::CrossingNoSignalMarkedCrosswalk,
+            B::CrossingNoSignalOrCrosswalk => PedestrianAction::CrossingNoSignalOrCrosswalk,
+            B::AlongHighwayWithTraffic => PedestrianAction::AlongHighwayWithTraffic,
+            B::AlongHighwayAgainstTraffic => PedestrianAction::AlongHighwayAgainstTraffic,
+            B::EmergingFromParkedVehicle => PedestrianAction::EmergingFromParkedVehicle,
+            B::GoingToFromSchoolBus => PedestrianAction::GoingToFromSchoolBus,
+            B::GettingOnOffVehicle => PedestrianAction::GettingOnOffVehicle,
+            B::PushingWorkingOnCar => PedestrianAction::PushingWorkingOnCar,
+            B::WorkingInRoadway => PedestrianAction::WorkingInRoadway,
+            B::PlayingInRoadway => PedestrianAction::PlayingInRoadway,
+            B::OtherActionsInRoadway => PedestrianAction::OtherActionsInRoadway,
+            B::NotInRoadway => PedestrianAction::NotInRoadway,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::EnumCount;
+
+    #[test]
+    fn gen_pedestrian_actions_covers_every_combination() {
+        let dim = PedestrianActionDim::gen_pedestrian_actions();
+        assert_eq!(
+            dim.len(),
+            PedestrianLocation::COUNT * PedestrianAction::COUNT
+        );
+        assert_eq!(dim[0].pedestrian_location, PedestrianLocation::Unknown);
+        assert_eq!(dim[0].pedestrian_action, PedestrianAction::Unknown);
+    }
+}
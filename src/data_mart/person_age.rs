@@ -1,4 +1,6 @@
+use crate::config::PersonAgeConfig;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -8,18 +10,28 @@ pub enum PersonAgeGroup {
     Unknown,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct PersonAge {
     pub person_age_id: u32,
     pub person_age: u8,
     pub person_age_known: bool,
     pub person_age_hier_def_group: PersonAgeGroup,
+    /// Band of the age in each configured hierarchy, keyed by the column of
+    /// the hierarchy (see [`AgeHierarchyConfig::column`]); `UNKNOWN` for the
+    /// unknown age.
+    ///
+    /// [`AgeHierarchyConfig::column`]: crate::config::AgeHierarchyConfig::column
+    #[serde(flatten)]
+    pub hier_bands: BTreeMap<String, String>,
 }
 
 impl PersonAge {
-    pub fn gen_ages() -> Vec<PersonAge> {
-        let mut ages = (1..=121)
+    /// Value of the unknown member in every configured hierarchy.
+    pub const UNKNOWN_BAND: &str = "UNKNOWN";
+
+    pub fn gen_ages(config: &PersonAgeConfig) -> Vec<PersonAge> {
+        let mut ages = (1..=config.max_age)
             .map(|age| PersonAge {
                 person_age_id: age as u32,
                 person_age: age,
@@ -29,6 +41,16 @@ impl PersonAge {
                 } else {
                     PersonAgeGroup::Infertile
                 },
+                hier_bands: config
+                    .hierarchies
+                    .iter()
+                    .map(|h| {
+                        let band = h
+                            .band(age)
+                            .expect("validated hierarchies have a band for every age");
+                        (h.column(), band.to_owned())
+                    })
+                    .collect(),
             })
             .collect::<Vec<_>>();
         ages.push(PersonAge {
@@ -36,7 +58,44 @@ impl PersonAge {
             person_age: 0,
             person_age_known: false,
             person_age_hier_def_group: PersonAgeGroup::Unknown,
+            hier_bands: config
+                .hierarchies
+                .iter()
+                .map(|h| (h.column(), Self::UNKNOWN_BAND.to_owned()))
+                .collect(),
         });
         ages
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_member_has_a_band_per_hierarchy() {
+        let config = PersonAgeConfig::default();
+        let ages = PersonAge::gen_ages(&config);
+        assert_eq!(ages.len(), usize::from(config.max_age) + 1);
+        assert!(
+            ages.iter()
+                .all(|a| a.hier_bands.len() == config.hierarchies.len())
+        );
+
+        let seventeen = &ages[16];
+        assert_eq!(seventeen.person_age, 17);
+        assert_eq!(seventeen.hier_bands["person_age_hier_life_stage"], "TEEN");
+        assert_eq!(
+            seventeen.hier_bands["person_age_hier_driving_age"],
+            "LEARNER"
+        );
+        assert_eq!(seventeen.person_age_hier_def_group, PersonAgeGroup::Fertile);
+
+        let unknown = ages.last().unwrap();
+        assert_eq!(unknown.person_age_id, 0);
+        assert_eq!(
+            unknown.hier_bands["person_age_hier_five_year"],
+            PersonAge::UNKNOWN_BAND
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use crate::base_database::person;
+use itertools::iproduct;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumCount as EnumCountMacro;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumCountMacro)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InjurySeverity {
+    Killed,
+    Injured,
+    Unspecified,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumCountMacro)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Ejection {
+    NotEjected,
+    PartiallyEjected,
+    Ejected,
+    Trapped,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumCountMacro)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EmotionalStatus {
+    Conscious,
+    Shock,
+    Incoherent,
+    SemiConscious,
+    Unconscious,
+    ApparentDeath,
+    Unknown,
+}
+
+/// Junk dimension of the person's own injury outcome: every combination of
+/// severity, ejection and emotional status. Row 0 is all Unknown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PersonInjuryDim {
+    pub person_injury_id: u32,
+    pub person_injury_severity: InjurySeverity,
+    pub person_injury_ejection: Ejection,
+    pub person_injury_emotional_status: EmotionalStatus,
+}
+
+impl PersonInjuryDim {
+    pub fn gen_injuries() -> Vec<PersonInjuryDim> {
+        use EmotionalStatus as S;
+        use InjurySeverity as I;
+        let severities = [I::Unknown, I::Killed, I::Injured, I::Unspecified];
+        let ejections = [
+            Ejection::Unknown,
+            Ejection::NotEjected,
+            Ejection::PartiallyEjected,
+            Ejection::Ejected,
+            Ejection::Trapped,
+        ];
+        let statuses = [
+            S::Unknown,
+            S::Conscious,
+            S::Shock,
+            S::Incoherent,
+            S::SemiConscious,
+            S::Unconscious,
+            S::ApparentDeath,
+        ];
+        iproduct!(severities, ejections, statuses)
+            .enumerate()
+            .map(|(i, (severity, ejection, status))| PersonInjuryDim {
+                person_injury_id: i as u32,
+                person_injury_severity: severity,
+                person_injury_ejection: ejection,
+                person_injury_emotional_status: status,
+            })
+            .collect()
+    }
+
+    pub(crate) fn key(&self) -> (InjurySeverity, Ejection, EmotionalStatus) {
+        (
+            self.person_injury_severity,
+            self.person_injury_ejection,
+            self.person_injury_emotional_status,
+        )
+    }
+}
+
+impl From<person::PersonInjury> for InjurySeverity {
+    fn from(i: person::PersonInjury) -> Self {
+        use person::PersonInjury as B;
+        match i {
+            B::Killed => InjurySeverity::Killed,
+            B::Injured => InjurySeverity::Injured,
+            B::Unspecified => InjurySeverity::Unspecified,
+        }
+    }
+}
+
+impl From<person::Ejection> for Ejection {
+    fn from(e: person::Ejection) -> Self {
+        use person::Ejection as B;
+        match e {
+            B::NotEjected => Ejection::NotEjected,
+            B::PartiallyEjected => Ejection::PartiallyEjected,
+            B::Ejected => Ejection::Ejected,
+            B::Trapped => Ejection::Trapped,
+        }
+    }
+}
+
+impl From<person::EmotionalStatus> for EmotionalStatus {
+    fn from(s: person::EmotionalStatus) -> Self {
+        use person::EmotionalStatus as B;
+        match s {
+            B::Conscious => EmotionalStatus::Conscious,
+            B::Shock => EmotionalStatus::Shock,
+            B::Incoherent => EmotionalStatus::Incoherent,
+            B::SemiConscious => EmotionalStatus::SemiConscious,
+            B::Unconscious => EmotionalStatus::Unconscious,
+            B::ApparentDeath => EmotionalStatus::ApparentDeath,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use strum::EnumCount;
+
+    #[test]
+    fn gen_injuries_covers_every_combination_once() {
+        let dim = PersonInjuryDim::gen_injuries();
+        assert_eq!(
+            dim.len(),
+            InjurySeverity::COUNT * Ejection::COUNT * EmotionalStatus::COUNT
+        );
+        let keys: HashSet<_> = dim.iter().map(PersonInjuryDim::key).collect();
+        assert_eq!(keys.len(), dim.len());
+        assert_eq!(
+            dim[0].key(),
+            (
+                InjurySeverity::Unknown,
+                Ejection::Unknown,
+                EmotionalStatus::Unknown
+            )
+        );
+    }
+}
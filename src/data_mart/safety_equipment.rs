@@ -0,0 +1,119 @@
+use crate::base_database::person;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumCount as EnumCountMacro;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumCountMacro)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SafetyEquipment {
+    NoEquipment,
+    LapBelt,
+    Harness,
+    LapBeltHarness,
+    ChildRestraint,
+    AirBag,
+    AirBagLapBelt,
+    AirBagLapBeltHarness,
+    AirBagChildRestraint,
+    HelmetMotorcycle,
+    HelmetBicycle,
+    HelmetOtherBicycle,
+    PadsBicycle,
+    StoppersBicycle,
+    Other,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SafetyEquipmentCategory {
+    /// Belts and child seats without an air bag.
+    Restraint,
+    AirBag,
+    Helmet,
+    OtherProtection,
+    NoEquipment,
+    Unknown,
+}
+
+/// Safety equipment used by the person.
+///
+/// Hierarchy:
+///   Level 1 – safety_equipment
+///   Level 2 – safety_equipment_hier_def_category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SafetyEquipmentDim {
+    pub safety_equipment_id: u32,
+    pub safety_equipment: SafetyEquipment,
+    pub safety_equipment_hier_def_category: SafetyEquipmentCategory,
+}
+
+impl SafetyEquipmentDim {
+    pub fn gen_safety_equipment() -> Vec<SafetyEquipmentDim> {
+        use SafetyEquipment as E;
+        use SafetyEquipmentCategory as C;
+        [
+            (E::Unknown, C::Unknown),
+            (E::NoEquipment, C::NoEquipment),
+            (E::LapBelt, C::Restraint),
+            (E::Harness, C::Restraint),
+            (E::LapBeltHarness, C::Restraint),
+            (E::ChildRestraint, C::Restraint),
+            (E::AirBag, C::AirBag),
+            (E::AirBagLapBelt, C::AirBag),
+            (E::AirBagLapBeltHarness, C::AirBag),
+            (E::AirBagChildRestraint, C::AirBag),
+            (E::HelmetMotorcycle, C::Helmet),
+            (E::HelmetBicycle, C::Helmet),
+            (E::HelmetOtherBicycle, C::Helmet),
+            (E::PadsBicycle, C::OtherProtection),
+            (E::StoppersBicycle, C::OtherProtection),
+            (E::Other, C::OtherProtection),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (safety_equipment, category))| SafetyEquipmentDim {
+            safety_equipment_id: i as u32,
+            safety_equipment,
+            safety_equipment_hier_def_category: category,
+        })
+        .collect()
+    }
+}
+
+impl From<person::SafetyEquipment> for SafetyEquipment {
+    fn from(e: person::SafetyEquipment) -> Self {
+        use person::SafetyEquipment as B;
+        match e {
+            B::NoEquipment => SafetyEquipment::NoEquipment,
+            B::LapBelt => SafetyEquipment::LapBelt,
+            B::Harness => SafetyEquipment::Harness,
+            B::LapBeltHarness => SafetyEquipment::LapBeltHarness,
+            B::ChildRestraint => SafetyEquipment::ChildRestraint,
+            B::AirBag => SafetyEquipment::AirBag,
+            B::AirBagLapBelt => SafetyEquipment::AirBagLapBelt,
+            B::AirBagLapBeltHarness => SafetyEquipment::AirBagLapBeltHarness,
+            B::AirBagChildRestraint => SafetyEquipment::AirBagChildRestraint,
+            B::HelmetMotorcycle => SafetyEquipment::HelmetMotorcycle,
+            B::HelmetBicycle => SafetyEquipment::HelmetBicycle,
+            B::HelmetOtherBicycle => SafetyEquipment::HelmetOtherBicycle,
+            B::PadsBicycle => SafetyEquipment::PadsBicycle,
+            B::StoppersBicycle => SafetyEquipment::StoppersBicycle,
+            B::Other => SafetyEquipment::Other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::EnumCount;
+
+    #[test]
+    fn gen_safety_equipment_length_matches_enum_count() {
+        assert_eq!(
+            SafetyEquipmentDim::gen_safety_equipment().len(),
+            SafetyEquipment::COUNT
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::base_database::crash::Crash;
+use crate::config::SeverityConfig;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumCount as EnumCountMacro;
+
+/// Outcome of the crash on the KABCO scale, with the injury classes A, B and
+/// C merged because the source does not grade injuries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumCountMacro)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CrashSeverity {
+    /// K: at least one person killed.
+    Fatal,
+    /// A/B/C: at least one person injured, nobody killed.
+    Injury,
+    /// O: nobody injured or killed.
+    PropertyDamageOnly,
+    Unknown,
+}
+
+impl CrashSeverity {
+    /// Classifies `crash` by its worst outcome, see [`casualties`].
+    pub fn of(crash: &Crash) -> Self {
+        let (injured, killed) = casualties(crash);
+        if killed > 0 {
+            CrashSeverity::Fatal
+        } else if injured > 0 {
+            CrashSeverity::Injury
+        } else {
+            CrashSeverity::PropertyDamageOnly
+        }
+    }
+}
+
+/// Injured and killed persons of `crash`. The person totals are sometimes
+/// left at zero, so the sum of the per-road-user counts is taken when it is
+/// larger.
+pub(crate) fn casualties(crash: &Crash) -> (u16, u16) {
+    let count = |persons: u16, road_users: [u16; 3]| {
+        persons.max(road_users.into_iter().fold(0, u16::saturating_add))
+    };
+    let injured = count(
+        crash.crash_persons_injured,
+        [
+            crash.crash_pedestrians_injured,
+            crash.crash_cyclist_injured,
+            crash.crash_motorist_injured,
+        ],
+    );
+    let killed = count(
+        crash.crash_persons_killed,
+        [
+            crash.crash_pedestrians_killed,
+            crash.crash_cyclist_killed,
+            crash.crash_motorist_killed,
+        ],
+    );
+    (injured, killed)
+}
+
+/// Severity class of the crash.
+///
+/// `severity_rank` orders the classes from least (1, property damage only) to
+/// most severe (3, fatal); Unknown is 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SeverityDim {
+    pub severity_id: u32,
+    pub severity: CrashSeverity,
+    pub severity_rank: u8,
+}
+
+impl SeverityDim {
+    pub fn gen_severities() -> Vec<SeverityDim> {
+        use CrashSeverity as S;
+        [
+            (S::Unknown, 0),
+            (S::PropertyDamageOnly, 1),
+            (S::Injury, 2),
+            (S::Fatal, 3),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (severity, severity_rank))| SeverityDim {
+            severity_id: i as u32,
+            severity,
+            severity_rank,
+        })
+        .collect()
+    }
+}
+
+/// Weighted severity index of the crash: its injured and killed persons, as
+/// counted for its [`CrashSeverity`], weighted by `weights`.
+///
+/// Saturates at [`SeverityConfig::MAX_INDEX`], which validated weights never
+/// reach.
+pub fn severity_index(crash: &Crash, weights: &SeverityConfig) -> u32 {
+    let (injured, killed) = casualties(crash);
+    u32::from(weights.injured_weight)
+        .saturating_mul(u32::from(injured))
+        .saturating_add(u32::from(weights.killed_weight).saturating_mul(u32::from(killed)))
+        .min(SeverityConfig::MAX_INDEX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::EnumCount;
+
+    #[test]
+    fn gen_severities_is_ranked() {
+        let dim = SeverityDim::gen_severities();
+        assert_eq!(dim.len(), CrashSeverity::COUNT);
+        assert_eq!(dim[0].severity, CrashSeverity::Unknown);
+        assert!(
+            dim.windows(2)
+                .all(|w| w[0].severity_rank < w[1].severity_rank)
+        );
+    }
+
+    #[test]
+    fn severity_follows_the_worst_outcome() {
+        let crash = Crash {
+            crash_persons_injured: 3,
+            crash_persons_killed: 1,
+            crash_motorist_injured: 3,
+            crash_motorist_killed: 1,
+            ..Crash::sample()
+        };
+        assert_eq!(CrashSeverity::of(&crash), CrashSeverity::Fatal);
+        assert_eq!(severity_index(&crash, &SeverityConfig::default()), 13);
+
+        let injury = Crash {
+            crash_persons_killed: 0,
+            crash_motorist_killed: 0,
+            ..crash
+        };
+        assert_eq!(CrashSeverity::of(&injury), CrashSeverity::Injury);
+
+        let pdo = Crash {
+            crash_persons_injured: 0,
+            crash_motorist_injured: 0,
+            ..injury
+        };
+        assert_eq!(CrashSeverity::of(&pdo), CrashSeverity::PropertyDamageOnly);
+
+        // the person totals were left at zero
+        let pedestrian_killed = Crash {
+            crash_pedestrians_killed: 1,
+            crash_cyclist_injured: 2,
+            ..pdo
+        };
+        assert_eq!(CrashSeverity::of(&pedestrian_killed), CrashSeverity::Fatal);
+        assert_eq!(
+            severity_index(&pedestrian_killed, &SeverityConfig::default()),
+            12
+        );
+    }
+
+    #[test]
+    fn severity_index_fits_an_sql_int() {
+        let crash = Crash {
+            crash_persons_injured: u16::MAX,
+            crash_persons_killed: u16::MAX,
+            crash_pedestrians_killed: u16::MAX,
+            crash_cyclist_killed: u16::MAX,
+            ..Crash::sample()
+        };
+        let largest = SeverityConfig {
+            injured_weight: 1,
+            killed_weight: SeverityConfig::MAX_WEIGHT_SUM - 1,
+        };
+        assert_eq!(
+            severity_index(&crash, &largest),
+            u32::from(SeverityConfig::MAX_WEIGHT_SUM) * u32::from(u16::MAX)
+        );
+        assert!(severity_index(&crash, &largest) <= SeverityConfig::MAX_INDEX);
+
+        let unvalidated = SeverityConfig {
+            injured_weight: u16::MAX,
+            killed_weight: u16::MAX,
+        };
+        assert_eq!(
+            severity_index(&crash, &unvalidated),
+            SeverityConfig::MAX_INDEX
+        );
+    }
+}
@@ -26,33 +26,89 @@
 --                 (moon_phase × weather × factor_category × person_sex ×
 --                  age_group) collapses the multi-billion-row star join into
 --                 ~1 000 rows the reporting layer reads in probably microseconds.
+--                 A second one over FactCrash holds the crash totals at
+--                 (moon_phase × weather × factor_category).
 -- =============================================================================
 
 
 -- =============================================================================
 -- Dimension: Time
 -- Parallel hierarchies:
---   Default  : timestamp → day → month → year
+--   Default  : timestamp → day → month → quarter → year
 --   Moon     : timestamp → moon_phase
--- Denormalized weather attribute stored directly on the dimension row.
+-- Calendar attributes (hour, weekday, ISO week, season, holiday) are derived
+-- from the New York local timestamp. Holidays are the US federal holidays plus
+-- the New York State ones (Lincoln's Birthday, Election Day), on the day they
+-- are observed as well as on the day itself. Rush hour is 07–10 and 16–19 on
+-- days that are neither weekend nor holiday.
+-- Light condition comes from the Sun's altitude at City Hall, computed for
+-- the start and end of the hour: DAYLIGHT if it stays above -0.833° (sunrise)
+-- all hour, NIGHT if it stays below -6° (end of civil twilight), otherwise
+-- CIVIL_TWILIGHT. sun_altitude is taken at half past the hour.
+-- Denormalized weather attribute stored directly on the dimension row, with
+-- the hourly measures it is derived from so analysts can bucket them
+-- themselves; the measures are NULL for hours without an observation.
+-- weather_rule_id names the configured weather rule that classified the hour.
+-- snow_risk: SNOW when precipitation exceeds rain by 0.05 mm or more (the rest
+-- fell as snow), else FREEZING_RAIN for rain at or below 0 °C, else FROST at
+-- or below 0 °C, else NO_RISK.
 -- =============================================================================
 CREATE TABLE project_julian_bruder_kenana_saeed.DimTime (
     time_id                 INT               NOT NULL,
-    [timestamp]             datetime          NOT NULL,
+    [timestamp]             datetime          NOT NULL,   -- New York local time
+    utc_offset_minutes      SMALLINT          NOT NULL,   -- -300 (EST) or -240 (EDT)
+    dst_flag                VARCHAR(15)       NOT NULL,
 
     -- Default hierarchy
     hier_def_day            DATE              NOT NULL,
     hier_def_month          VARCHAR(12)       NOT NULL,   -- e.g. 'January'
+    hier_def_month_number   TINYINT           NOT NULL,   -- 1–12, sort key of hier_def_month
+    hier_def_quarter        TINYINT           NOT NULL,   -- 1–4
     hier_def_year           SMALLINT          NOT NULL,
 
+    -- Calendar attributes
+    hour_of_day             TINYINT           NOT NULL,   -- 0–23, local
+    day_of_week             VARCHAR(9)        NOT NULL,
+    day_of_week_number      TINYINT           NOT NULL,   -- ISO 8601: 1 = Monday
+    iso_week                TINYINT           NOT NULL,   -- ISO 8601 week, 1–53
+    iso_week_year           SMALLINT          NOT NULL,   -- year the ISO week belongs to
+    is_weekend              BIT               NOT NULL,
+    is_rush_hour            BIT               NOT NULL,
+    season                  VARCHAR(6)        NOT NULL,   -- meteorological
+    holiday                 VARCHAR(25)       NOT NULL,
+    is_holiday              BIT               NOT NULL,
+
     -- Moon-phase hierarchy
     hier_moon_phase         VARCHAR(20)       NOT NULL,
+    moon_illumination       DECIMAL(4,3)      NOT NULL,   -- illuminated fraction 0–1
+    moon_age_days           DECIMAL(4,2)      NOT NULL,   -- days since new moon
+
+    -- Light
+    light_condition         VARCHAR(14)       NOT NULL,
+    sun_altitude            DECIMAL(5,2)      NOT NULL,   -- degrees, half past the hour
 
     -- Denormalized weather attribute
     weather                 VARCHAR(20)       NOT NULL,
+    weather_rule_id         VARCHAR(40)       NULL,       -- rule that classified weather
+    snow_risk               VARCHAR(13)       NOT NULL,
+
+    -- Weather measures
+    temperature_c           DECIMAL(5,2)      NULL,       -- at 2 m
+    precipitation_mm        DECIMAL(5,2)      NULL,       -- rain, showers and snow
+    rain_mm                 DECIMAL(5,2)      NULL,       -- rain and showers
+    cloudcover_pct          DECIMAL(5,2)      NULL,
+    windspeed_kmh           DECIMAL(5,2)      NULL,       -- at 10 m
 
     CONSTRAINT PK_DimTime PRIMARY KEY CLUSTERED (time_id),
 
+    -- AMBIGUOUS: repeated fall-back hour, resolved to its first (EDT) occurrence
+    -- NON_EXISTENT: skipped spring-forward hour, resolved with the EST offset
+    CONSTRAINT CK_DimTime_DstFlag CHECK (dst_flag IN (
+        'NORMAL',
+        'AMBIGUOUS',
+        'NON_EXISTENT'
+    )),
+
     CONSTRAINT CK_DimTime_MoonPhase CHECK (hier_moon_phase IN (
         'NEW',
         'WAXING_CRESCENT',
@@ -65,6 +121,12 @@ CREATE TABLE project_julian_bruder_kenana_saeed.DimTime (
         'UNKNOWN'
     )),
 
+    CONSTRAINT CK_DimTime_LightCondition CHECK (light_condition IN (
+        'DAYLIGHT',
+        'CIVIL_TWILIGHT',
+        'NIGHT'
+    )),
+
     CONSTRAINT CK_DimTime_Weather CHECK (weather IN (
         'CLEAR',
         'CLOUDY',
@@ -76,9 +138,42 @@ CREATE TABLE project_julian_bruder_kenana_saeed.DimTime (
         'UNKNOWN'
     )),
 
+    CONSTRAINT CK_DimTime_SnowRisk CHECK (snow_risk IN (
+        'NO_RISK',
+        'FROST',
+        'FREEZING_RAIN',
+        'SNOW',
+        'UNKNOWN'
+    )),
+
     CONSTRAINT CK_DimTime_Month CHECK (hier_def_month IN (
         'January', 'February', 'March', 'April', 'May', 'June',
         'July', 'August', 'September', 'October', 'November', 'December'
+    )),
+
+    CONSTRAINT CK_DimTime_DayOfWeek CHECK (day_of_week IN (
+        'MONDAY', 'TUESDAY', 'WEDNESDAY', 'THURSDAY', 'FRIDAY', 'SATURDAY', 'SUNDAY'
+    )),
+
+    CONSTRAINT CK_DimTime_Season CHECK (season IN (
+        'WINTER', 'SPRING', 'SUMMER', 'AUTUMN'
+    )),
+
+    CONSTRAINT CK_DimTime_Holiday CHECK (holiday IN (
+        'NEW_YEARS_DAY',
+        'MARTIN_LUTHER_KING_JR_DAY',
+        'LINCOLNS_BIRTHDAY',
+        'WASHINGTONS_BIRTHDAY',
+        'MEMORIAL_DAY',
+        'JUNETEENTH',
+        'INDEPENDENCE_DAY',
+        'LABOR_DAY',
+        'COLUMBUS_DAY',
+        'ELECTION_DAY',
+        'VETERANS_DAY',
+        'THANKSGIVING',
+        'CHRISTMAS_DAY',
+        'NO_HOLIDAY'
     ))
 );
 
@@ -107,13 +202,19 @@ CREATE INDEX IX_DimTime_MoonPhase_Weather
 -- =============================================================================
 -- Dimension: Person Age
 -- Hierarchy: age → age_group (Fertile / Infertile / Unknown)
--- Row 0 is the "age unknown" sentinel.
+-- Further hierarchies come from "person_ages" in config.json, one
+-- person_age_hier_<name> column each; shown are the defaults. Their bands
+-- are configured too, so they carry no CHECK constraint.
+-- Row 0 is the "age unknown" sentinel; it is UNKNOWN in every hierarchy.
 -- =============================================================================
 CREATE TABLE project_julian_bruder_kenana_saeed.DimPersonAge (
     person_age_id               INT         NOT NULL,
     person_age                  TINYINT     NOT NULL,   -- 0 means "not known"
     person_age_known            BIT         NOT NULL,   -- person_age != 0
     person_age_hier_def_group   VARCHAR(12) NOT NULL,
+    person_age_hier_five_year   VARCHAR(20) NOT NULL,   -- '00-04' … '80-84', '85+'
+    person_age_hier_life_stage  VARCHAR(20) NOT NULL,   -- CHILD, TEEN, ADULT, SENIOR
+    person_age_hier_driving_age VARCHAR(20) NOT NULL,   -- UNDER_16, LEARNER, YOUNG_DRIVER, DRIVER, OLDER_DRIVER
 
     CONSTRAINT PK_DimPersonAge PRIMARY KEY CLUSTERED (person_age_id),
 
@@ -205,6 +306,294 @@ CREATE TABLE project_julian_bruder_kenana_saeed.DimPersonType (
 );
 
 
+-- =============================================================================
+-- Dimension: Vehicle Type
+-- Hierarchy: vehicle type → category
+--
+--   Level 1 – vehicle_type                    (leaf)
+--   Level 2 – vehicle_type_hier_def_category  (root)
+-- Persons without a vehicle (pedestrians) reference UNKNOWN.
+-- =============================================================================
+CREATE TABLE project_julian_bruder_kenana_saeed.DimVehicleType (
+    vehicle_type_id                 INT         NOT NULL,
+    vehicle_type                    VARCHAR(20) NOT NULL,
+    vehicle_type_hier_def_category  VARCHAR(15) NOT NULL,
+
+    CONSTRAINT PK_DimVehicleType PRIMARY KEY CLUSTERED (vehicle_type_id),
+
+    CONSTRAINT CK_DimVehicleType_Type CHECK (vehicle_type IN (
+        'SEDAN',
+        'STATION_WAGON_SUV',
+        'CONVERTIBLE',
+        'TAXI',
+        'VAN',
+        'PICK_UP_TRUCK',
+        'BOX_TRUCK',
+        'TRACTOR_TRUCK',
+        'DUMP_TRUCK',
+        'GARBAGE_TRUCK',
+        'TOW_TRUCK',
+        'TANKER',
+        'FLAT_BED',
+        'OTHER_TRUCK',
+        'BUS',
+        'BICYCLE',
+        'E_BIKE',
+        'E_SCOOTER',
+        'MOPED',
+        'MOTORCYCLE',
+        'AMBULANCE',
+        'FIRE_TRUCK',
+        'PEDICAB',
+        'UNKNOWN'
+    )),
+
+    CONSTRAINT CK_DimVehicleType_Category CHECK (vehicle_type_hier_def_category IN (
+        'PASSENGER_CAR',
+        'TRUCK',
+        'TWO_WHEELER',
+        'BUS',
+        'OTHER',
+        'UNKNOWN'
+    ))
+);
+
+
+-- =============================================================================
+-- Dimension: Person Injury (junk)
+-- Every combination of the person's own injury severity, ejection and
+-- emotional status. Row 0 is all 'UNKNOWN'.
+-- =============================================================================
+CREATE TABLE project_julian_bruder_kenana_saeed.DimPersonInjury (
+    person_injury_id                INT         NOT NULL,
+    person_injury_severity          VARCHAR(15) NOT NULL,
+    person_injury_ejection          VARCHAR(20) NOT NULL,
+    person_injury_emotional_status  VARCHAR(15) NOT NULL,
+
+    CONSTRAINT PK_DimPersonInjury PRIMARY KEY CLUSTERED (person_injury_id),
+
+    CONSTRAINT CK_DimPersonInjury_Severity CHECK (person_injury_severity IN (
+        'KILLED',
+        'INJURED',
+        'UNSPECIFIED',
+        'UNKNOWN'
+    )),
+
+    CONSTRAINT CK_DimPersonInjury_Ejection CHECK (person_injury_ejection IN (
+        'NOT_EJECTED',
+        'PARTIALLY_EJECTED',
+        'EJECTED',
+        'TRAPPED',
+        'UNKNOWN'
+    )),
+
+    CONSTRAINT CK_DimPersonInjury_EmotionalStatus CHECK (person_injury_emotional_status IN (
+        'CONSCIOUS',
+        'SHOCK',
+        'INCOHERENT',
+        'SEMI_CONSCIOUS',
+        'UNCONSCIOUS',
+        'APPARENT_DEATH',
+        'UNKNOWN'
+    ))
+);
+
+
+-- =============================================================================
+-- Dimension: Bodily Injury
+-- Hierarchy: body part → body region
+--
+--   Level 1 – bodily_injury                  (leaf)
+--   Level 2 – bodily_injury_hier_def_region  (root)
+-- Uninjured persons reference UNKNOWN.
+-- =============================================================================
+CREATE TABLE project_julian_bruder_kenana_saeed.DimBodilyInjury (
+    bodily_injury_id               INT         NOT NULL,
+    bodily_injury                  VARCHAR(25) NOT NULL,
+    bodily_injury_hier_def_region  VARCHAR(20) NOT NULL,
+
+    CONSTRAINT PK_DimBodilyInjury PRIMARY KEY CLUSTERED (bodily_injury_id),
+
+    CONSTRAINT CK_DimBodilyInjury_Part CHECK (bodily_injury IN (
+        'HEAD',
+        'FACE',
+        'EYE',
+        'NECK',
+        'CHEST',
+        'BACK',
+        'ABDOMEN_PELVIS',
+        'SHOULDER_UPPER_ARM',
+        'ELBOW_LOWER_ARM_HAND',
+        'HIP_UPPER_LEG',
+        'KNEE_LOWER_LEG_FOOT',
+        'ENTIRE_BODY',
+        'UNKNOWN'
+    )),
+
+    CONSTRAINT CK_DimBodilyInjury_Region CHECK (bodily_injury_hier_def_region IN (
+        'HEAD_NECK',
+        'TORSO',
+        'UPPER_EXTREMITY',
+        'LOWER_EXTREMITY',
+        'ENTIRE_BODY',
+        'UNKNOWN'
+    ))
+);
+
+
+-- =============================================================================
+-- Dimension: Safety Equipment
+-- Hierarchy: equipment → category
+--
+--   Level 1 – safety_equipment                    (leaf)
+--   Level 2 – safety_equipment_hier_def_category  (root)
+-- =============================================================================
+CREATE TABLE project_julian_bruder_kenana_saeed.DimSafetyEquipment (
+    safety_equipment_id                 INT         NOT NULL,
+    safety_equipment                    VARCHAR(25) NOT NULL,
+    safety_equipment_hier_def_category  VARCHAR(20) NOT NULL,
+
+    CONSTRAINT PK_DimSafetyEquipment PRIMARY KEY CLUSTERED (safety_equipment_id),
+
+    CONSTRAINT CK_DimSafetyEquipment_Equipment CHECK (safety_equipment IN (
+        'NO_EQUIPMENT',
+        'LAP_BELT',
+        'HARNESS',
+        'LAP_BELT_HARNESS',
+        'CHILD_RESTRAINT',
+        'AIR_BAG',
+        'AIR_BAG_LAP_BELT',
+        'AIR_BAG_LAP_BELT_HARNESS',
+        'AIR_BAG_CHILD_RESTRAINT',
+        'HELMET_MOTORCYCLE',
+        'HELMET_BICYCLE',
+        'HELMET_OTHER_BICYCLE',
+        'PADS_BICYCLE',
+        'STOPPERS_BICYCLE',
+        'OTHER',
+        'UNKNOWN'
+    )),
+
+    CONSTRAINT CK_DimSafetyEquipment_Category CHECK (safety_equipment_hier_def_category IN (
+        'RESTRAINT',
+        'AIR_BAG',
+        'HELMET',
+        'OTHER_PROTECTION',
+        'NO_EQUIPMENT',
+        'UNKNOWN'
+    ))
+);
+
+
+-- =============================================================================
+-- Dimension: Pedestrian Action (junk)
+-- Every combination of where a pedestrian / cyclist was and what they were
+-- doing. Occupants reference row 0, all 'UNKNOWN'.
+-- =============================================================================
+CREATE TABLE project_julian_bruder_kenana_saeed.DimPedestrianAction (
+    pedestrian_action_id  INT         NOT NULL,
+    pedestrian_location   VARCHAR(20) NOT NULL,
+    pedestrian_action     VARCHAR(40) NOT NULL,
+
+    CONSTRAINT PK_DimPedestrianAction PRIMARY KEY CLUSTERED (pedestrian_action_id),
+
+    CONSTRAINT CK_DimPedestrianAction_Location CHECK (pedestrian_location IN (
+        'AT_INTERSECTION',
+        'NOT_AT_INTERSECTION',
+        'UNKNOWN'
+    )),
+
+    CONSTRAINT CK_DimPedestrianAction_Action CHECK (pedestrian_action IN (
+        'CROSSING_WITH_SIGNAL',
+        'CROSSING_AGAINST_SIGNAL',
+        'CROSSING_NO_SIGNAL_MARKED_CROSSWALK',
+        'CROSSING_NO_SIGNAL_OR_CROSSWALK',
+        'ALONG_HIGHWAY_WITH_TRAFFIC',
+        'ALONG_HIGHWAY_AGAINST_TRAFFIC',
+        'EMERGING_FROM_PARKED_VEHICLE',
+        'GOING_TO_FROM_SCHOOL_BUS',
+        'GETTING_ON_OFF_VEHICLE',
+        'PUSHING_WORKING_ON_CAR',
+        'WORKING_IN_ROADWAY',
+        'PLAYING_IN_ROADWAY',
+        'OTHER_ACTIONS_IN_ROADWAY',
+        'NOT_IN_ROADWAY',
+        'UNKNOWN'
+    ))
+);
+
+
+-- =============================================================================
+-- Dimension: Severity
+-- KABCO-style crash severity: FATAL (K), INJURY (A/B/C, which the source does
+-- not distinguish) and PROPERTY_DAMAGE_ONLY (O), taken from the crash's
+-- injured / killed counts. severity_rank orders the classes for ranking:
+-- 0 UNKNOWN, 1 PROPERTY_DAMAGE_ONLY, 2 INJURY, 3 FATAL.
+-- =============================================================================
+CREATE TABLE project_julian_bruder_kenana_saeed.DimSeverity (
+    severity_id    INT         NOT NULL,
+    severity       VARCHAR(20) NOT NULL,
+    severity_rank  TINYINT     NOT NULL,
+
+    CONSTRAINT PK_DimSeverity PRIMARY KEY CLUSTERED (severity_id),
+
+    CONSTRAINT CK_DimSeverity_Severity CHECK (severity IN (
+        'FATAL',
+        'INJURY',
+        'PROPERTY_DAMAGE_ONLY',
+        'UNKNOWN'
+    ))
+);
+
+
+-- =============================================================================
+-- Dimension: Location
+-- Hierarchy: grid cell → ZIP code → borough
+--
+--   Level 1 – location_hier_def_grid_cell  (0.01° cell, south-west corner)
+--   Level 2 – location_hier_def_zip_code
+--   Level 3 – location_hier_def_borough
+-- One row per combination that occurs on a crash; missing parts are
+-- 'UNKNOWN'. Location 0 is the fully unknown location.
+-- =============================================================================
+CREATE TABLE project_julian_bruder_kenana_saeed.DimLocation (
+    location_id                  INT          NOT NULL,
+    location_hier_def_borough    VARCHAR(15)  NOT NULL,
+    location_borough_source      VARCHAR(10)  NOT NULL,   -- reported or inferred from coordinates
+    location_hier_def_zip_code   VARCHAR(7)   NOT NULL,
+    location_zip_code_source     VARCHAR(10)  NOT NULL,   -- reported or inferred from coordinates
+    location_hier_def_grid_cell  VARCHAR(15)  NOT NULL,   -- e.g. '40.62_-73.98'
+    grid_cell_latitude           DECIMAL(7,5) NULL,       -- cell centre
+    grid_cell_longitude          DECIMAL(8,5) NULL,
+
+    CONSTRAINT PK_DimLocation PRIMARY KEY CLUSTERED (location_id),
+
+    CONSTRAINT CK_DimLocation_Borough CHECK (location_hier_def_borough IN (
+        'BRONX',
+        'BROOKLYN',
+        'MANHATTAN',
+        'QUEENS',
+        'STATEN_ISLAND',
+        'UNKNOWN'
+    )),
+
+    CONSTRAINT CK_DimLocation_BoroughSource CHECK (location_borough_source IN (
+        'REPORTED',
+        'INFERRED',
+        'UNKNOWN'
+    )),
+
+    CONSTRAINT CK_DimLocation_ZipCodeSource CHECK (location_zip_code_source IN (
+        'REPORTED',
+        'INFERRED',
+        'UNKNOWN'
+    ))
+);
+
+CREATE INDEX IX_DimLocation_Borough_Zip
+    ON project_julian_bruder_kenana_saeed.DimLocation (location_hier_def_borough, location_hier_def_zip_code);
+
+
 -- =============================================================================
 -- Dimension: Contributing Factor
 -- Hierarchy: factor → sub-category → category
@@ -294,12 +683,56 @@ CREATE TABLE project_julian_bruder_kenana_saeed.DimContributingFactor (
 );
 
 
+-- =============================================================================
+-- Dimension: Factor Group  +  Bridge: Factor Group → Contributing Factor
+--
+-- A crash lists up to five contributing factors. Each distinct combination
+-- is one factor group; the fact row references the group of its crash and
+-- the bridge fans it out to the member factors.
+--   Full counting      : join through the bridge and ignore the weight —
+--                        every factor is credited with the whole crash.
+--   Allocated counting : multiply measures by weighting_factor (1 / n) —
+--                        totals across factors equal the fact totals.
+-- Group 0 is the Unknown group (no recognised factor), bridged to factor 0.
+-- =============================================================================
+CREATE TABLE project_julian_bruder_kenana_saeed.DimFactorGroup (
+    factor_group_id         INT          NOT NULL,
+    factor_count            TINYINT      NOT NULL,
+    factor_group_label      VARCHAR(320) NOT NULL,   -- e.g. 'UNSAFE_SPEED + ALCOHOL_INVOLVEMENT'
+
+    CONSTRAINT PK_DimFactorGroup PRIMARY KEY CLUSTERED (factor_group_id)
+);
+
+CREATE TABLE project_julian_bruder_kenana_saeed.BridgeFactorGroup (
+    factor_group_id         INT          NOT NULL,
+    contributing_factor_id  INT          NOT NULL,
+    weighting_factor        DECIMAL(5,4) NOT NULL,
+
+    CONSTRAINT PK_BridgeFactorGroup
+        PRIMARY KEY CLUSTERED (factor_group_id, contributing_factor_id),
+
+    CONSTRAINT FK_BridgeFactorGroup_FactorGroup
+        FOREIGN KEY (factor_group_id)
+        REFERENCES project_julian_bruder_kenana_saeed.DimFactorGroup (factor_group_id),
+
+    CONSTRAINT FK_BridgeFactorGroup_ContributingFactor
+        FOREIGN KEY (contributing_factor_id)
+        REFERENCES project_julian_bruder_kenana_saeed.DimContributingFactor (contributing_factor_id)
+);
+
+-- Queries filter by factor and then join to the groups containing it.
+CREATE INDEX IX_BridgeFactorGroup_ContributingFactor
+    ON project_julian_bruder_kenana_saeed.BridgeFactorGroup (contributing_factor_id)
+    INCLUDE (weighting_factor);
+
+
 -- =============================================================================
 -- Fact Table: Vehicle Incident Person
 --
 -- Grain: one row per person involved in a crash.
--- All measures are taken from the crash record the person belongs to,
--- so they express the total impact of the crash each person was part of.
+-- is_injured / is_killed are the person's own outcome; the count measures
+-- are taken from the crash record the person belongs to, so they express
+-- the total impact of the crash each person was part of.
 --
 -- WHY CLUSTERED COLUMNSTORE (CCI) INSTEAD OF A CLUSTERED B-TREE:
 --   1. Access pattern: every analytical query performs a full or near-full
@@ -327,23 +760,40 @@ CREATE TABLE project_julian_bruder_kenana_saeed.Fact (
     fact_id                 INT     NOT NULL,
 
     -- Dimension foreign keys
-    contributing_factor_id  INT     NOT NULL,
+    bodily_injury_id        INT     NOT NULL,
+    contributing_factor_id  INT     NOT NULL,   -- the person's / their vehicle's factor
+    factor_group_id         INT     NOT NULL,   -- all factors, via BridgeFactorGroup
+    location_id             INT     NOT NULL,
+    pedestrian_action_id    INT     NOT NULL,
     person_age_id           INT     NOT NULL,
+    person_injury_id        INT     NOT NULL,   -- severity × ejection × emotional status
     person_position_id      INT     NOT NULL,
     person_role_id          INT     NOT NULL,
     person_sex_id           INT     NOT NULL,
     person_type_id          INT     NOT NULL,
+    safety_equipment_id     INT     NOT NULL,
+    severity_id             INT     NOT NULL,   -- severity class of the crash
     time_id                 INT     NOT NULL,
+    vehicle_type_id         INT     NOT NULL,   -- the person's vehicle
+
+    -- Measures (additive): the person's own outcome
+    is_injured              BIT     NOT NULL,
+    is_killed               BIT     NOT NULL,
 
-    -- Measures (additive)
-    persons_injured         TINYINT NOT NULL,
-    persons_killed          TINYINT NOT NULL,
-    pedestrians_injured     TINYINT NOT NULL,
-    pedestrians_killed      TINYINT NOT NULL,
-    cyclist_injured         TINYINT NOT NULL,
-    cyclist_killed          TINYINT NOT NULL,
-    motorist_injured        TINYINT NOT NULL,
-    motorist_killed         TINYINT NOT NULL,
+    -- Measures (additive per crash, not per person): crash totals, stored
+    -- as reported (capped at 65535); counts above casualty_plausibility
+    -- max_count (100 by default) are flagged IMPLAUSIBLE_COUNT on load.
+    -- persons_injured / persons_killed are the larger of the person total
+    -- and the pedestrian + cyclist + motorist sum, as for DimSeverity, so
+    -- that a FATAL crash never shows 0 killed.
+    persons_injured         INT     NOT NULL,
+    persons_killed          INT     NOT NULL,
+    pedestrians_injured     INT     NOT NULL,
+    pedestrians_killed      INT     NOT NULL,
+    cyclist_injured         INT     NOT NULL,
+    cyclist_killed          INT     NOT NULL,
+    motorist_injured        INT     NOT NULL,
+    motorist_killed         INT     NOT NULL,
 
     -- PK is NONCLUSTERED: the CCI (below) is the physical storage order.
     CONSTRAINT PK_Fact PRIMARY KEY NONCLUSTERED (fact_id),
@@ -376,6 +826,38 @@ CREATE TABLE project_julian_bruder_kenana_saeed.Fact (
         FOREIGN KEY (contributing_factor_id)
         REFERENCES project_julian_bruder_kenana_saeed.DimContributingFactor (contributing_factor_id),
 
+    CONSTRAINT FK_Fact_FactorGroup
+        FOREIGN KEY (factor_group_id)
+        REFERENCES project_julian_bruder_kenana_saeed.DimFactorGroup (factor_group_id),
+
+    CONSTRAINT FK_Fact_VehicleType
+        FOREIGN KEY (vehicle_type_id)
+        REFERENCES project_julian_bruder_kenana_saeed.DimVehicleType (vehicle_type_id),
+
+    CONSTRAINT FK_Fact_Location
+        FOREIGN KEY (location_id)
+        REFERENCES project_julian_bruder_kenana_saeed.DimLocation (location_id),
+
+    CONSTRAINT FK_Fact_PersonInjury
+        FOREIGN KEY (person_injury_id)
+        REFERENCES project_julian_bruder_kenana_saeed.DimPersonInjury (person_injury_id),
+
+    CONSTRAINT FK_Fact_BodilyInjury
+        FOREIGN KEY (bodily_injury_id)
+        REFERENCES project_julian_bruder_kenana_saeed.DimBodilyInjury (bodily_injury_id),
+
+    CONSTRAINT FK_Fact_SafetyEquipment
+        FOREIGN KEY (safety_equipment_id)
+        REFERENCES project_julian_bruder_kenana_saeed.DimSafetyEquipment (safety_equipment_id),
+
+    CONSTRAINT FK_Fact_PedestrianAction
+        FOREIGN KEY (pedestrian_action_id)
+        REFERENCES project_julian_bruder_kenana_saeed.DimPedestrianAction (pedestrian_action_id),
+
+    CONSTRAINT FK_Fact_Severity
+        FOREIGN KEY (severity_id)
+        REFERENCES project_julian_bruder_kenana_saeed.DimSeverity (severity_id),
+
     CONSTRAINT CK_Fact_NonNegative CHECK (
         persons_injured     >= 0 AND
         persons_killed      >= 0 AND
@@ -396,6 +878,71 @@ CREATE CLUSTERED COLUMNSTORE INDEX CCI_Fact
     ON project_julian_bruder_kenana_saeed.Fact;
 
 
+-- =============================================================================
+-- Fact Table: Crash
+--
+-- Grain: one row per collision. Summing the measures here counts every
+-- victim once, whereas the person-grain Fact repeats the crash totals for
+-- every person involved. Use Fact for demographic questions and FactCrash
+-- for injury and death totals.
+--
+-- crash_id is the NYPD COLLISION_ID, kept as a degenerate dimension.
+-- contributing_factor_id is the first factor of the crash; all factors are
+-- reachable through factor_group_id and BridgeFactorGroup.
+-- Stored as a clustered columnstore for the same reasons as Fact.
+-- =============================================================================
+CREATE TABLE project_julian_bruder_kenana_saeed.FactCrash (
+    -- Degenerate dimension / key
+    crash_id                INT     NOT NULL,
+
+    -- Dimension foreign keys
+    contributing_factor_id  INT     NOT NULL,
+    factor_group_id         INT     NOT NULL,
+    location_id             INT     NOT NULL,
+    severity_id             INT     NOT NULL,
+    time_id                 INT     NOT NULL,
+
+    -- Measures (additive); persons_injured / persons_killed as on Fact
+    persons_injured         INT     NOT NULL,
+    persons_killed          INT     NOT NULL,
+    pedestrians_injured     INT     NOT NULL,
+    pedestrians_killed      INT     NOT NULL,
+    cyclist_injured         INT     NOT NULL,
+    cyclist_killed          INT     NOT NULL,
+    motorist_injured        INT     NOT NULL,
+    motorist_killed         INT     NOT NULL,
+    -- weighted severity index of the crash, injured_weight ×
+    -- persons_injured + killed_weight × persons_killed (config.json
+    -- "severity"; 1 and 10 by default)
+    severity_index          INT     NOT NULL,
+
+    CONSTRAINT PK_FactCrash PRIMARY KEY NONCLUSTERED (crash_id),
+
+    CONSTRAINT FK_FactCrash_Time
+        FOREIGN KEY (time_id)
+        REFERENCES project_julian_bruder_kenana_saeed.DimTime (time_id),
+
+    CONSTRAINT FK_FactCrash_ContributingFactor
+        FOREIGN KEY (contributing_factor_id)
+        REFERENCES project_julian_bruder_kenana_saeed.DimContributingFactor (contributing_factor_id),
+
+    CONSTRAINT FK_FactCrash_FactorGroup
+        FOREIGN KEY (factor_group_id)
+        REFERENCES project_julian_bruder_kenana_saeed.DimFactorGroup (factor_group_id),
+
+    CONSTRAINT FK_FactCrash_Location
+        FOREIGN KEY (location_id)
+        REFERENCES project_julian_bruder_kenana_saeed.DimLocation (location_id),
+
+    CONSTRAINT FK_FactCrash_Severity
+        FOREIGN KEY (severity_id)
+        REFERENCES project_julian_bruder_kenana_saeed.DimSeverity (severity_id)
+);
+
+CREATE CLUSTERED COLUMNSTORE INDEX CCI_FactCrash
+    ON project_julian_bruder_kenana_saeed.FactCrash;
+
+
 -- =============================================================================
 -- Materialized View: Severity by Moon Phase, Weather, Factor, Sex and Age Group
 --
@@ -415,9 +962,10 @@ CREATE CLUSTERED COLUMNSTORE INDEX CCI_Fact
 --
 -- SCOPE NOTE:
 --   incident_count counts fact rows (people) in each cell, not unique crashes.
---   Severity sums are additive across the grain, consistent with the fact table
---   definition.  Consumers who need crash-level counts must apply additional
---   logic (not in scope of this mart layer).
+--   injured_persons / killed_persons sum the person's own PERSON_INJURY
+--   outcome. The crash totals of Fact are deliberately left
+--   out: they repeat for every person of the crash and would be multiplied by
+--   its occupants.
 -- =============================================================================
 CREATE VIEW project_julian_bruder_kenana_saeed.MV_SeverityByMoonWeatherFactorSexAge
 WITH SCHEMABINDING
@@ -430,15 +978,10 @@ AS
         dps.person_sex                                  AS person_sex,
         dpa.person_age_hier_def_group                   AS age_group,
 
-        -- Severity measures (all additive — SUM is safe across the grain)
-        SUM(CAST(f.persons_injured     AS INT))         AS total_persons_injured,
-        SUM(CAST(f.persons_killed      AS INT))         AS total_persons_killed,
-        SUM(CAST(f.pedestrians_injured AS INT))         AS total_pedestrians_injured,
-        SUM(CAST(f.pedestrians_killed  AS INT))         AS total_pedestrians_killed,
-        SUM(CAST(f.cyclist_injured     AS INT))         AS total_cyclist_injured,
-        SUM(CAST(f.cyclist_killed      AS INT))         AS total_cyclist_killed,
-        SUM(CAST(f.motorist_injured    AS INT))         AS total_motorist_injured,
-        SUM(CAST(f.motorist_killed     AS INT))         AS total_motorist_killed,
+        -- Severity measures: the persons' own outcome, so every injured or
+        -- killed person of the cell is counted exactly once
+        SUM(CAST(f.is_injured          AS BIGINT))      AS injured_persons,
+        SUM(CAST(f.is_killed           AS BIGINT))      AS killed_persons,
 
         -- Row count required by SQL Server for indexed view maintenance
         COUNT_BIG(*)                                    AS incident_count
@@ -467,3 +1010,55 @@ GO
 CREATE UNIQUE CLUSTERED INDEX UCI_MV_SeverityByMoonWeatherFactorSexAge
     ON project_julian_bruder_kenana_saeed.MV_SeverityByMoonWeatherFactorSexAge
         (moon_phase, weather, person_sex, age_group, factor_category);
+
+
+-- =============================================================================
+-- Materialized View: Crash Severity by Moon Phase, Weather and Factor
+--
+-- WHY THIS VIEW:
+--   The crash totals (persons, pedestrians, cyclists and motorists injured or
+--   killed) are only additive at the crash grain. Summed over Fact they would
+--   be counted once per person of the crash, so they are aggregated here from
+--   FactCrash instead, along the crash-level axes of the research question.
+--   Sex and age group are properties of persons and are therefore not axes of
+--   this view; MV_SeverityByMoonWeatherFactorSexAge covers them.
+--
+-- SCOPE NOTE:
+--   crash_count counts collisions. factor_category is the category of the
+--   first contributing factor of the crash, as on FactCrash.
+-- =============================================================================
+CREATE VIEW project_julian_bruder_kenana_saeed.MV_CrashSeverityByMoonWeatherFactor
+WITH SCHEMABINDING
+AS
+    SELECT
+        dt.hier_moon_phase                              AS moon_phase,
+        dt.weather                                      AS weather,
+        dcf.contributing_factor_hier_def_category       AS factor_category,
+
+        -- Crash totals, every victim counted once
+        SUM(CAST(fc.persons_injured     AS BIGINT))     AS total_persons_injured,
+        SUM(CAST(fc.persons_killed      AS BIGINT))     AS total_persons_killed,
+        SUM(CAST(fc.pedestrians_injured AS BIGINT))     AS total_pedestrians_injured,
+        SUM(CAST(fc.pedestrians_killed  AS BIGINT))     AS total_pedestrians_killed,
+        SUM(CAST(fc.cyclist_injured     AS BIGINT))     AS total_cyclist_injured,
+        SUM(CAST(fc.cyclist_killed      AS BIGINT))     AS total_cyclist_killed,
+        SUM(CAST(fc.motorist_injured    AS BIGINT))     AS total_motorist_injured,
+        SUM(CAST(fc.motorist_killed     AS BIGINT))     AS total_motorist_killed,
+        SUM(CAST(fc.severity_index      AS BIGINT))     AS total_severity_index,
+
+        -- Row count required by SQL Server for indexed view maintenance
+        COUNT_BIG(*)                                    AS crash_count
+
+    FROM project_julian_bruder_kenana_saeed.FactCrash               AS fc
+    JOIN project_julian_bruder_kenana_saeed.DimTime                 AS dt  ON dt.time_id                = fc.time_id
+    JOIN project_julian_bruder_kenana_saeed.DimContributingFactor   AS dcf ON dcf.contributing_factor_id = fc.contributing_factor_id
+
+    GROUP BY
+        dt.hier_moon_phase,
+        dt.weather,
+        dcf.contributing_factor_hier_def_category;
+GO
+
+CREATE UNIQUE CLUSTERED INDEX UCI_MV_CrashSeverityByMoonWeatherFactor
+    ON project_julian_bruder_kenana_saeed.MV_CrashSeverityByMoonWeatherFactor
+        (moon_phase, weather, factor_category);
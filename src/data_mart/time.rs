@@ -2,26 +2,114 @@ use serde::de::Error;
 use serde::{Deserialize, Serialize};
 use serde::{Deserializer, Serializer};
 use time::PrimitiveDateTime;
-use time::{Date, macros::format_description};
+use time::{Date, Weekday, macros::format_description};
 
 use crate::base_database;
+use crate::calendar::holidays::{self, holiday_on};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Time {
     pub time_id: u32,
+    /// New York local time.
     #[serde(with = "sql_datetime")]
     pub timestamp: PrimitiveDateTime,
+    pub utc_offset_minutes: i16,
+    pub dst_flag: DstFlag,
 
     // default hierarchy
     pub hier_def_day: Date,
     pub hier_def_month: String,
+    /// 1–12, to sort `hier_def_month` chronologically.
+    pub hier_def_month_number: u8,
+    pub hier_def_quarter: u8,
     pub hier_def_year: u16,
 
+    // calendar attributes
+    pub hour_of_day: u8,
+    pub day_of_week: DayOfWeek,
+    /// ISO 8601: 1 is Monday.
+    pub day_of_week_number: u8,
+    /// ISO 8601 week; the first days of January may belong to the last week
+    /// of the year before, see `iso_week_year`.
+    pub iso_week: u8,
+    pub iso_week_year: u16,
+    pub is_weekend: bool,
+    pub is_rush_hour: bool,
+    pub season: Season,
+    pub holiday: Holiday,
+    pub is_holiday: bool,
+
     // moon hierarchy
     pub hier_moon_phase: MoonPhase,
+    pub moon_illumination: f64,
+    pub moon_age_days: f64,
+
+    // light
+    pub light_condition: LightCondition,
+    pub sun_altitude: f64,
 
     // denormalized weather
     pub weather: Weather,
+    /// Id of the weather rule that classified the hour, `None` without an
+    /// observation.
+    pub weather_rule_id: Option<String>,
+    pub snow_risk: SnowRisk,
+    // weather measures, `None` for hours without an observation
+    pub temperature_c: Option<f64>,
+    pub precipitation_mm: Option<f64>,
+    pub rain_mm: Option<f64>,
+    pub cloudcover_pct: Option<f64>,
+    pub windspeed_kmh: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DstFlag {
+    Normal,
+    Ambiguous,
+    NonExistent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DayOfWeek {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+/// Meteorological season: winter is December to February.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Season {
+    Winter,
+    Spring,
+    Summer,
+    Autumn,
+}
+
+/// The US federal or New York State holiday on, or observed on, the day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Holiday {
+    NewYearsDay,
+    MartinLutherKingJrDay,
+    LincolnsBirthday,
+    WashingtonsBirthday,
+    MemorialDay,
+    Juneteenth,
+    IndependenceDay,
+    LaborDay,
+    ColumbusDay,
+    ElectionDay,
+    VeteransDay,
+    Thanksgiving,
+    ChristmasDay,
+    NoHoliday,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -38,6 +126,14 @@ pub enum MoonPhase {
     Unknown,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LightCondition {
+    Daylight,
+    CivilTwilight,
+    Night,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Weather {
@@ -51,34 +147,154 @@ pub enum Weather {
     Unknown,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SnowRisk {
+    NoRisk,
+    Frost,
+    FreezingRain,
+    Snow,
+    Unknown,
+}
+
+/// Local hours counted as rush hour on working days: 07:00–09:59 and
+/// 16:00–18:59.
+pub const RUSH_HOURS: [std::ops::Range<u8>; 2] = [7..10, 16..19];
+
 impl Time {
     pub fn gen_times(bdb_times: Vec<base_database::time::Time>) -> Vec<Time> {
         let month_format = format_description!("[month repr:long]");
         bdb_times
             .into_iter()
             .enumerate()
-            .map(|(i, bdd_time)| Time {
-                time_id: i as u32 + 1, // start from 1 to avoid confusion with conventional uninitialized value of 0
-                timestamp: bdd_time.timestamp,
-                hier_def_day: bdd_time.timestamp.date(),
-                hier_def_month: bdd_time
-                    .timestamp
-                    .format(&month_format)
-                    .expect("shouldn't fail formatting Month from Date"),
-                hier_def_year: bdd_time.timestamp.year() as u16,
-                hier_moon_phase: bdd_time
-                    .moon_phase
-                    .map(MoonPhase::from)
-                    .unwrap_or(MoonPhase::Unknown),
-                weather: bdd_time
-                    .weather
-                    .map(Weather::from)
-                    .unwrap_or(Weather::Unknown),
+            .map(|(i, bdd_time)| {
+                let date = bdd_time.timestamp.date();
+                let hour = bdd_time.timestamp.hour();
+                let (iso_week_year, iso_week, weekday) = date.to_iso_week_date();
+                let holiday = holiday_on(date);
+                let is_weekend = matches!(weekday, Weekday::Saturday | Weekday::Sunday);
+                let month = date.month() as u8;
+                Time {
+                    time_id: i as u32 + 1, // start from 1 to avoid confusion with conventional uninitialized value of 0
+                    timestamp: bdd_time.timestamp,
+                    utc_offset_minutes: bdd_time.utc_offset.whole_minutes(),
+                    dst_flag: DstFlag::from(bdd_time.dst_flag),
+                    hier_def_day: bdd_time.timestamp.date(),
+                    hier_def_month: bdd_time
+                        .timestamp
+                        .format(&month_format)
+                        .expect("shouldn't fail formatting Month from Date"),
+                    hier_def_month_number: month,
+                    hier_def_quarter: (month - 1) / 3 + 1,
+                    hier_def_year: bdd_time.timestamp.year() as u16,
+                    hour_of_day: hour,
+                    day_of_week: DayOfWeek::from(weekday),
+                    day_of_week_number: weekday.number_from_monday(),
+                    iso_week,
+                    iso_week_year: iso_week_year as u16,
+                    is_weekend,
+                    is_rush_hour: !is_weekend
+                        && holiday.is_none()
+                        && RUSH_HOURS.iter().any(|r| r.contains(&hour)),
+                    season: Season::of(month),
+                    holiday: holiday.map_or(Holiday::NoHoliday, Holiday::from),
+                    is_holiday: holiday.is_some(),
+                    hier_moon_phase: MoonPhase::from(bdd_time.moon_phase),
+                    moon_illumination: round_to(bdd_time.moon_illumination, 3),
+                    moon_age_days: round_to(bdd_time.moon_age_days, 2),
+                    light_condition: LightCondition::from(bdd_time.light_condition),
+                    sun_altitude: round_to(bdd_time.sun_altitude, 2),
+                    weather: bdd_time
+                        .weather
+                        .map(Weather::from)
+                        .unwrap_or(Weather::Unknown),
+                    snow_risk: bdd_time.snow_risk.map_or(SnowRisk::Unknown, SnowRisk::from),
+                    temperature_c: measure(&bdd_time, |m| m.temperature),
+                    precipitation_mm: measure(&bdd_time, |m| m.precipitation),
+                    rain_mm: measure(&bdd_time, |m| m.rain),
+                    cloudcover_pct: measure(&bdd_time, |m| m.cloudcover),
+                    windspeed_kmh: measure(&bdd_time, |m| m.windspeed),
+                    weather_rule_id: bdd_time.weather_rule_id,
+                }
             })
             .collect()
     }
 }
 
+/// One of the weather measures of the hour, rounded to the 0.01 of the
+/// DECIMAL column; the source has at most two decimals.
+fn measure(
+    bdd_time: &base_database::time::Time,
+    f: impl Fn(&base_database::time::WeatherMeasures) -> f32,
+) -> Option<f64> {
+    bdd_time
+        .weather_measures
+        .as_ref()
+        .map(|m| round_to(f64::from(f(m)), 2))
+}
+
+/// Rounds to the number of decimals of the corresponding DECIMAL column.
+fn round_to(value: f64, decimals: i32) -> f64 {
+    let factor = 10f64.powi(decimals);
+    (value * factor).round() / factor
+}
+
+impl Season {
+    /// Season of the month numbered `month` (1–12).
+    fn of(month: u8) -> Self {
+        match month {
+            3..=5 => Season::Spring,
+            6..=8 => Season::Summer,
+            9..=11 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+}
+
+impl From<Weekday> for DayOfWeek {
+    fn from(weekday: Weekday) -> Self {
+        match weekday {
+            Weekday::Monday => DayOfWeek::Monday,
+            Weekday::Tuesday => DayOfWeek::Tuesday,
+            Weekday::Wednesday => DayOfWeek::Wednesday,
+            Weekday::Thursday => DayOfWeek::Thursday,
+            Weekday::Friday => DayOfWeek::Friday,
+            Weekday::Saturday => DayOfWeek::Saturday,
+            Weekday::Sunday => DayOfWeek::Sunday,
+        }
+    }
+}
+
+impl From<holidays::Holiday> for Holiday {
+    fn from(holiday: holidays::Holiday) -> Self {
+        match holiday {
+            holidays::Holiday::NewYearsDay => Holiday::NewYearsDay,
+            holidays::Holiday::MartinLutherKingJrDay => Holiday::MartinLutherKingJrDay,
+            holidays::Holiday::LincolnsBirthday => Holiday::LincolnsBirthday,
+            holidays::Holiday::WashingtonsBirthday => Holiday::WashingtonsBirthday,
+            holidays::Holiday::MemorialDay => Holiday::MemorialDay,
+            holidays::Holiday::Juneteenth => Holiday::Juneteenth,
+            holidays::Holiday::IndependenceDay => Holiday::IndependenceDay,
+            holidays::Holiday::LaborDay => Holiday::LaborDay,
+            holidays::Holiday::ColumbusDay => Holiday::ColumbusDay,
+            holidays::Holiday::ElectionDay => Holiday::ElectionDay,
+            holidays::Holiday::VeteransDay => Holiday::VeteransDay,
+            holidays::Holiday::Thanksgiving => Holiday::Thanksgiving,
+            holidays::Holiday::ChristmasDay => Holiday::ChristmasDay,
+        }
+    }
+}
+
+impl From<base_database::timezone::DstFlag> for DstFlag {
+    fn from(dst_flag: base_database::timezone::DstFlag) -> Self {
+        match dst_flag {
+            base_database::timezone::DstFlag::Normal => DstFlag::Normal,
+            base_database::timezone::DstFlag::Ambiguous => DstFlag::Ambiguous,
+            base_database::timezone::DstFlag::NonExistent => DstFlag::NonExistent,
+        }
+    }
+}
+
 impl From<base_database::time::MoonPhase> for MoonPhase {
     fn from(moon_phase: base_database::time::MoonPhase) -> Self {
         match moon_phase {
@@ -94,6 +310,16 @@ impl From<base_database::time::MoonPhase> for MoonPhase {
     }
 }
 
+impl From<base_database::time::LightCondition> for LightCondition {
+    fn from(light_condition: base_database::time::LightCondition) -> Self {
+        match light_condition {
+            base_database::time::LightCondition::Daylight => LightCondition::Daylight,
+            base_database::time::LightCondition::CivilTwilight => LightCondition::CivilTwilight,
+            base_database::time::LightCondition::Night => LightCondition::Night,
+        }
+    }
+}
+
 impl From<base_database::time::Weather> for Weather {
     fn from(weather: base_database::time::Weather) -> Self {
         match weather {
@@ -108,6 +334,17 @@ impl From<base_database::time::Weather> for Weather {
     }
 }
 
+impl From<base_database::time::SnowRisk> for SnowRisk {
+    fn from(snow_risk: base_database::time::SnowRisk) -> Self {
+        match snow_risk {
+            base_database::time::SnowRisk::NoRisk => SnowRisk::NoRisk,
+            base_database::time::SnowRisk::Frost => SnowRisk::Frost,
+            base_database::time::SnowRisk::FreezingRain => SnowRisk::FreezingRain,
+            base_database::time::SnowRisk::Snow => SnowRisk::Snow,
+        }
+    }
+}
+
 pub mod sql_datetime {
     use time::{PrimitiveDateTime, format_description::BorrowedFormatItem};
 
@@ -132,3 +369,51 @@ pub mod sql_datetime {
         PrimitiveDateTime::parse(&s, &FORMAT).map_err(D::Error::custom)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::{PrimitiveDateTime, UtcOffset, macros::datetime};
+
+    fn gen_time(timestamp: PrimitiveDateTime) -> Time {
+        let bdb_time = base_database::time::Time {
+            time_id: 0,
+            timestamp,
+            utc_offset: UtcOffset::from_hms(-5, 0, 0).unwrap(),
+            dst_flag: base_database::timezone::DstFlag::Normal,
+            moon_phase: base_database::time::MoonPhase::New,
+            moon_phase_angle: 180.0,
+            moon_illumination: 0.0,
+            moon_age_days: 0.0,
+            sun_altitude: -10.0,
+            light_condition: base_database::time::LightCondition::Night,
+            weather: None,
+            weather_rule_id: None,
+            weather_measures: None,
+            snow_risk: None,
+        };
+        Time::gen_times(vec![bdb_time]).remove(0)
+    }
+
+    #[test]
+    fn calendar_attributes() {
+        let t = gen_time(datetime!(2021-01-01 08:00));
+        assert_eq!(t.hier_def_month_number, 1);
+        assert_eq!(t.hier_def_quarter, 1);
+        assert_eq!(t.day_of_week, DayOfWeek::Friday);
+        assert_eq!(t.day_of_week_number, 5);
+        // belongs to the last ISO week of 2020
+        assert_eq!((t.iso_week_year, t.iso_week), (2020, 53));
+        assert_eq!(t.season, Season::Winter);
+        assert_eq!(t.holiday, Holiday::NewYearsDay);
+        assert!(!t.is_rush_hour);
+
+        let t = gen_time(datetime!(2021-11-24 17:00));
+        assert_eq!(t.hier_def_quarter, 4);
+        assert_eq!(t.season, Season::Autumn);
+        assert!(t.is_rush_hour && !t.is_weekend && !t.is_holiday);
+
+        let t = gen_time(datetime!(2021-11-27 08:00));
+        assert!(t.is_weekend && !t.is_rush_hour);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::base_database::vehicle;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumCount as EnumCountMacro;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumCountMacro)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum VehicleType {
+    Sedan,
+    StationWagonSuv,
+    Convertible,
+    Taxi,
+    Van,
+    PickUpTruck,
+    BoxTruck,
+    TractorTruck,
+    DumpTruck,
+    GarbageTruck,
+    TowTruck,
+    Tanker,
+    FlatBed,
+    OtherTruck,
+    Bus,
+    Bicycle,
+    EBike,
+    EScooter,
+    Moped,
+    Motorcycle,
+    Ambulance,
+    FireTruck,
+    Pedicab,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum VehicleCategory {
+    PassengerCar,
+    Truck,
+    TwoWheeler,
+    Bus,
+    Other,
+    Unknown,
+}
+
+/// Vehicle type of the person's own vehicle; persons without one
+/// (pedestrians) and unrecognised types are Unknown.
+///
+/// Hierarchy:
+///   Level 1 – vehicle_type
+///   Level 2 – vehicle_type_hier_def_category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct VehicleTypeDim {
+    pub vehicle_type_id: u32,
+    pub vehicle_type: VehicleType,
+    pub vehicle_type_hier_def_category: VehicleCategory,
+}
+
+impl VehicleTypeDim {
+    pub fn gen_vehicle_types() -> Vec<VehicleTypeDim> {
+        use VehicleCategory as C;
+        use VehicleType as T;
+        [
+            (T::Unknown, C::Unknown),
+            (T::Sedan, C::PassengerCar),
+            (T::StationWagonSuv, C::PassengerCar),
+            (T::Convertible, C::PassengerCar),
+            (T::Taxi, C::PassengerCar),
+            (T::Van, C::PassengerCar),
+            (T::PickUpTruck, C::Truck),
+            (T::BoxTruck, C::Truck),
+            (T::TractorTruck, C::Truck),
+            (T::DumpTruck, C::Truck),
+            (T::GarbageTruck, C::Truck),
+            (T::TowTruck, C::Truck),
+            (T::Tanker, C::Truck),
+            (T::FlatBed, C::Truck),
+            (T::OtherTruck, C::Truck),
+            (T::Bus, C::Bus),
+            (T::Bicycle, C::TwoWheeler),
+            (T::EBike, C::TwoWheeler),
+            (T::EScooter, C::TwoWheeler),
+            (T::Moped, C::TwoWheeler),
+            (T::Motorcycle, C::TwoWheeler),
+            (T::Ambulance, C::Other),
+            (T::FireTruck, C::Other),
+            (T::Pedicab, C::Other),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (vehicle_type, category))| VehicleTypeDim {
+            vehicle_type_id: i as u32,
+            vehicle_type,
+            vehicle_type_hier_def_category: category,
+        })
+        .collect()
+    }
+}
+
+impl From<vehicle::VehicleType> for VehicleType {
+    fn from(t: vehicle::VehicleType) -> Self {
+        use vehicle::VehicleType as B;
+        match t {
+            B::Sedan => VehicleType::Sedan,
+            B::StationWagonSuv => VehicleType::StationWagonSuv,
+            B::Convertible => VehicleType::Convertible,
+            B::Taxi => VehicleType::Taxi,
+            B::Van => VehicleType::Van,
+            B::PickUpTruck => VehicleType::PickUpTruck,
+            B::BoxTruck => VehicleType::BoxTruck,
+            B::TractorTruck => VehicleType::TractorTruck,
+            B::DumpTruck => VehicleType::DumpTruck,
+            B::GarbageTruck => VehicleType::GarbageTruck,
+            B::TowTruck => VehicleType::TowTruck,
+            B::Tanker => VehicleType::Tanker,
+            B::FlatBed => VehicleType::FlatBed,
+            B::OtherTruck => VehicleType::OtherTruck,
+            B::Bus => VehicleType::Bus,
+            B::Bicycle => VehicleType::Bicycle,
+            B::EBike => VehicleType::EBike,
+            B::EScooter => VehicleType::EScooter,
+            B::Moped => VehicleType::Moped,
+            B::Motorcycle => VehicleType::Motorcycle,
+            B::Ambulance => VehicleType::Ambulance,
+            B::FireTruck => VehicleType::FireTruck,
+            B::Pedicab => VehicleType::Pedicab,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::EnumCount;
+
+    #[test]
+    fn gen_vehicle_types_covers_every_type_once() {
+        let dim = VehicleTypeDim::gen_vehicle_types();
+        assert_eq!(dim.len(), VehicleType::COUNT);
+        for t in &dim {
+            assert_eq!(
+                dim.iter()
+                    .filter(|o| o.vehicle_type == t.vehicle_type)
+                    .count(),
+                1
+            );
+        }
+        assert_eq!(dim[0].vehicle_type, VehicleType::Unknown);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod rejects;
+pub mod unmapped;
@@ -0,0 +1,263 @@
+//! Quarantine for rows that are dropped or degraded on their way into the
+//! base database.
+//!
+//! Every rejected row is appended to `<dir>/<source>.csv` as
+//! `reason,line,column,value` followed by the fields of the original record,
+//! and counted per source and reason for the end-of-run summary. Unknown
+//! categories are also tallied per distinct value, see [`UnmappedValues`].
+
+use crate::data_quality::unmapped::UnmappedValues;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    fs::File,
+    path::PathBuf,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RejectSource {
+    Crashes,
+    Persons,
+    Vehicles,
+    Weather,
+    Moon,
+}
+
+impl RejectSource {
+    pub fn file_stem(self) -> &'static str {
+        match self {
+            RejectSource::Crashes => "crashes",
+            RejectSource::Persons => "persons",
+            RejectSource::Vehicles => "vehicles",
+            RejectSource::Weather => "weather",
+            RejectSource::Moon => "moon",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RejectReason {
+    /// A required field could not be parsed; the row was dropped.
+    Unparseable,
+    /// An optional field could not be parsed and was set to unknown; the row was kept.
+    Degraded,
+    /// A category string matched no known value and was set to unknown; the row was kept.
+    UnknownCategory,
+    /// The person references a collision missing from the crashes file; the row was dropped.
+    OrphanPerson,
+    /// The crash lies outside the time dimension; its persons produce no fact rows.
+    NoTimeMember,
+    /// The coordinates lie outside New York City and were set to unknown; the row was kept.
+    OutsideNyc,
+    /// A casualty count exceeds plausible bounds; the row was kept as reported.
+    ImplausibleCount,
+    /// An age lies outside the plausible range and was set to unknown; the row was kept.
+    ImplausibleAge,
+    /// A driver is younger than the minimum driver age; the age was set to
+    /// unknown and the row was kept.
+    UnderageDriver,
+}
+
+impl RejectReason {
+    pub fn code(self) -> &'static str {
+        match self {
+            RejectReason::Unparseable => "UNPARSEABLE",
+            RejectReason::Degraded => "DEGRADED",
+            RejectReason::UnknownCategory => "UNKNOWN_CATEGORY",
+            RejectReason::OrphanPerson => "ORPHAN_PERSON",
+            RejectReason::NoTimeMember => "NO_TIME_MEMBER",
+            RejectReason::OutsideNyc => "OUTSIDE_NYC",
+            RejectReason::ImplausibleCount => "IMPLAUSIBLE_COUNT",
+            RejectReason::ImplausibleAge => "IMPLAUSIBLE_AGE",
+            RejectReason::UnderageDriver => "UNDERAGE_DRIVER",
+        }
+    }
+}
+
+impl fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Where a rejected value was found.
+#[derive(Debug, Clone, Copy)]
+pub struct RejectLocation<'a> {
+    pub line: u64,
+    pub column: &'a str,
+    pub value: &'a str,
+}
+
+/// Collects rejected rows and writes them to per-source CSV files.
+///
+/// [`Rejects::default`] only counts, which is what unit tests and library
+/// callers without an output directory want.
+#[derive(Default)]
+pub struct Rejects {
+    dir: Option<PathBuf>,
+    writers: HashMap<RejectSource, csv::Writer<File>>,
+    counts: BTreeMap<(RejectSource, RejectReason), u64>,
+    unmapped: UnmappedValues,
+}
+
+impl Rejects {
+    /// Writes reject files to `dir`, which is created on the first reject.
+    pub fn to_dir(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Some(dir.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Quarantines a row that is available as its original CSV fields.
+    pub fn reject_fields<I, F>(
+        &mut self,
+        source: RejectSource,
+        reason: RejectReason,
+        at: RejectLocation<'_>,
+        fields: I,
+    ) where
+        I: IntoIterator<Item = F>,
+        F: AsRef<[u8]>,
+    {
+        if let Some(wtr) = self.begin(source, reason, at) {
+            wtr.write_record(fields)
+                .unwrap_or_else(|e| panic!("failed to write {} reject: {e}", source.file_stem()));
+        }
+    }
+
+    /// Quarantines a row that is available as a parsed raw record.
+    pub fn reject_record<T: Serialize>(
+        &mut self,
+        source: RejectSource,
+        reason: RejectReason,
+        at: RejectLocation<'_>,
+        record: &T,
+    ) {
+        if let Some(wtr) = self.begin(source, reason, at) {
+            wtr.serialize(record)
+                .unwrap_or_else(|e| panic!("failed to write {} reject: {e}", source.file_stem()));
+        }
+    }
+
+    /// Counts the reject and writes the `reason,line,column,value` prefix.
+    fn begin(
+        &mut self,
+        source: RejectSource,
+        reason: RejectReason,
+        at: RejectLocation<'_>,
+    ) -> Option<&mut csv::Writer<File>> {
+        *self.counts.entry((source, reason)).or_default() += 1;
+        if reason == RejectReason::UnknownCategory {
+            self.unmapped.record(source, at.column, at.value);
+        }
+
+        let dir = self.dir.as_ref()?;
+        let wtr = self.writers.entry(source).or_insert_with(|| {
+            std::fs::create_dir_all(dir)
+                .unwrap_or_else(|e| panic!("failed to create {}: {e}", dir.display()));
+            let path = dir.join(format!("{}.csv", source.file_stem()));
+            let mut wtr = csv::WriterBuilder::new()
+                .flexible(true)
+                .has_headers(false)
+                .from_path(&path)
+                .unwrap_or_else(|e| panic!("failed to create {}: {e}", path.display()));
+            wtr.write_record(["reason", "line", "column", "value", "record"])
+                .unwrap_or_else(|e| panic!("failed to write {}: {e}", path.display()));
+            wtr
+        });
+        for field in [reason.code(), &at.line.to_string(), at.column, at.value] {
+            wtr.write_field(field)
+                .unwrap_or_else(|e| panic!("failed to write {} reject: {e}", source.file_stem()));
+        }
+        Some(wtr)
+    }
+
+    /// Tallies a value the source uses for "not known", e.g. `Unspecified`,
+    /// in the unmapped value report without quarantining its row.
+    pub fn record_explicit_unknown(&mut self, source: RejectSource, column: &str, value: &str) {
+        self.unmapped.record_explicit_unknown(source, column, value);
+    }
+
+    /// Number of rejects per source and reason, in a stable order.
+    pub fn summary(&self) -> impl Iterator<Item = (RejectSource, RejectReason, u64)> + '_ {
+        self.counts
+            .iter()
+            .map(|(&(source, reason), &count)| (source, reason, count))
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// The distinct values behind the `UNKNOWN_CATEGORY` rejects.
+    pub fn unmapped(&self) -> &UnmappedValues {
+        &self.unmapped
+    }
+
+    /// Flushes all reject files and returns their paths.
+    pub fn flush(&mut self) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        for (source, wtr) in &mut self.writers {
+            wtr.flush()
+                .unwrap_or_else(|e| panic!("failed to flush {} rejects: {e}", source.file_stem()));
+            if let Some(dir) = &self.dir {
+                paths.push(dir.join(format!("{}.csv", source.file_stem())));
+            }
+        }
+        paths.sort();
+        paths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Record {
+        id: u32,
+        name: Option<String>,
+    }
+
+    #[test]
+    fn writes_prefix_and_original_fields() {
+        let dir = std::env::temp_dir().join(format!(
+            "rejects_writes_prefix_and_original_fields_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut rejects = Rejects::to_dir(&dir);
+        let at = RejectLocation {
+            line: 7,
+            column: "PERSON_AGE",
+            value: "abc",
+        };
+        rejects.reject_fields(
+            RejectSource::Persons,
+            RejectReason::Degraded,
+            at,
+            ["1", "abc"],
+        );
+        rejects.reject_record(
+            RejectSource::Persons,
+            RejectReason::UnknownCategory,
+            at,
+            &Record { id: 2, name: None },
+        );
+        rejects.flush();
+
+        let written = std::fs::read_to_string(dir.join("persons.csv"));
+        std::fs::remove_dir_all(&dir).unwrap();
+        let written = written.unwrap();
+        assert_eq!(
+            written,
+            "reason,line,column,value,record\n\
+             DEGRADED,7,PERSON_AGE,abc,1,abc\n\
+             UNKNOWN_CATEGORY,7,PERSON_AGE,abc,2,\n"
+        );
+        assert_eq!(rejects.total(), 2);
+        assert_eq!(rejects.unmapped().report(|_| 1).len(), 1);
+    }
+}
@@ -0,0 +1,115 @@
+//! Frequency of the category strings that matched no known value.
+//!
+//! Such values end up as unknown, so a category NYPD adds or misspells would
+//! otherwise only show as a growing unknown member. Every distinct value is
+//! tallied per source and column as it is quarantined. Explicit unknowns such
+//! as `Unspecified` are tallied as well, without being quarantined, so that
+//! the whole unknown member is accounted for.
+
+use crate::data_quality::rejects::RejectSource;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default)]
+pub struct UnmappedValues {
+    /// Count and whether the value is an explicit unknown, per source,
+    /// column and value.
+    counts: BTreeMap<(RejectSource, String, String), (u64, bool)>,
+}
+
+/// One distinct unmapped value.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnmappedValue {
+    pub source: &'static str,
+    pub column: String,
+    pub value: String,
+    pub count: u64,
+    /// `count` as a fraction of the rows read from `source`.
+    pub share: f64,
+    /// The source's own marker for "not known", e.g. `Unspecified`: expected,
+    /// so never warned about.
+    pub explicit_unknown: bool,
+}
+
+impl UnmappedValues {
+    pub fn record(&mut self, source: RejectSource, column: &str, value: &str) {
+        self.tally(source, column, value, false);
+    }
+
+    pub fn record_explicit_unknown(&mut self, source: RejectSource, column: &str, value: &str) {
+        self.tally(source, column, value, true);
+    }
+
+    fn tally(&mut self, source: RejectSource, column: &str, value: &str, explicit_unknown: bool) {
+        let (count, _) = self
+            .counts
+            .entry((source, column.to_owned(), value.to_owned()))
+            .or_insert((0, explicit_unknown));
+        *count += 1;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Every distinct value, most frequent first; `rows` gives the number of
+    /// rows read per source, the base of the share.
+    pub fn report(&self, rows: impl Fn(RejectSource) -> u64) -> Vec<UnmappedValue> {
+        let mut report: Vec<UnmappedValue> = self
+            .counts
+            .iter()
+            .map(
+                |((source, column, value), &(count, explicit_unknown))| UnmappedValue {
+                    source: source.file_stem(),
+                    column: column.clone(),
+                    value: value.clone(),
+                    count,
+                    share: count as f64 / rows(*source).max(1) as f64,
+                    explicit_unknown,
+                },
+            )
+            .collect();
+        report.sort_by_key(|v| std::cmp::Reverse(v.count));
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_counts_distinct_values() {
+        let mut unmapped = UnmappedValues::default();
+        unmapped.record(RejectSource::Persons, "PED_ROLE", "Illnes");
+        unmapped.record(RejectSource::Persons, "PED_ROLE", "Scooter Rider");
+        unmapped.record(RejectSource::Persons, "PED_ROLE", "Scooter Rider");
+        unmapped.record(RejectSource::Crashes, "BOROUGH", "Scooter Rider");
+        unmapped.record_explicit_unknown(
+            RejectSource::Crashes,
+            "CONTRIBUTING FACTOR VEHICLE 1",
+            "Unspecified",
+        );
+
+        let report = unmapped.report(|source| match source {
+            RejectSource::Persons => 200,
+            _ => 10,
+        });
+        assert_eq!(report.len(), 4);
+        assert_eq!(report[0].value, "Scooter Rider");
+        assert!(!report[0].explicit_unknown);
+        assert_eq!(report[0].source, "persons");
+        assert_eq!(report[0].count, 2);
+        assert_eq!(report[0].share, 0.01);
+        assert!(
+            report
+                .iter()
+                .any(|v| v.source == "crashes" && v.share == 0.1)
+        );
+        assert!(
+            report
+                .iter()
+                .any(|v| v.value == "Unspecified" && v.explicit_unknown)
+        );
+    }
+}
@@ -0,0 +1,200 @@
+//! Offline reverse geocoding against boundary polygons from a local GeoJSON
+//! file, e.g. the NYC Open Data borough or modified ZCTA boundaries.
+
+use anyhow::{Context, Result, bail};
+use serde_json::Value;
+use std::path::Path;
+
+/// Number of latitude bands the edges of a region are bucketed into, so that
+/// a lookup only tests the edges crossing the latitude of the point.
+const BANDS: usize = 512;
+
+/// One named area, e.g. a borough, made of one or more polygons.
+#[derive(Debug, Clone)]
+pub struct Region {
+    pub name: String,
+    /// `(south, west, north, east)`
+    bbox: (f64, f64, f64, f64),
+    /// Edges of all rings as `((lon, lat), (lon, lat))`.
+    edges: Vec<((f64, f64), (f64, f64))>,
+    /// Indices into `edges`, per latitude band of `bbox`.
+    bands: Vec<Vec<u32>>,
+}
+
+impl Region {
+    fn new(name: String, rings: Vec<Vec<(f64, f64)>>) -> Self {
+        let edges: Vec<_> = rings
+            .iter()
+            .flat_map(|ring| {
+                ring.iter()
+                    .copied()
+                    .zip(ring.iter().copied().cycle().skip(1))
+            })
+            .filter(|(a, b)| a != b)
+            .collect();
+        let points = rings.iter().flatten();
+        let bbox = points.fold(
+            (f64::MAX, f64::MAX, f64::MIN, f64::MIN),
+            |(s, w, n, e), &(lon, lat)| (s.min(lat), w.min(lon), n.max(lat), e.max(lon)),
+        );
+
+        let mut region = Self {
+            name,
+            bbox,
+            edges,
+            bands: vec![Vec::new(); BANDS],
+        };
+        for (i, &((_, lat_a), (_, lat_b))) in region.edges.iter().enumerate() {
+            let (lo, hi) = (region.band(lat_a.min(lat_b)), region.band(lat_a.max(lat_b)));
+            for band in &mut region.bands[lo..=hi] {
+                band.push(i as u32);
+            }
+        }
+        region
+    }
+
+    fn band(&self, latitude: f64) -> usize {
+        let (south, _, north, _) = self.bbox;
+        let height = (north - south).max(f64::EPSILON);
+        (((latitude - south) / height * BANDS as f64) as usize).min(BANDS - 1)
+    }
+
+    /// Whether the point lies inside the region, by the even–odd rule over
+    /// all rings, so holes and multi-polygons need no special casing.
+    pub fn contains(&self, latitude: f64, longitude: f64) -> bool {
+        let (south, west, north, east) = self.bbox;
+        if !(south..=north).contains(&latitude) || !(west..=east).contains(&longitude) {
+            return false;
+        }
+        let mut inside = false;
+        for &i in &self.bands[self.band(latitude)] {
+            let ((x1, y1), (x2, y2)) = self.edges[i as usize];
+            if (y1 > latitude) != (y2 > latitude) {
+                let x = x1 + (latitude - y1) / (y2 - y1) * (x2 - x1);
+                if longitude < x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+}
+
+/// The regions of one GeoJSON `FeatureCollection`.
+#[derive(Debug, Clone, Default)]
+pub struct Boundaries {
+    pub regions: Vec<Region>,
+}
+
+impl Boundaries {
+    /// Loads the `Polygon` and `MultiPolygon` features of the GeoJSON file at
+    /// `path`. Each region is named after the first of `name_properties` the
+    /// feature has; other geometries are skipped.
+    pub fn load(path: impl AsRef<Path>, name_properties: &[&str]) -> Result<Self> {
+        let path = path.as_ref();
+        let json =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        Self::from_geojson(&json, name_properties)
+            .with_context(|| format!("parsing {}", path.display()))
+    }
+
+    pub fn from_geojson(json: &str, name_properties: &[&str]) -> Result<Self> {
+        let root: Value = serde_json::from_str(json)?;
+        let Some(features) = root.get("features").and_then(Value::as_array) else {
+            bail!("not a GeoJSON FeatureCollection");
+        };
+
+        let mut regions = Vec::new();
+        for (i, feature) in features.iter().enumerate() {
+            let properties = feature.get("properties");
+            let name = name_properties
+                .iter()
+                .find_map(|p| match properties?.get(p)? {
+                    Value::String(s) => Some(s.clone()),
+                    Value::Number(n) => Some(n.to_string()),
+                    _ => None,
+                })
+                .with_context(|| {
+                    format!("feature {i} has none of the properties {name_properties:?}")
+                })?;
+            let geometry = feature.get("geometry").unwrap_or(&Value::Null);
+            let coordinates = geometry.get("coordinates").unwrap_or(&Value::Null);
+            let rings = match geometry.get("type").and_then(Value::as_str) {
+                Some("Polygon") => rings(coordinates),
+                Some("MultiPolygon") => coordinates
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(rings)
+                    .collect::<Option<Vec<_>>>()
+                    .map(|polygons| polygons.concat()),
+                _ => continue,
+            }
+            .with_context(|| format!("feature {i} ({name}) has malformed coordinates"))?;
+            regions.push(Region::new(name, rings));
+        }
+        Ok(Self { regions })
+    }
+
+    /// Name of the first region containing the point.
+    pub fn locate(&self, latitude: f64, longitude: f64) -> Option<&str> {
+        self.regions
+            .iter()
+            .find(|r| r.contains(latitude, longitude))
+            .map(|r| r.name.as_str())
+    }
+}
+
+/// The rings of one GeoJSON polygon as `(lon, lat)` points.
+fn rings(polygon: &Value) -> Option<Vec<Vec<(f64, f64)>>> {
+    polygon
+        .as_array()?
+        .iter()
+        .map(|ring| {
+            ring.as_array()?
+                .iter()
+                .map(|p| Some((p.get(0)?.as_f64()?, p.get(1)?.as_f64()?)))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GEOJSON: &str = r#"{
+        "type": "FeatureCollection",
+        "features": [
+            { "type": "Feature", "properties": { "boro_name": "Ring" },
+              "geometry": { "type": "Polygon", "coordinates": [
+                  [[0, 0], [4, 0], [4, 4], [0, 4], [0, 0]],
+                  [[1, 1], [3, 1], [3, 3], [1, 3], [1, 1]]
+              ] } },
+            { "type": "Feature", "properties": { "boro_name": "Islands" },
+              "geometry": { "type": "MultiPolygon", "coordinates": [
+                  [[[10, 0], [11, 0], [11, 1], [10, 1], [10, 0]]],
+                  [[[12, 0], [13, 0], [13, 1], [12, 1], [12, 0]]]
+              ] } },
+            { "type": "Feature", "properties": { "boro_name": "Pin" },
+              "geometry": { "type": "Point", "coordinates": [2, 2] } }
+        ]
+    }"#;
+
+    #[test]
+    fn point_in_polygon_with_holes_and_multipolygons() {
+        let b = Boundaries::from_geojson(GEOJSON, &["BoroName", "boro_name"]).unwrap();
+        assert_eq!(b.regions.len(), 2);
+        // (lat, lon)
+        assert_eq!(b.locate(0.5, 0.5), Some("Ring"));
+        assert_eq!(b.locate(2.0, 2.0), None, "inside the hole");
+        assert_eq!(b.locate(0.5, 12.5), Some("Islands"));
+        assert_eq!(b.locate(0.5, 11.5), None, "between the islands");
+        assert_eq!(b.locate(-1.0, 2.0), None);
+    }
+
+    #[test]
+    fn missing_name_property_is_an_error() {
+        assert!(Boundaries::from_geojson(GEOJSON, &["zipcode"]).is_err());
+    }
+}
@@ -13,10 +13,25 @@ use tiberius::{AuthMethod, Client, Config};
 use tokio::net::TcpStream;
 use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
 
+use crate::config::PersonAgeConfig;
 use crate::data_mart::{
-    contributing_factor::ContributingFactorDim, fact::Fact, person_age::PersonAge,
-    person_position::PersonPosition, person_role::PersonPositionRole, person_sex::PersonSex,
-    person_type::PersonType, time::Time as DmTime,
+    bodily_injury::BodilyInjuryDim,
+    contributing_factor::ContributingFactorDim,
+    fact::Fact,
+    fact_crash::FactCrash,
+    factor_group::{FactorGroup, FactorGroupBridge},
+    location::LocationDim,
+    pedestrian_action::PedestrianActionDim,
+    person_age::PersonAge,
+    person_injury::PersonInjuryDim,
+    person_position::PersonPosition,
+    person_role::PersonPositionRole,
+    person_sex::PersonSex,
+    person_type::PersonType,
+    safety_equipment::SafetyEquipmentDim,
+    severity::SeverityDim,
+    time::Time as DmTime,
+    vehicle_type::VehicleTypeDim,
 };
 
 // ---------------------------------------------------------------------------
@@ -60,7 +75,17 @@ pub enum DataMartTable {
     DimPersonSex,
     DimPersonType,
     DimContributingFactor,
+    DimVehicleType,
+    DimLocation,
+    DimPersonInjury,
+    DimBodilyInjury,
+    DimSafetyEquipment,
+    DimPedestrianAction,
+    DimSeverity,
+    DimFactorGroup,
+    BridgeFactorGroup,
     Fact,
+    FactCrash,
 }
 
 // ---------------------------------------------------------------------------
@@ -131,7 +156,9 @@ async fn exec(client: &mut Client<Compat<TcpStream>>, sql: &str) -> Result<()> {
 ///
 /// The function is *idempotent*: re-running it against a database that already
 /// has all objects is safe (existing-object errors are swallowed).
-pub async fn setup_data_mart(creds: &DbCredentials) -> Result<()> {
+///
+/// `DimPersonAge` gets a column per hierarchy of `person_ages`.
+pub async fn setup_data_mart(creds: &DbCredentials, person_ages: &PersonAgeConfig) -> Result<()> {
     let mut client = connect(creds).await?;
 
     // -- Schema --------------------------------------------------------------
@@ -149,13 +176,38 @@ pub async fn setup_data_mart(creds: &DbCredentials) -> Result<()> {
         &mut client,
         &format!(
             "CREATE TABLE [{SCHEMA}].[DimTime] (
-                time_id             INT           NOT NULL,
-                [timestamp]         DATETIME      NOT NULL,
-                hier_def_day        DATE          NOT NULL,
-                hier_def_month      VARCHAR(12)   NOT NULL,
-                hier_def_year       SMALLINT      NOT NULL,
-                hier_moon_phase     VARCHAR(20)   NOT NULL,
-                weather             VARCHAR(20)   NOT NULL,
+                time_id               INT           NOT NULL,
+                [timestamp]           DATETIME      NOT NULL,
+                utc_offset_minutes    SMALLINT      NOT NULL,
+                dst_flag              VARCHAR(15)   NOT NULL,
+                hier_def_day          DATE          NOT NULL,
+                hier_def_month        VARCHAR(12)   NOT NULL,
+                hier_def_month_number TINYINT       NOT NULL,
+                hier_def_quarter      TINYINT       NOT NULL,
+                hier_def_year         SMALLINT      NOT NULL,
+                hour_of_day           TINYINT       NOT NULL,
+                day_of_week           VARCHAR(9)    NOT NULL,
+                day_of_week_number    TINYINT       NOT NULL,
+                iso_week              TINYINT       NOT NULL,
+                iso_week_year         SMALLINT      NOT NULL,
+                is_weekend            BIT           NOT NULL,
+                is_rush_hour          BIT           NOT NULL,
+                season                VARCHAR(6)    NOT NULL,
+                holiday               VARCHAR(25)   NOT NULL,
+                is_holiday            BIT           NOT NULL,
+                hier_moon_phase       VARCHAR(20)   NOT NULL,
+                moon_illumination     DECIMAL(4,3)  NOT NULL,
+                moon_age_days         DECIMAL(4,2)  NOT NULL,
+                light_condition       VARCHAR(14)   NOT NULL,
+                sun_altitude          DECIMAL(5,2)  NOT NULL,
+                weather               VARCHAR(20)   NOT NULL,
+                weather_rule_id       VARCHAR(40)   NULL,
+                snow_risk             VARCHAR(13)   NOT NULL,
+                temperature_c         DECIMAL(5,2)  NULL,
+                precipitation_mm      DECIMAL(5,2)  NULL,
+                rain_mm               DECIMAL(5,2)  NULL,
+                cloudcover_pct        DECIMAL(5,2)  NULL,
+                windspeed_kmh         DECIMAL(5,2)  NULL,
                 CONSTRAINT PK_DimTime PRIMARY KEY CLUSTERED (time_id)
             )"
         ),
@@ -182,6 +234,17 @@ pub async fn setup_data_mart(creds: &DbCredentials) -> Result<()> {
     .await?;
 
     // -- DimPersonAge --------------------------------------------------------
+    let age_hier_columns: String = person_ages
+        .hierarchies
+        .iter()
+        .map(|h| {
+            format!(
+                "\n                {:<27} VARCHAR({}) NOT NULL,",
+                h.column(),
+                PersonAgeConfig::MAX_LABEL_LEN
+            )
+        })
+        .collect();
     exec(
         &mut client,
         &format!(
@@ -189,7 +252,7 @@ pub async fn setup_data_mart(creds: &DbCredentials) -> Result<()> {
                 person_age_id               INT         NOT NULL,
                 person_age                  TINYINT     NOT NULL,
                 person_age_known            BIT         NOT NULL,
-                person_age_hier_def_group   VARCHAR(12) NOT NULL,
+                person_age_hier_def_group   VARCHAR(12) NOT NULL,{age_hier_columns}
                 CONSTRAINT PK_DimPersonAge PRIMARY KEY CLUSTERED (person_age_id)
             )"
         ),
@@ -263,27 +326,185 @@ pub async fn setup_data_mart(creds: &DbCredentials) -> Result<()> {
     )
     .await?;
 
+    // -- DimVehicleType ------------------------------------------------------
+    exec(
+        &mut client,
+        &format!(
+            "CREATE TABLE [{SCHEMA}].[DimVehicleType] (
+                vehicle_type_id                 INT         NOT NULL,
+                vehicle_type                    VARCHAR(20) NOT NULL,
+                vehicle_type_hier_def_category  VARCHAR(15) NOT NULL,
+                CONSTRAINT PK_DimVehicleType PRIMARY KEY CLUSTERED (vehicle_type_id)
+            )"
+        ),
+    )
+    .await?;
+
+    // -- DimPersonInjury (junk) ----------------------------------------------
+    exec(
+        &mut client,
+        &format!(
+            "CREATE TABLE [{SCHEMA}].[DimPersonInjury] (
+                person_injury_id                INT         NOT NULL,
+                person_injury_severity          VARCHAR(15) NOT NULL,
+                person_injury_ejection          VARCHAR(20) NOT NULL,
+                person_injury_emotional_status  VARCHAR(15) NOT NULL,
+                CONSTRAINT PK_DimPersonInjury PRIMARY KEY CLUSTERED (person_injury_id)
+            )"
+        ),
+    )
+    .await?;
+
+    // -- DimBodilyInjury -----------------------------------------------------
+    exec(
+        &mut client,
+        &format!(
+            "CREATE TABLE [{SCHEMA}].[DimBodilyInjury] (
+                bodily_injury_id               INT         NOT NULL,
+                bodily_injury                  VARCHAR(25) NOT NULL,
+                bodily_injury_hier_def_region  VARCHAR(20) NOT NULL,
+                CONSTRAINT PK_DimBodilyInjury PRIMARY KEY CLUSTERED (bodily_injury_id)
+            )"
+        ),
+    )
+    .await?;
+
+    // -- DimSafetyEquipment --------------------------------------------------
+    exec(
+        &mut client,
+        &format!(
+            "CREATE TABLE [{SCHEMA}].[DimSafetyEquipment] (
+                safety_equipment_id                 INT         NOT NULL,
+                safety_equipment                    VARCHAR(25) NOT NULL,
+                safety_equipment_hier_def_category  VARCHAR(20) NOT NULL,
+                CONSTRAINT PK_DimSafetyEquipment PRIMARY KEY CLUSTERED (safety_equipment_id)
+            )"
+        ),
+    )
+    .await?;
+
+    // -- DimPedestrianAction (junk) ------------------------------------------
+    exec(
+        &mut client,
+        &format!(
+            "CREATE TABLE [{SCHEMA}].[DimPedestrianAction] (
+                pedestrian_action_id  INT         NOT NULL,
+                pedestrian_location   VARCHAR(20) NOT NULL,
+                pedestrian_action     VARCHAR(40) NOT NULL,
+                CONSTRAINT PK_DimPedestrianAction PRIMARY KEY CLUSTERED (pedestrian_action_id)
+            )"
+        ),
+    )
+    .await?;
+
+    // -- DimSeverity ---------------------------------------------------------
+    exec(
+        &mut client,
+        &format!(
+            "CREATE TABLE [{SCHEMA}].[DimSeverity] (
+                severity_id    INT         NOT NULL,
+                severity       VARCHAR(20) NOT NULL,
+                severity_rank  TINYINT     NOT NULL,
+                CONSTRAINT PK_DimSeverity PRIMARY KEY CLUSTERED (severity_id)
+            )"
+        ),
+    )
+    .await?;
+
+    // -- DimLocation ---------------------------------------------------------
+    exec(
+        &mut client,
+        &format!(
+            "CREATE TABLE [{SCHEMA}].[DimLocation] (
+                location_id                  INT          NOT NULL,
+                location_hier_def_borough    VARCHAR(15)  NOT NULL,
+                location_borough_source      VARCHAR(10)  NOT NULL,
+                location_hier_def_zip_code   VARCHAR(7)   NOT NULL,
+                location_zip_code_source     VARCHAR(10)  NOT NULL,
+                location_hier_def_grid_cell  VARCHAR(15)  NOT NULL,
+                grid_cell_latitude           DECIMAL(7,5) NULL,
+                grid_cell_longitude          DECIMAL(8,5) NULL,
+                CONSTRAINT PK_DimLocation PRIMARY KEY CLUSTERED (location_id)
+            )"
+        ),
+    )
+    .await?;
+
+    // -- DimFactorGroup / BridgeFactorGroup -----------------------------------
+    exec(
+        &mut client,
+        &format!(
+            "CREATE TABLE [{SCHEMA}].[DimFactorGroup] (
+                factor_group_id     INT          NOT NULL,
+                factor_count        TINYINT      NOT NULL,
+                factor_group_label  VARCHAR(320) NOT NULL,
+                CONSTRAINT PK_DimFactorGroup PRIMARY KEY CLUSTERED (factor_group_id)
+            )"
+        ),
+    )
+    .await?;
+
+    exec(
+        &mut client,
+        &format!(
+            "CREATE TABLE [{SCHEMA}].[BridgeFactorGroup] (
+                factor_group_id         INT          NOT NULL,
+                contributing_factor_id  INT          NOT NULL,
+                weighting_factor        DECIMAL(5,4) NOT NULL,
+                CONSTRAINT PK_BridgeFactorGroup
+                    PRIMARY KEY CLUSTERED (factor_group_id, contributing_factor_id),
+                CONSTRAINT FK_BridgeFactorGroup_FactorGroup
+                    FOREIGN KEY (factor_group_id)
+                    REFERENCES [{SCHEMA}].[DimFactorGroup] (factor_group_id),
+                CONSTRAINT FK_BridgeFactorGroup_ContributingFactor
+                    FOREIGN KEY (contributing_factor_id)
+                    REFERENCES [{SCHEMA}].[DimContributingFactor] (contributing_factor_id)
+            )"
+        ),
+    )
+    .await?;
+
+    exec(
+        &mut client,
+        &format!(
+            "CREATE INDEX IX_BridgeFactorGroup_ContributingFactor \
+             ON [{SCHEMA}].[BridgeFactorGroup] (contributing_factor_id) \
+             INCLUDE (weighting_factor)"
+        ),
+    )
+    .await?;
+
     // -- Fact ----------------------------------------------------------------
     exec(
         &mut client,
         &format!(
             "CREATE TABLE [{SCHEMA}].[Fact] (
                 fact_id                 INT     NOT NULL,
+                bodily_injury_id        INT     NOT NULL,
                 contributing_factor_id  INT     NOT NULL,
+                factor_group_id         INT     NOT NULL,
+                location_id             INT     NOT NULL,
+                pedestrian_action_id    INT     NOT NULL,
                 person_age_id           INT     NOT NULL,
+                person_injury_id        INT     NOT NULL,
                 person_position_id      INT     NOT NULL,
                 person_role_id          INT     NOT NULL,
                 person_sex_id           INT     NOT NULL,
                 person_type_id          INT     NOT NULL,
+                safety_equipment_id     INT     NOT NULL,
+                severity_id             INT     NOT NULL,
                 time_id                 INT     NOT NULL,
-                persons_injured         TINYINT NOT NULL,
-                persons_killed          TINYINT NOT NULL,
-                pedestrians_injured     TINYINT NOT NULL,
-                pedestrians_killed      TINYINT NOT NULL,
-                cyclist_injured         TINYINT NOT NULL,
-                cyclist_killed          TINYINT NOT NULL,
-                motorist_injured        TINYINT NOT NULL,
-                motorist_killed         TINYINT NOT NULL,
+                vehicle_type_id         INT     NOT NULL,
+                is_injured              BIT     NOT NULL,
+                is_killed               BIT     NOT NULL,
+                persons_injured         INT     NOT NULL,
+                persons_killed          INT     NOT NULL,
+                pedestrians_injured     INT     NOT NULL,
+                pedestrians_killed      INT     NOT NULL,
+                cyclist_injured         INT     NOT NULL,
+                cyclist_killed          INT     NOT NULL,
+                motorist_injured        INT     NOT NULL,
+                motorist_killed         INT     NOT NULL,
                 CONSTRAINT PK_Fact PRIMARY KEY NONCLUSTERED (fact_id),
                 CONSTRAINT FK_Fact_Time
                     FOREIGN KEY (time_id)
@@ -305,7 +526,31 @@ pub async fn setup_data_mart(creds: &DbCredentials) -> Result<()> {
                     REFERENCES [{SCHEMA}].[DimPersonType] (person_type_id),
                 CONSTRAINT FK_Fact_ContributingFactor
                     FOREIGN KEY (contributing_factor_id)
-                    REFERENCES [{SCHEMA}].[DimContributingFactor] (contributing_factor_id)
+                    REFERENCES [{SCHEMA}].[DimContributingFactor] (contributing_factor_id),
+                CONSTRAINT FK_Fact_FactorGroup
+                    FOREIGN KEY (factor_group_id)
+                    REFERENCES [{SCHEMA}].[DimFactorGroup] (factor_group_id),
+                CONSTRAINT FK_Fact_VehicleType
+                    FOREIGN KEY (vehicle_type_id)
+                    REFERENCES [{SCHEMA}].[DimVehicleType] (vehicle_type_id),
+                CONSTRAINT FK_Fact_Location
+                    FOREIGN KEY (location_id)
+                    REFERENCES [{SCHEMA}].[DimLocation] (location_id),
+                CONSTRAINT FK_Fact_PersonInjury
+                    FOREIGN KEY (person_injury_id)
+                    REFERENCES [{SCHEMA}].[DimPersonInjury] (person_injury_id),
+                CONSTRAINT FK_Fact_BodilyInjury
+                    FOREIGN KEY (bodily_injury_id)
+                    REFERENCES [{SCHEMA}].[DimBodilyInjury] (bodily_injury_id),
+                CONSTRAINT FK_Fact_SafetyEquipment
+                    FOREIGN KEY (safety_equipment_id)
+                    REFERENCES [{SCHEMA}].[DimSafetyEquipment] (safety_equipment_id),
+                CONSTRAINT FK_Fact_PedestrianAction
+                    FOREIGN KEY (pedestrian_action_id)
+                    REFERENCES [{SCHEMA}].[DimPedestrianAction] (pedestrian_action_id),
+                CONSTRAINT FK_Fact_Severity
+                    FOREIGN KEY (severity_id)
+                    REFERENCES [{SCHEMA}].[DimSeverity] (severity_id)
             )"
         ),
     )
@@ -321,6 +566,56 @@ pub async fn setup_data_mart(creds: &DbCredentials) -> Result<()> {
     )
     .await?;
 
+    // -- FactCrash -----------------------------------------------------------
+    exec(
+        &mut client,
+        &format!(
+            "CREATE TABLE [{SCHEMA}].[FactCrash] (
+                crash_id                INT     NOT NULL,
+                contributing_factor_id  INT     NOT NULL,
+                factor_group_id         INT     NOT NULL,
+                location_id             INT     NOT NULL,
+                severity_id             INT     NOT NULL,
+                time_id                 INT     NOT NULL,
+                persons_injured         INT     NOT NULL,
+                persons_killed          INT     NOT NULL,
+                pedestrians_injured     INT     NOT NULL,
+                pedestrians_killed      INT     NOT NULL,
+                cyclist_injured         INT     NOT NULL,
+                cyclist_killed          INT     NOT NULL,
+                motorist_injured        INT     NOT NULL,
+                motorist_killed         INT     NOT NULL,
+                severity_index          INT     NOT NULL,
+                CONSTRAINT PK_FactCrash PRIMARY KEY NONCLUSTERED (crash_id),
+                CONSTRAINT FK_FactCrash_Time
+                    FOREIGN KEY (time_id)
+                    REFERENCES [{SCHEMA}].[DimTime] (time_id),
+                CONSTRAINT FK_FactCrash_ContributingFactor
+                    FOREIGN KEY (contributing_factor_id)
+                    REFERENCES [{SCHEMA}].[DimContributingFactor] (contributing_factor_id),
+                CONSTRAINT FK_FactCrash_FactorGroup
+                    FOREIGN KEY (factor_group_id)
+                    REFERENCES [{SCHEMA}].[DimFactorGroup] (factor_group_id),
+                CONSTRAINT FK_FactCrash_Location
+                    FOREIGN KEY (location_id)
+                    REFERENCES [{SCHEMA}].[DimLocation] (location_id),
+                CONSTRAINT FK_FactCrash_Severity
+                    FOREIGN KEY (severity_id)
+                    REFERENCES [{SCHEMA}].[DimSeverity] (severity_id)
+            )"
+        ),
+    )
+    .await?;
+
+    exec(
+        &mut client,
+        &format!(
+            "CREATE CLUSTERED COLUMNSTORE INDEX CCI_FactCrash \
+             ON [{SCHEMA}].[FactCrash]"
+        ),
+    )
+    .await?;
+
     // -- Materialized / indexed view -----------------------------------------
     // The view body must be a single CREATE VIEW statement (no GO batch
     // separator inside a programmatic call).
@@ -336,14 +631,8 @@ pub async fn setup_data_mart(creds: &DbCredentials) -> Result<()> {
                  dcf.contributing_factor_hier_def_category       AS factor_category,
                  dps.person_sex                                  AS person_sex,
                  dpa.person_age_hier_def_group                   AS age_group,
-                 SUM(CAST(f.persons_injured     AS INT))         AS total_persons_injured,
-                 SUM(CAST(f.persons_killed      AS INT))         AS total_persons_killed,
-                 SUM(CAST(f.pedestrians_injured AS INT))         AS total_pedestrians_injured,
-                 SUM(CAST(f.pedestrians_killed  AS INT))         AS total_pedestrians_killed,
-                 SUM(CAST(f.cyclist_injured     AS INT))         AS total_cyclist_injured,
-                 SUM(CAST(f.cyclist_killed      AS INT))         AS total_cyclist_killed,
-                 SUM(CAST(f.motorist_injured    AS INT))         AS total_motorist_injured,
-                 SUM(CAST(f.motorist_killed     AS INT))         AS total_motorist_killed,
+                 SUM(CAST(f.is_injured          AS BIGINT))      AS injured_persons,
+                 SUM(CAST(f.is_killed           AS BIGINT))      AS killed_persons,
                  COUNT_BIG(*)                                    AS incident_count
              FROM [{SCHEMA}].[Fact]                  AS f
              JOIN [{SCHEMA}].[DimTime]               AS dt  ON dt.time_id                 = f.time_id
@@ -370,6 +659,49 @@ pub async fn setup_data_mart(creds: &DbCredentials) -> Result<()> {
     )
     .await?;
 
+    // Crash totals are aggregated at the crash grain, where every victim is
+    // counted once.
+    exec(
+        &mut client,
+        &format!(
+            "CREATE VIEW [{SCHEMA}].[MV_CrashSeverityByMoonWeatherFactor]
+             WITH SCHEMABINDING
+             AS
+             SELECT
+                 dt.hier_moon_phase                              AS moon_phase,
+                 dt.weather                                      AS weather,
+                 dcf.contributing_factor_hier_def_category       AS factor_category,
+                 SUM(CAST(fc.persons_injured     AS BIGINT))     AS total_persons_injured,
+                 SUM(CAST(fc.persons_killed      AS BIGINT))     AS total_persons_killed,
+                 SUM(CAST(fc.pedestrians_injured AS BIGINT))     AS total_pedestrians_injured,
+                 SUM(CAST(fc.pedestrians_killed  AS BIGINT))     AS total_pedestrians_killed,
+                 SUM(CAST(fc.cyclist_injured     AS BIGINT))     AS total_cyclist_injured,
+                 SUM(CAST(fc.cyclist_killed      AS BIGINT))     AS total_cyclist_killed,
+                 SUM(CAST(fc.motorist_injured    AS BIGINT))     AS total_motorist_injured,
+                 SUM(CAST(fc.motorist_killed     AS BIGINT))     AS total_motorist_killed,
+                 SUM(CAST(fc.severity_index      AS BIGINT))     AS total_severity_index,
+                 COUNT_BIG(*)                                    AS crash_count
+             FROM [{SCHEMA}].[FactCrash]             AS fc
+             JOIN [{SCHEMA}].[DimTime]               AS dt  ON dt.time_id                 = fc.time_id
+             JOIN [{SCHEMA}].[DimContributingFactor] AS dcf ON dcf.contributing_factor_id = fc.contributing_factor_id
+             GROUP BY
+                 dt.hier_moon_phase,
+                 dt.weather,
+                 dcf.contributing_factor_hier_def_category"
+        ),
+    )
+    .await?;
+
+    exec(
+        &mut client,
+        &format!(
+            "CREATE UNIQUE CLUSTERED INDEX UCI_MV_CrashSeverityByMoonWeatherFactor \
+             ON [{SCHEMA}].[MV_CrashSeverityByMoonWeatherFactor] \
+             (moon_phase, weather, factor_category)"
+        ),
+    )
+    .await?;
+
     println!("      DDL complete.");
     Ok(())
 }
@@ -382,7 +714,7 @@ pub async fn setup_data_mart(creds: &DbCredentials) -> Result<()> {
 ///
 /// MSSQL supports up to 1 000 rows per multi-row VALUES list (the 2 100-param
 /// limit is the binding constraint for wide tables, so we keep this
-/// conservative at 100 rows per flush for the fact table which has 16 columns,
+/// conservative at 100 rows per flush for the fact table which has 27 columns,
 /// and larger for the narrower dimension tables).
 const DIM_BATCH_SIZE: usize = 500;
 const FACT_BATCH_SIZE: usize = 100;
@@ -391,6 +723,15 @@ const FACT_BATCH_SIZE: usize = 100;
 // Enum → string helpers (SCREAMING_SNAKE_CASE, matching the DDL CHECK values)
 // ---------------------------------------------------------------------------
 
+fn dst_flag_str(f: crate::data_mart::time::DstFlag) -> &'static str {
+    use crate::data_mart::time::DstFlag::*;
+    match f {
+        Normal => "NORMAL",
+        Ambiguous => "AMBIGUOUS",
+        NonExistent => "NON_EXISTENT",
+    }
+}
+
 fn moon_phase_str(p: crate::data_mart::time::MoonPhase) -> &'static str {
     use crate::data_mart::time::MoonPhase::*;
     match p {
@@ -406,6 +747,15 @@ fn moon_phase_str(p: crate::data_mart::time::MoonPhase) -> &'static str {
     }
 }
 
+fn light_condition_str(v: crate::data_mart::time::LightCondition) -> &'static str {
+    use crate::data_mart::time::LightCondition::*;
+    match v {
+        Daylight => "DAYLIGHT",
+        CivilTwilight => "CIVIL_TWILIGHT",
+        Night => "NIGHT",
+    }
+}
+
 fn weather_str(w: crate::data_mart::time::Weather) -> &'static str {
     use crate::data_mart::time::Weather::*;
     match w {
@@ -420,6 +770,60 @@ fn weather_str(w: crate::data_mart::time::Weather) -> &'static str {
     }
 }
 
+fn snow_risk_str(v: crate::data_mart::time::SnowRisk) -> &'static str {
+    use crate::data_mart::time::SnowRisk::*;
+    match v {
+        NoRisk => "NO_RISK",
+        Frost => "FROST",
+        FreezingRain => "FREEZING_RAIN",
+        Snow => "SNOW",
+        Unknown => "UNKNOWN",
+    }
+}
+
+fn day_of_week_str(v: crate::data_mart::time::DayOfWeek) -> &'static str {
+    use crate::data_mart::time::DayOfWeek::*;
+    match v {
+        Monday => "MONDAY",
+        Tuesday => "TUESDAY",
+        Wednesday => "WEDNESDAY",
+        Thursday => "THURSDAY",
+        Friday => "FRIDAY",
+        Saturday => "SATURDAY",
+        Sunday => "SUNDAY",
+    }
+}
+
+fn season_str(v: crate::data_mart::time::Season) -> &'static str {
+    use crate::data_mart::time::Season::*;
+    match v {
+        Winter => "WINTER",
+        Spring => "SPRING",
+        Summer => "SUMMER",
+        Autumn => "AUTUMN",
+    }
+}
+
+fn holiday_str(v: crate::data_mart::time::Holiday) -> &'static str {
+    use crate::data_mart::time::Holiday::*;
+    match v {
+        NewYearsDay => "NEW_YEARS_DAY",
+        MartinLutherKingJrDay => "MARTIN_LUTHER_KING_JR_DAY",
+        LincolnsBirthday => "LINCOLNS_BIRTHDAY",
+        WashingtonsBirthday => "WASHINGTONS_BIRTHDAY",
+        MemorialDay => "MEMORIAL_DAY",
+        Juneteenth => "JUNETEENTH",
+        IndependenceDay => "INDEPENDENCE_DAY",
+        LaborDay => "LABOR_DAY",
+        ColumbusDay => "COLUMBUS_DAY",
+        ElectionDay => "ELECTION_DAY",
+        VeteransDay => "VETERANS_DAY",
+        Thanksgiving => "THANKSGIVING",
+        ChristmasDay => "CHRISTMAS_DAY",
+        NoHoliday => "NO_HOLIDAY",
+    }
+}
+
 fn age_group_str(g: crate::data_mart::person_age::PersonAgeGroup) -> &'static str {
     use crate::data_mart::person_age::PersonAgeGroup::*;
     match g {
@@ -477,6 +881,217 @@ fn person_type_str(t: crate::data_mart::person_type::PersonTypeType) -> &'static
     }
 }
 
+fn vehicle_type_str(t: crate::data_mart::vehicle_type::VehicleType) -> &'static str {
+    use crate::data_mart::vehicle_type::VehicleType::*;
+    match t {
+        Sedan => "SEDAN",
+        StationWagonSuv => "STATION_WAGON_SUV",
+        Convertible => "CONVERTIBLE",
+        Taxi => "TAXI",
+        Van => "VAN",
+        PickUpTruck => "PICK_UP_TRUCK",
+        BoxTruck => "BOX_TRUCK",
+        TractorTruck => "TRACTOR_TRUCK",
+        DumpTruck => "DUMP_TRUCK",
+        GarbageTruck => "GARBAGE_TRUCK",
+        TowTruck => "TOW_TRUCK",
+        Tanker => "TANKER",
+        FlatBed => "FLAT_BED",
+        OtherTruck => "OTHER_TRUCK",
+        Bus => "BUS",
+        Bicycle => "BICYCLE",
+        EBike => "E_BIKE",
+        EScooter => "E_SCOOTER",
+        Moped => "MOPED",
+        Motorcycle => "MOTORCYCLE",
+        Ambulance => "AMBULANCE",
+        FireTruck => "FIRE_TRUCK",
+        Pedicab => "PEDICAB",
+        Unknown => "UNKNOWN",
+    }
+}
+
+fn vehicle_category_str(c: crate::data_mart::vehicle_type::VehicleCategory) -> &'static str {
+    use crate::data_mart::vehicle_type::VehicleCategory::*;
+    match c {
+        PassengerCar => "PASSENGER_CAR",
+        Truck => "TRUCK",
+        TwoWheeler => "TWO_WHEELER",
+        Bus => "BUS",
+        Other => "OTHER",
+        Unknown => "UNKNOWN",
+    }
+}
+
+fn injury_severity_str(v: crate::data_mart::person_injury::InjurySeverity) -> &'static str {
+    use crate::data_mart::person_injury::InjurySeverity::*;
+    match v {
+        Killed => "KILLED",
+        Injured => "INJURED",
+        Unspecified => "UNSPECIFIED",
+        Unknown => "UNKNOWN",
+    }
+}
+
+fn ejection_str(v: crate::data_mart::person_injury::Ejection) -> &'static str {
+    use crate::data_mart::person_injury::Ejection::*;
+    match v {
+        NotEjected => "NOT_EJECTED",
+        PartiallyEjected => "PARTIALLY_EJECTED",
+        Ejected => "EJECTED",
+        Trapped => "TRAPPED",
+        Unknown => "UNKNOWN",
+    }
+}
+
+fn emotional_status_str(v: crate::data_mart::person_injury::EmotionalStatus) -> &'static str {
+    use crate::data_mart::person_injury::EmotionalStatus::*;
+    match v {
+        Conscious => "CONSCIOUS",
+        Shock => "SHOCK",
+        Incoherent => "INCOHERENT",
+        SemiConscious => "SEMI_CONSCIOUS",
+        Unconscious => "UNCONSCIOUS",
+        ApparentDeath => "APPARENT_DEATH",
+        Unknown => "UNKNOWN",
+    }
+}
+
+fn bodily_injury_str(v: crate::data_mart::bodily_injury::BodilyInjury) -> &'static str {
+    use crate::data_mart::bodily_injury::BodilyInjury::*;
+    match v {
+        Head => "HEAD",
+        Face => "FACE",
+        Eye => "EYE",
+        Neck => "NECK",
+        Chest => "CHEST",
+        Back => "BACK",
+        AbdomenPelvis => "ABDOMEN_PELVIS",
+        ShoulderUpperArm => "SHOULDER_UPPER_ARM",
+        ElbowLowerArmHand => "ELBOW_LOWER_ARM_HAND",
+        HipUpperLeg => "HIP_UPPER_LEG",
+        KneeLowerLegFoot => "KNEE_LOWER_LEG_FOOT",
+        EntireBody => "ENTIRE_BODY",
+        Unknown => "UNKNOWN",
+    }
+}
+
+fn body_region_str(v: crate::data_mart::bodily_injury::BodyRegion) -> &'static str {
+    use crate::data_mart::bodily_injury::BodyRegion::*;
+    match v {
+        HeadNeck => "HEAD_NECK",
+        Torso => "TORSO",
+        UpperExtremity => "UPPER_EXTREMITY",
+        LowerExtremity => "LOWER_EXTREMITY",
+        EntireBody => "ENTIRE_BODY",
+        Unknown => "UNKNOWN",
+    }
+}
+
+fn safety_equipment_str(v: crate::data_mart::safety_equipment::SafetyEquipment) -> &'static str {
+    use crate::data_mart::safety_equipment::SafetyEquipment::*;
+    match v {
+        NoEquipment => "NO_EQUIPMENT",
+        LapBelt => "LAP_BELT",
+        Harness => "HARNESS",
+        LapBeltHarness => "LAP_BELT_HARNESS",
+        ChildRestraint => "CHILD_RESTRAINT",
+        AirBag => "AIR_BAG",
+        AirBagLapBelt => "AIR_BAG_LAP_BELT",
+        AirBagLapBeltHarness => "AIR_BAG_LAP_BELT_HARNESS",
+        AirBagChildRestraint => "AIR_BAG_CHILD_RESTRAINT",
+        HelmetMotorcycle => "HELMET_MOTORCYCLE",
+        HelmetBicycle => "HELMET_BICYCLE",
+        HelmetOtherBicycle => "HELMET_OTHER_BICYCLE",
+        PadsBicycle => "PADS_BICYCLE",
+        StoppersBicycle => "STOPPERS_BICYCLE",
+        Other => "OTHER",
+        Unknown => "UNKNOWN",
+    }
+}
+
+fn safety_equipment_category_str(
+    v: crate::data_mart::safety_equipment::SafetyEquipmentCategory,
+) -> &'static str {
+    use crate::data_mart::safety_equipment::SafetyEquipmentCategory::*;
+    match v {
+        Restraint => "RESTRAINT",
+        AirBag => "AIR_BAG",
+        Helmet => "HELMET",
+        OtherProtection => "OTHER_PROTECTION",
+        NoEquipment => "NO_EQUIPMENT",
+        Unknown => "UNKNOWN",
+    }
+}
+
+fn pedestrian_location_str(
+    v: crate::data_mart::pedestrian_action::PedestrianLocation,
+) -> &'static str {
+    use crate::data_mart::pedestrian_action::PedestrianLocation::*;
+    match v {
+        AtIntersection => "AT_INTERSECTION",
+        NotAtIntersection => "NOT_AT_INTERSECTION",
+        Unknown => "UNKNOWN",
+    }
+}
+
+fn pedestrian_action_str(v: crate::data_mart::pedestrian_action::PedestrianAction) -> &'static str {
+    use crate::data_mart::pedestrian_action::PedestrianAction::*;
+    match v {
+        CrossingWithSignal => "CROSSING_WITH_SIGNAL",
+        CrossingAgainstSignal => "CROSSING_AGAINST_SIGNAL",
+        CrossingNoSignalMarkedCrosswalk => "CROSSING_NO_SIGNAL_MARKED_CROSSWALK",
+        CrossingNoSignalOrCrosswalk => "CROSSING_NO_SIGNAL_OR_CROSSWALK",
+        AlongHighwayWithTraffic => "ALONG_HIGHWAY_WITH_TRAFFIC",
+        AlongHighwayAgainstTraffic => "ALONG_HIGHWAY_AGAINST_TRAFFIC",
+        EmergingFromParkedVehicle => "EMERGING_FROM_PARKED_VEHICLE",
+        GoingToFromSchoolBus => "GOING_TO_FROM_SCHOOL_BUS",
+        GettingOnOffVehicle => "GETTING_ON_OFF_VEHICLE",
+        PushingWorkingOnCar => "PUSHING_WORKING_ON_CAR",
+        WorkingInRoadway => "WORKING_IN_ROADWAY",
+        PlayingInRoadway => "PLAYING_IN_ROADWAY",
+        OtherActionsInRoadway => "OTHER_ACTIONS_IN_ROADWAY",
+        NotInRoadway => "NOT_IN_ROADWAY",
+        Unknown => "UNKNOWN",
+    }
+}
+
+fn severity_str(v: crate::data_mart::severity::CrashSeverity) -> &'static str {
+    use crate::data_mart::severity::CrashSeverity::*;
+    match v {
+        Fatal => "FATAL",
+        Injury => "INJURY",
+        PropertyDamageOnly => "PROPERTY_DAMAGE_ONLY",
+        Unknown => "UNKNOWN",
+    }
+}
+
+fn borough_str(b: crate::data_mart::location::Borough) -> &'static str {
+    use crate::data_mart::location::Borough::*;
+    match b {
+        Bronx => "BRONX",
+        Brooklyn => "BROOKLYN",
+        Manhattan => "MANHATTAN",
+        Queens => "QUEENS",
+        StatenIsland => "STATEN_ISLAND",
+        Unknown => "UNKNOWN",
+    }
+}
+
+fn location_source_str(s: crate::data_mart::location::LocationSource) -> &'static str {
+    use crate::data_mart::location::LocationSource::*;
+    match s {
+        Reported => "REPORTED",
+        Inferred => "INFERRED",
+        Unknown => "UNKNOWN",
+    }
+}
+
+/// A nullable decimal literal.
+fn opt_decimal(v: Option<f64>, places: usize) -> String {
+    v.map_or_else(|| "NULL".into(), |v| format!("{v:.places$}"))
+}
+
 fn contributing_factor_str(
     f: crate::data_mart::contributing_factor::ContributingFactor,
 ) -> &'static str {
@@ -669,7 +1284,12 @@ async fn insert_dim_time(client: &mut Client<Compat<TcpStream>>, rows: &[DmTime]
     for (batch_idx, chunk) in rows.chunks(DIM_BATCH_SIZE).enumerate() {
         let mut sql = format!(
             "INSERT INTO [{SCHEMA}].[DimTime] \
-             (time_id,[timestamp],hier_def_day,hier_def_month,hier_def_year,hier_moon_phase,weather) VALUES "
+             (time_id,[timestamp],utc_offset_minutes,dst_flag,hier_def_day,hier_def_month,\
+              hier_def_month_number,hier_def_quarter,hier_def_year,hour_of_day,day_of_week,\
+              day_of_week_number,iso_week,iso_week_year,is_weekend,is_rush_hour,season,holiday,\
+              is_holiday,hier_moon_phase,moon_illumination,moon_age_days,light_condition,\
+              sun_altitude,weather,weather_rule_id,snow_risk,temperature_c,precipitation_mm,rain_mm,\
+              cloudcover_pct,windspeed_kmh) VALUES "
         );
 
         let values: Vec<String> = chunk
@@ -694,12 +1314,39 @@ async fn insert_dim_time(client: &mut Client<Compat<TcpStream>>, rows: &[DmTime]
                     day.day()
                 );
                 format!(
-                    "({},'{ts_str}','{day_str}','{}',{},\'{}\',\'{}\')",
+                    "({},'{ts_str}',{},'{}','{day_str}','{}',{},{},{},{},'{}',{},{},{},{},{},'{}','{}',{},\'{}\',{:.3},{:.2},'{}',{:.2},\'{}\',{},'{}',{},{},{},{},{})",
                     r.time_id,
+                    r.utc_offset_minutes,
+                    dst_flag_str(r.dst_flag),
                     r.hier_def_month,
+                    r.hier_def_month_number,
+                    r.hier_def_quarter,
                     r.hier_def_year,
+                    r.hour_of_day,
+                    day_of_week_str(r.day_of_week),
+                    r.day_of_week_number,
+                    r.iso_week,
+                    r.iso_week_year,
+                    u8::from(r.is_weekend),
+                    u8::from(r.is_rush_hour),
+                    season_str(r.season),
+                    holiday_str(r.holiday),
+                    u8::from(r.is_holiday),
                     moon_phase_str(r.hier_moon_phase),
+                    r.moon_illumination,
+                    r.moon_age_days,
+                    light_condition_str(r.light_condition),
+                    r.sun_altitude,
                     weather_str(r.weather),
+                    r.weather_rule_id
+                        .as_ref()
+                        .map_or_else(|| "NULL".into(), |id| format!("'{id}'")),
+                    snow_risk_str(r.snow_risk),
+                    opt_decimal(r.temperature_c, 2),
+                    opt_decimal(r.precipitation_mm, 2),
+                    opt_decimal(r.rain_mm, 2),
+                    opt_decimal(r.cloudcover_pct, 2),
+                    opt_decimal(r.windspeed_kmh, 2),
                 )
             })
             .collect();
@@ -726,16 +1373,24 @@ async fn insert_dim_person_age(
     );
 
     for (batch_idx, chunk) in rows.chunks(DIM_BATCH_SIZE).enumerate() {
+        let hier_columns: String = chunk[0]
+            .hier_bands
+            .keys()
+            .map(|c| format!(",{c}"))
+            .collect();
         let mut sql = format!(
             "INSERT INTO [{SCHEMA}].[DimPersonAge] \
-             (person_age_id,person_age,person_age_known,person_age_hier_def_group) VALUES "
+             (person_age_id,person_age,person_age_known,person_age_hier_def_group{hier_columns}) \
+             VALUES "
         );
 
         let values: Vec<String> = chunk
             .iter()
             .map(|r| {
+                // band labels are validated to contain no quotes
+                let hier_bands: String = r.hier_bands.values().map(|b| format!(",'{b}'")).collect();
                 format!(
-                    "({},{},{},'{}')",
+                    "({},{},{},'{}'{hier_bands})",
                     r.person_age_id,
                     r.person_age,
                     if r.person_age_known { 1 } else { 0 },
@@ -946,24 +1601,389 @@ async fn insert_dim_contributing_factor(
     Ok(())
 }
 
-async fn insert_fact(client: &mut Client<Compat<TcpStream>>, rows: &[Fact]) -> Result<()> {
+async fn insert_dim_vehicle_type(
+    client: &mut Client<Compat<TcpStream>>,
+    rows: &[VehicleTypeDim],
+) -> Result<()> {
     println!(
-        "      inserting Fact ({} rows, batch size {FACT_BATCH_SIZE})…",
+        "      inserting DimVehicleType ({} rows, batch size {DIM_BATCH_SIZE})…",
         rows.len()
     );
 
-    let total_batches = rows.chunks(FACT_BATCH_SIZE).count();
+    for (batch_idx, chunk) in rows.chunks(DIM_BATCH_SIZE).enumerate() {
+        let mut sql = format!(
+            "INSERT INTO [{SCHEMA}].[DimVehicleType] \
+             (vehicle_type_id,vehicle_type,vehicle_type_hier_def_category) VALUES "
+        );
 
-    for (batch_idx, chunk) in rows.chunks(FACT_BATCH_SIZE).enumerate() {
+        let values: Vec<String> = chunk
+            .iter()
+            .map(|r| {
+                format!(
+                    "({},\'{}\',\'{}\')",
+                    r.vehicle_type_id,
+                    vehicle_type_str(r.vehicle_type),
+                    vehicle_category_str(r.vehicle_type_hier_def_category),
+                )
+            })
+            .collect();
+
+        sql.push_str(&values.join(","));
+
+        client
+            .execute(sql.as_str(), &[])
+            .await
+            .with_context(|| format!("DimVehicleType batch {batch_idx}"))?;
+    }
+
+    println!("      DimVehicleType done.");
+    Ok(())
+}
+
+async fn insert_dim_person_injury(
+    client: &mut Client<Compat<TcpStream>>,
+    rows: &[PersonInjuryDim],
+) -> Result<()> {
+    println!(
+        "      inserting DimPersonInjury ({} rows, batch size {DIM_BATCH_SIZE})…",
+        rows.len()
+    );
+
+    for (batch_idx, chunk) in rows.chunks(DIM_BATCH_SIZE).enumerate() {
+        let mut sql = format!(
+            "INSERT INTO [{SCHEMA}].[DimPersonInjury] \
+             (person_injury_id,person_injury_severity,person_injury_ejection,person_injury_emotional_status) VALUES "
+        );
+
+        let values: Vec<String> = chunk
+            .iter()
+            .map(|r| {
+                format!(
+                    "({},\'{}\',\'{}\',\'{}\')",
+                    r.person_injury_id,
+                    injury_severity_str(r.person_injury_severity),
+                    ejection_str(r.person_injury_ejection),
+                    emotional_status_str(r.person_injury_emotional_status),
+                )
+            })
+            .collect();
+
+        sql.push_str(&values.join(","));
+
+        client
+            .execute(sql.as_str(), &[])
+            .await
+            .with_context(|| format!("DimPersonInjury batch {batch_idx}"))?;
+    }
+
+    println!("      DimPersonInjury done.");
+    Ok(())
+}
+
+async fn insert_dim_bodily_injury(
+    client: &mut Client<Compat<TcpStream>>,
+    rows: &[BodilyInjuryDim],
+) -> Result<()> {
+    println!(
+        "      inserting DimBodilyInjury ({} rows, batch size {DIM_BATCH_SIZE})…",
+        rows.len()
+    );
+
+    for (batch_idx, chunk) in rows.chunks(DIM_BATCH_SIZE).enumerate() {
+        let mut sql = format!(
+            "INSERT INTO [{SCHEMA}].[DimBodilyInjury] \
+             (bodily_injury_id,bodily_injury,bodily_injury_hier_def_region) VALUES "
+        );
+
+        let values: Vec<String> = chunk
+            .iter()
+            .map(|r| {
+                format!(
+                    "({},\'{}\',\'{}\')",
+                    r.bodily_injury_id,
+                    bodily_injury_str(r.bodily_injury),
+                    body_region_str(r.bodily_injury_hier_def_region),
+                )
+            })
+            .collect();
+
+        sql.push_str(&values.join(","));
+
+        client
+            .execute(sql.as_str(), &[])
+            .await
+            .with_context(|| format!("DimBodilyInjury batch {batch_idx}"))?;
+    }
+
+    println!("      DimBodilyInjury done.");
+    Ok(())
+}
+
+async fn insert_dim_safety_equipment(
+    client: &mut Client<Compat<TcpStream>>,
+    rows: &[SafetyEquipmentDim],
+) -> Result<()> {
+    println!(
+        "      inserting DimSafetyEquipment ({} rows, batch size {DIM_BATCH_SIZE})…",
+        rows.len()
+    );
+
+    for (batch_idx, chunk) in rows.chunks(DIM_BATCH_SIZE).enumerate() {
+        let mut sql = format!(
+            "INSERT INTO [{SCHEMA}].[DimSafetyEquipment] \
+             (safety_equipment_id,safety_equipment,safety_equipment_hier_def_category) VALUES "
+        );
+
+        let values: Vec<String> = chunk
+            .iter()
+            .map(|r| {
+                format!(
+                    "({},\'{}\',\'{}\')",
+                    r.safety_equipment_id,
+                    safety_equipment_str(r.safety_equipment),
+                    safety_equipment_category_str(r.safety_equipment_hier_def_category),
+                )
+            })
+            .collect();
+
+        sql.push_str(&values.join(","));
+
+        client
+            .execute(sql.as_str(), &[])
+            .await
+            .with_context(|| format!("DimSafetyEquipment batch {batch_idx}"))?;
+    }
+
+    println!("      DimSafetyEquipment done.");
+    Ok(())
+}
+
+async fn insert_dim_pedestrian_action(
+    client: &mut Client<Compat<TcpStream>>,
+    rows: &[PedestrianActionDim],
+) -> Result<()> {
+    println!(
+        "      inserting DimPedestrianAction ({} rows, batch size {DIM_BATCH_SIZE})…",
+        rows.len()
+    );
+
+    for (batch_idx, chunk) in rows.chunks(DIM_BATCH_SIZE).enumerate() {
+        let mut sql = format!(
+            "INSERT INTO [{SCHEMA}].[DimPedestrianAction] \
+             (pedestrian_action_id,pedestrian_location,pedestrian_action) VALUES "
+        );
+
+        let values: Vec<String> = chunk
+            .iter()
+            .map(|r| {
+                format!(
+                    "({},\'{}\',\'{}\')",
+                    r.pedestrian_action_id,
+                    pedestrian_location_str(r.pedestrian_location),
+                    pedestrian_action_str(r.pedestrian_action),
+                )
+            })
+            .collect();
+
+        sql.push_str(&values.join(","));
+
+        client
+            .execute(sql.as_str(), &[])
+            .await
+            .with_context(|| format!("DimPedestrianAction batch {batch_idx}"))?;
+    }
+
+    println!("      DimPedestrianAction done.");
+    Ok(())
+}
+
+async fn insert_dim_severity(
+    client: &mut Client<Compat<TcpStream>>,
+    rows: &[SeverityDim],
+) -> Result<()> {
+    println!(
+        "      inserting DimSeverity ({} rows, batch size {DIM_BATCH_SIZE})…",
+        rows.len()
+    );
+
+    for (batch_idx, chunk) in rows.chunks(DIM_BATCH_SIZE).enumerate() {
+        let mut sql = format!(
+            "INSERT INTO [{SCHEMA}].[DimSeverity] \
+             (severity_id,severity,severity_rank) VALUES "
+        );
+
+        let values: Vec<String> = chunk
+            .iter()
+            .map(|r| {
+                format!(
+                    "({},\'{}\',{})",
+                    r.severity_id,
+                    severity_str(r.severity),
+                    r.severity_rank,
+                )
+            })
+            .collect();
+
+        sql.push_str(&values.join(","));
+
+        client
+            .execute(sql.as_str(), &[])
+            .await
+            .with_context(|| format!("DimSeverity batch {batch_idx}"))?;
+    }
+
+    println!("      DimSeverity done.");
+    Ok(())
+}
+
+async fn insert_dim_location(
+    client: &mut Client<Compat<TcpStream>>,
+    rows: &[LocationDim],
+) -> Result<()> {
+    println!(
+        "      inserting DimLocation ({} rows, batch size {DIM_BATCH_SIZE})…",
+        rows.len()
+    );
+
+    for (batch_idx, chunk) in rows.chunks(DIM_BATCH_SIZE).enumerate() {
+        let mut sql = format!(
+            "INSERT INTO [{SCHEMA}].[DimLocation] \
+             (location_id,location_hier_def_borough,location_borough_source,location_hier_def_zip_code,\
+              location_zip_code_source,location_hier_def_grid_cell,grid_cell_latitude,grid_cell_longitude) VALUES "
+        );
+
+        let values: Vec<String> = chunk
+            .iter()
+            .map(|r| {
+                format!(
+                    "({},\'{}\',\'{}\',\'{}\',\'{}\',\'{}\',{},{})",
+                    r.location_id,
+                    borough_str(r.location_hier_def_borough),
+                    location_source_str(r.location_borough_source),
+                    r.location_hier_def_zip_code,
+                    location_source_str(r.location_zip_code_source),
+                    r.location_hier_def_grid_cell,
+                    opt_decimal(r.grid_cell_latitude, 5),
+                    opt_decimal(r.grid_cell_longitude, 5),
+                )
+            })
+            .collect();
+
+        sql.push_str(&values.join(","));
+
+        client
+            .execute(sql.as_str(), &[])
+            .await
+            .with_context(|| format!("DimLocation batch {batch_idx}"))?;
+    }
+
+    println!("      DimLocation done.");
+    Ok(())
+}
+
+async fn insert_dim_factor_group(
+    client: &mut Client<Compat<TcpStream>>,
+    rows: &[FactorGroup],
+) -> Result<()> {
+    println!(
+        "      inserting DimFactorGroup ({} rows, batch size {DIM_BATCH_SIZE})…",
+        rows.len()
+    );
+
+    for (batch_idx, chunk) in rows.chunks(DIM_BATCH_SIZE).enumerate() {
+        let mut sql = format!(
+            "INSERT INTO [{SCHEMA}].[DimFactorGroup] \
+             (factor_group_id,factor_count,factor_group_label) VALUES "
+        );
+
+        let values: Vec<String> = chunk
+            .iter()
+            .map(|r| {
+                format!(
+                    "({},{},\'{}\')",
+                    r.factor_group_id, r.factor_count, r.factor_group_label,
+                )
+            })
+            .collect();
+
+        sql.push_str(&values.join(","));
+
+        client
+            .execute(sql.as_str(), &[])
+            .await
+            .with_context(|| format!("DimFactorGroup batch {batch_idx}"))?;
+    }
+
+    println!("      DimFactorGroup done.");
+    Ok(())
+}
+
+async fn insert_bridge_factor_group(
+    client: &mut Client<Compat<TcpStream>>,
+    rows: &[FactorGroupBridge],
+) -> Result<()> {
+    println!(
+        "      inserting BridgeFactorGroup ({} rows, batch size {DIM_BATCH_SIZE})…",
+        rows.len()
+    );
+
+    for (batch_idx, chunk) in rows.chunks(DIM_BATCH_SIZE).enumerate() {
+        let mut sql = format!(
+            "INSERT INTO [{SCHEMA}].[BridgeFactorGroup] \
+             (factor_group_id,contributing_factor_id,weighting_factor) VALUES "
+        );
+
+        let values: Vec<String> = chunk
+            .iter()
+            .map(|r| {
+                format!(
+                    "({},{},{:.4})",
+                    r.factor_group_id, r.contributing_factor_id, r.weighting_factor,
+                )
+            })
+            .collect();
+
+        sql.push_str(&values.join(","));
+
+        client
+            .execute(sql.as_str(), &[])
+            .await
+            .with_context(|| format!("BridgeFactorGroup batch {batch_idx}"))?;
+    }
+
+    println!("      BridgeFactorGroup done.");
+    Ok(())
+}
+
+/// Streams the fact rows from the CSV written by the pipeline, so the fact
+/// table never has to be held in memory as a whole.
+async fn insert_fact(client: &mut Client<Compat<TcpStream>>, fact_csv: &str) -> Result<()> {
+    println!("      inserting Fact from {fact_csv} (batch size {FACT_BATCH_SIZE})…");
+
+    let mut rdr = csv::Reader::from_path(fact_csv)
+        .with_context(|| format!("opening fact rows in {fact_csv}"))?;
+    let mut rows = rdr.deserialize::<Fact>();
+    let mut chunk: Vec<Fact> = Vec::with_capacity(FACT_BATCH_SIZE);
+    let mut inserted = 0usize;
+
+    for batch_idx in 0.. {
+        chunk.clear();
+        for row in rows.by_ref().take(FACT_BATCH_SIZE) {
+            chunk.push(row.with_context(|| format!("reading fact row from {fact_csv}"))?);
+        }
+        if chunk.is_empty() {
+            break;
+        }
         if batch_idx % 500 == 0 {
-            println!("        batch {batch_idx}/{total_batches}…");
+            println!("        batch {batch_idx} ({inserted} rows inserted)…");
         }
 
         let mut sql = format!(
             "INSERT INTO [{SCHEMA}].[Fact] \
-             (fact_id,contributing_factor_id,person_age_id,person_position_id,\
-              person_role_id,person_sex_id,person_type_id,time_id,\
-              persons_injured,persons_killed,pedestrians_injured,pedestrians_killed,\
+             (fact_id,bodily_injury_id,contributing_factor_id,factor_group_id,location_id,\
+              pedestrian_action_id,person_age_id,person_injury_id,person_position_id,person_role_id,\
+              person_sex_id,person_type_id,safety_equipment_id,severity_id,time_id,vehicle_type_id,\
+              is_injured,is_killed,persons_injured,persons_killed,pedestrians_injured,pedestrians_killed,\
               cyclist_injured,cyclist_killed,motorist_injured,motorist_killed) VALUES "
         );
 
@@ -971,15 +1991,25 @@ async fn insert_fact(client: &mut Client<Compat<TcpStream>>, rows: &[Fact]) -> R
             .iter()
             .map(|r| {
                 format!(
-                    "({},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{})",
+                    "({},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{})",
                     r.fact_id,
+                    r.bodily_injury_id,
                     r.contributing_factor_id,
+                    r.factor_group_id,
+                    r.location_id,
+                    r.pedestrian_action_id,
                     r.person_age_id,
+                    r.person_injury_id,
                     r.person_position_id,
                     r.person_role_id,
                     r.person_sex_id,
                     r.person_type_id,
+                    r.safety_equipment_id,
+                    r.severity_id,
                     r.time_id,
+                    r.vehicle_type_id,
+                    u8::from(r.is_injured),
+                    u8::from(r.is_killed),
                     r.persons_injured,
                     r.persons_killed,
                     r.pedestrians_injured,
@@ -998,9 +2028,63 @@ async fn insert_fact(client: &mut Client<Compat<TcpStream>>, rows: &[Fact]) -> R
             .execute(sql.as_str(), &[])
             .await
             .with_context(|| format!("Fact batch {batch_idx}"))?;
+        inserted += chunk.len();
+    }
+
+    println!("      Fact done ({inserted} rows).");
+    Ok(())
+}
+
+async fn insert_fact_crash(
+    client: &mut Client<Compat<TcpStream>>,
+    rows: &[FactCrash],
+) -> Result<()> {
+    println!(
+        "      inserting FactCrash ({} rows, batch size {FACT_BATCH_SIZE})…",
+        rows.len()
+    );
+
+    for (batch_idx, chunk) in rows.chunks(FACT_BATCH_SIZE).enumerate() {
+        let mut sql = format!(
+            "INSERT INTO [{SCHEMA}].[FactCrash] \
+             (crash_id,contributing_factor_id,factor_group_id,location_id,severity_id,time_id,\
+              persons_injured,persons_killed,pedestrians_injured,pedestrians_killed,\
+              cyclist_injured,cyclist_killed,motorist_injured,motorist_killed,severity_index) VALUES "
+        );
+
+        let values: Vec<String> = chunk
+            .iter()
+            .map(|r| {
+                format!(
+                    "({},{},{},{},{},{},{},{},{},{},{},{},{},{},{})",
+                    r.crash_id,
+                    r.contributing_factor_id,
+                    r.factor_group_id,
+                    r.location_id,
+                    r.severity_id,
+                    r.time_id,
+                    r.persons_injured,
+                    r.persons_killed,
+                    r.pedestrians_injured,
+                    r.pedestrians_killed,
+                    r.cyclist_injured,
+                    r.cyclist_killed,
+                    r.motorist_injured,
+                    r.motorist_killed,
+                    r.severity_index,
+                )
+            })
+            .collect();
+
+        sql.push_str(&values.join(","));
+
+        client
+            .execute(sql.as_str(), &[])
+            .await
+            .with_context(|| format!("FactCrash batch {batch_idx}"))?;
     }
 
-    println!("      Fact done.");
+    println!("      FactCrash done.");
     Ok(())
 }
 
@@ -1017,7 +2101,18 @@ pub struct DataMart<'a> {
     pub dim_person_sex: &'a [PersonSex],
     pub dim_person_type: &'a [PersonType],
     pub dim_contributing_factor: &'a [ContributingFactorDim],
-    pub fact: &'a [Fact],
+    pub dim_vehicle_type: &'a [VehicleTypeDim],
+    pub dim_location: &'a [LocationDim],
+    pub dim_person_injury: &'a [PersonInjuryDim],
+    pub dim_bodily_injury: &'a [BodilyInjuryDim],
+    pub dim_safety_equipment: &'a [SafetyEquipmentDim],
+    pub dim_pedestrian_action: &'a [PedestrianActionDim],
+    pub dim_severity: &'a [SeverityDim],
+    pub dim_factor_group: &'a [FactorGroup],
+    pub bridge_factor_group: &'a [FactorGroupBridge],
+    /// Path of the fact CSV written by the pipeline; streamed in batches.
+    pub fact_csv: &'a str,
+    pub fact_crash: &'a [FactCrash],
 }
 
 /// Insert the tables listed in `tables` into the database.
@@ -1065,10 +2160,41 @@ pub async fn ingest_data_mart(
     if wants(DataMartTable::DimContributingFactor) {
         insert_dim_contributing_factor(&mut client, data.dim_contributing_factor).await?;
     }
+    if wants(DataMartTable::DimVehicleType) {
+        insert_dim_vehicle_type(&mut client, data.dim_vehicle_type).await?;
+    }
+    if wants(DataMartTable::DimLocation) {
+        insert_dim_location(&mut client, data.dim_location).await?;
+    }
+    if wants(DataMartTable::DimPersonInjury) {
+        insert_dim_person_injury(&mut client, data.dim_person_injury).await?;
+    }
+    if wants(DataMartTable::DimBodilyInjury) {
+        insert_dim_bodily_injury(&mut client, data.dim_bodily_injury).await?;
+    }
+    if wants(DataMartTable::DimSafetyEquipment) {
+        insert_dim_safety_equipment(&mut client, data.dim_safety_equipment).await?;
+    }
+    if wants(DataMartTable::DimPedestrianAction) {
+        insert_dim_pedestrian_action(&mut client, data.dim_pedestrian_action).await?;
+    }
+    if wants(DataMartTable::DimSeverity) {
+        insert_dim_severity(&mut client, data.dim_severity).await?;
+    }
+    if wants(DataMartTable::DimFactorGroup) {
+        insert_dim_factor_group(&mut client, data.dim_factor_group).await?;
+    }
+    // The bridge references both DimFactorGroup and DimContributingFactor.
+    if wants(DataMartTable::BridgeFactorGroup) {
+        insert_bridge_factor_group(&mut client, data.bridge_factor_group).await?;
+    }
 
     // -- Fact table last -----------------------------------------------------
     if wants(DataMartTable::Fact) {
-        insert_fact(&mut client, data.fact).await?;
+        insert_fact(&mut client, data.fact_csv).await?;
+    }
+    if wants(DataMartTable::FactCrash) {
+        insert_fact_crash(&mut client, data.fact_crash).await?;
     }
 
     println!("      All selected tables ingested successfully.");
@@ -1,4 +1,9 @@
+pub mod astro;
 pub mod base_database;
+pub mod calendar;
+pub mod config;
 pub mod data_mart;
+pub mod data_quality;
+pub mod geo;
 pub mod ingestion;
 pub mod raw;
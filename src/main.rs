@@ -1,71 +1,237 @@
 use datawarehousing_example_nyc_vehicle_incidents::{
-    base_database::{crash::Crash, person::Person, time::Time as BdbTime},
+    base_database::{
+        crash::Crash,
+        location::{Geocoder, Provenance},
+        mapping::Mappings,
+        person::{Person, VehicleOrdinals},
+        time::{Time as BdbTime, cross_check_moon},
+        timezone,
+        vehicle::Vehicle,
+    },
+    config::Config,
     data_mart::{
-        contributing_factor::ContributingFactorDim, fact::Fact, person_age::PersonAge,
-        person_position::PersonPosition, person_role::PersonPositionRole, person_sex::PersonSex,
-        person_type::PersonType, time::Time as DmTime,
+        bodily_injury::BodilyInjuryDim,
+        contributing_factor::ContributingFactorDim,
+        fact::FactBuilder,
+        fact_crash::FactCrash,
+        factor_group::{FactorGroup, FactorGroupBridge},
+        location::LocationDim,
+        pedestrian_action::PedestrianActionDim,
+        person_age::PersonAge,
+        person_injury::PersonInjuryDim,
+        person_position::PersonPosition,
+        person_role::PersonPositionRole,
+        person_sex::PersonSex,
+        person_type::PersonType,
+        safety_equipment::SafetyEquipmentDim,
+        severity::SeverityDim,
+        time::Time as DmTime,
+        vehicle_type::VehicleTypeDim,
     },
+    data_quality::rejects::{RejectLocation, RejectReason, RejectSource, Rejects},
+    geo::Boundaries,
     ingestion::{DataMart, DataMartTable, DbCredentials},
     raw::{
-        crashes::RawCrashRecord, moon::RawMoonRecord, persons::RawPersonRecord,
-        weather::RawWeatherRecord,
+        ColumnAliases, RawLoadError, crashes::RawCrashRecord, moon::RawMoonRecord,
+        persons::RawPersonRecord, vehicles::RawVehicleRecord, weather::RawWeatherRecord,
     },
 };
-use std::fs;
+use itertools::{Itertools, MinMaxResult};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::{BufWriter, Write},
+    path::Path,
+};
+use time::{Date, PrimitiveDateTime};
 
 #[tokio::main]
 async fn main() {
+    let config = Config::load("config.json").unwrap_or_else(|e| {
+        eprintln!("ERROR: invalid configuration: {e:#}");
+        std::process::exit(1);
+    });
+    let mappings =
+        Mappings::load(config.mappings_dir.as_deref().map(Path::new)).unwrap_or_else(|e| {
+            eprintln!("ERROR: invalid mapping table: {e:#}");
+            std::process::exit(1);
+        });
+    let aliases = &config.column_aliases;
+
+    // Rows that are dropped or degraded are quarantined here instead of
+    // vanishing; start from an empty directory so stale files do not linger.
+    let _ = fs::remove_dir_all(REJECTS_DIR);
+    let mut rejects = Rejects::to_dir(REJECTS_DIR);
+
     // -----------------------------------------------------------------------
-    // Stage 1: Load raw data
+    // Stage 1: Load raw time sources
     // -----------------------------------------------------------------------
-    println!("[1/7] Loading raw data...");
-
-    let raw_moon = RawMoonRecord::load_from_csv("data/moon.csv");
-    println!("      moon records:    {}", raw_moon.len());
+    // Moon and weather are small (one row per day / hour) and are kept in
+    // memory; crashes and persons are streamed in the following stages.
+    // Moon phases are computed; the moon file is only used as a cross-check.
+    println!("[1/7] Loading raw moon and weather data...");
+
+    let raw_moon = if Path::new(MOON_CSV).exists() {
+        let raw_moon = load_or_exit(
+            RawMoonRecord::read_csv(MOON_CSV, &aliases.moon)
+                .and_then(|rows| rows.quarantined(&mut rejects)),
+        );
+        println!("      moon records:    {}", raw_moon.len());
+        raw_moon
+    } else {
+        println!("      moon records:    none ({MOON_CSV} not found, skipping cross-check)");
+        Vec::new()
+    };
 
-    let raw_weather = RawWeatherRecord::load_from_csv("data/weather.csv");
+    let raw_weather = load_or_exit(
+        RawWeatherRecord::read_csv("data/weather.csv", &aliases.weather)
+            .and_then(|rows| rows.quarantined(&mut rejects)),
+    );
     println!("      weather records: {}", raw_weather.len());
 
-    let raw_crashes = RawCrashRecord::load_from_csv("data/crashes.csv");
-    println!("      crash records:   {}", raw_crashes.len());
-
-    let raw_persons = RawPersonRecord::load_from_csv("data/persons.csv");
-    println!("      person records:  {}", raw_persons.len());
-
     // -----------------------------------------------------------------------
-    // Stage 2: Build base database
+    // Stage 2: Build time rows and the crash index
     // -----------------------------------------------------------------------
-    println!("[2/7] Building base database...");
+    println!("[2/7] Building base database times and crash index...");
+
+    let time_range = match config.time_range {
+        Some(range) => (range.start, range.end),
+        None => crash_date_range(CRASHES_CSV, &aliases.crashes).unwrap_or_else(|| {
+            eprintln!(
+                "      ERROR: {CRASHES_CSV} has no crash dates to derive the time range from."
+            );
+            eprintln!("      Set \"time_range\" in config.json and re-run.");
+            std::process::exit(1);
+        }),
+    };
+    println!("      time range:  {} – {}", time_range.0, time_range.1);
+
+    // Local hours for which weather was observed, to report crashes outside it.
+    let weather_hours: HashSet<PrimitiveDateTime> = raw_weather
+        .iter()
+        .map(|w| timezone::to_local(w.time))
+        .collect();
 
-    let bdb_times: Vec<BdbTime> = BdbTime::from(raw_weather, raw_moon);
+    let bdb_times: Vec<BdbTime> = BdbTime::from(
+        raw_weather,
+        time_range,
+        &config.moon_phases,
+        &config.weather_rules,
+    );
     println!("      time rows:   {}", bdb_times.len());
 
+    if !raw_moon.is_empty() {
+        let check = cross_check_moon(&bdb_times, &raw_moon);
+        println!(
+            "      moon cross-check: {} of {} events in {MOON_CSV} confirmed",
+            check.events - check.mismatches.len(),
+            check.events
+        );
+        for (date, phase) in &check.mismatches {
+            eprintln!("      WARNING: computed moon phase disagrees: {phase:?} on {date}");
+        }
+    }
+
     // Build a lookup from truncated-hour timestamp → time_id for crash linking.
-    let time_lookup: std::collections::HashMap<time::PrimitiveDateTime, u32> =
+    let time_lookup: HashMap<PrimitiveDateTime, u32> =
         bdb_times.iter().map(|t| (t.timestamp, t.time_id)).collect();
 
-    let bdb_crashes: Vec<Crash> = raw_crashes
-        .into_iter()
-        .map(|raw| {
-            let crash = Crash::from(raw);
-            // Truncate the crash timestamp to the hour to find the matching time row.
-            let hour_ts = crash
-                .crash_timestamp
-                .replace_minute(0)
-                .and_then(|t| t.replace_second(0))
-                .and_then(|t| t.replace_nanosecond(0))
-                .ok();
-            let time_id = hour_ts.and_then(|ts| time_lookup.get(&ts).copied());
-            match time_id {
-                Some(id) => crash.with_time_id(id),
-                None => crash,
+    // Boundary files are optional; without them missing boroughs and ZIP
+    // codes stay unknown.
+    let geocoder = Geocoder::new(
+        load_boundaries(BOROUGHS_GEOJSON, Geocoder::BOROUGH_PROPERTIES),
+        load_boundaries(ZIP_CODES_GEOJSON, Geocoder::ZIP_CODE_PROPERTIES),
+    );
+    if geocoder.is_empty() {
+        println!(
+            "      boundaries:  none ({BOROUGHS_GEOJSON} / {ZIP_CODES_GEOJSON} not found, \
+             skipping geocoding)"
+        );
+    }
+
+    let mut bdb_crashes: Vec<Crash> = Vec::new();
+    let mut crashes_without_weather = 0usize;
+    for row in load_or_exit(RawCrashRecord::read_csv(CRASHES_CSV, &aliases.crashes)) {
+        let Some(raw) = load_or_exit(row).quarantine(&mut rejects) else {
+            continue;
+        };
+        // Truncate the crash timestamp to the hour to find the matching time row.
+        let hour_ts = raw
+            .crash_time
+            .replace_minute(0)
+            .and_then(|t| t.replace_second(0))
+            .and_then(|t| t.replace_nanosecond(0))
+            .ok()
+            .map(|t| PrimitiveDateTime::new(raw.crash_date, t));
+        let time_id = hour_ts.and_then(|ts| time_lookup.get(&ts).copied());
+        if time_id.is_some() && !hour_ts.is_some_and(|ts| weather_hours.contains(&ts)) {
+            crashes_without_weather += 1;
+        }
+        if time_id.is_none() {
+            let value = format!("{} {}", raw.crash_date, raw.crash_time);
+            let at = RejectLocation {
+                line: raw.line,
+                column: "CRASH DATE",
+                value: &value,
+            };
+            rejects.reject_record(RejectSource::Crashes, RejectReason::NoTimeMember, at, &raw);
+        }
+        let crash = Crash::from_raw(
+            raw,
+            &geocoder,
+            &mappings,
+            &config.casualty_plausibility,
+            &mut rejects,
+        );
+        bdb_crashes.push(match time_id {
+            Some(id) => crash.with_time_id(id),
+            None => crash,
+        });
+    }
+    let crash_rows = bdb_crashes.len() as u64;
+    println!("      crash rows:  {crash_rows}");
+    if !geocoder.is_empty() {
+        let inferred = |source: fn(&Crash) -> Option<Provenance>| {
+            bdb_crashes
+                .iter()
+                .filter(|c| source(c) == Some(Provenance::Inferred))
+                .count()
+        };
+        println!(
+            "      geocoded:    {} boroughs, {} ZIP codes inferred from coordinates",
+            inferred(|c| c.location.borough_source),
+            inferred(|c| c.location.zip_code_source)
+        );
+    }
+    if crashes_without_weather > 0 {
+        let coverage = weather_hours.iter().minmax();
+        eprintln!(
+            "      WARNING: {crashes_without_weather} crashes fall outside the weather coverage \
+             ({}); they get UNKNOWN weather",
+            match coverage {
+                MinMaxResult::MinMax(first, last) => format!("{} – {}", first.date(), last.date()),
+                MinMaxResult::OneElement(only) => only.date().to_string(),
+                MinMaxResult::NoElements => "no weather data".into(),
             }
-        })
-        .collect();
-    println!("      crash rows:  {}", bdb_crashes.len());
+        );
+    }
 
-    let bdb_persons: Vec<Person> = raw_persons.into_iter().map(Person::from).collect();
-    println!("      person rows: {}", bdb_persons.len());
+    // The vehicles file is optional: without it, vehicle types come from the
+    // VEHICLE TYPE CODE columns of the crashes.
+    let mut vehicles: Vec<Vehicle> = Vec::new();
+    if Path::new(VEHICLES_CSV).exists() {
+        for row in load_or_exit(RawVehicleRecord::read_csv(VEHICLES_CSV, &aliases.vehicles)) {
+            if let Some(raw) = load_or_exit(row).quarantine(&mut rejects) {
+                vehicles.push(Vehicle::from_raw(raw, &mappings, &mut rejects));
+            }
+        }
+    }
+    if vehicles.is_empty() {
+        println!("      vehicle rows: none ({VEHICLES_CSV} not found, using crash vehicle types)");
+    } else {
+        println!("      vehicle rows: {}", vehicles.len());
+    }
+    let vehicle_rows = vehicles.len() as u64;
 
     // -----------------------------------------------------------------------
     // Stage 3: Build data mart dimension tables
@@ -75,7 +241,7 @@ async fn main() {
     let dm_times: Vec<DmTime> = DmTime::gen_times(bdb_times);
     println!("      dim_time rows:               {}", dm_times.len());
 
-    let dim_ages: Vec<PersonAge> = PersonAge::gen_ages();
+    let dim_ages: Vec<PersonAge> = PersonAge::gen_ages(&config.person_ages);
     println!("      dim_person_age rows:          {}", dim_ages.len());
 
     let dim_positions: Vec<PersonPosition> = PersonPosition::gen_positions();
@@ -96,13 +262,78 @@ async fn main() {
     let dim_factors: Vec<ContributingFactorDim> = ContributingFactorDim::gen_factors();
     println!("      dim_contributing_factor rows: {}", dim_factors.len());
 
+    let dim_vehicle_types: Vec<VehicleTypeDim> = VehicleTypeDim::gen_vehicle_types();
+    println!(
+        "      dim_vehicle_type rows:        {}",
+        dim_vehicle_types.len()
+    );
+
+    let dim_locations: Vec<LocationDim> = LocationDim::gen_locations(&bdb_crashes);
+    println!(
+        "      dim_location rows:            {}",
+        dim_locations.len()
+    );
+
+    let dim_injuries: Vec<PersonInjuryDim> = PersonInjuryDim::gen_injuries();
+    println!("      dim_person_injury rows:       {}", dim_injuries.len());
+
+    let dim_bodily_injuries: Vec<BodilyInjuryDim> = BodilyInjuryDim::gen_bodily_injuries();
+    println!(
+        "      dim_bodily_injury rows:       {}",
+        dim_bodily_injuries.len()
+    );
+
+    let dim_safety_equipment: Vec<SafetyEquipmentDim> = SafetyEquipmentDim::gen_safety_equipment();
+    println!(
+        "      dim_safety_equipment rows:    {}",
+        dim_safety_equipment.len()
+    );
+
+    let dim_pedestrian_actions: Vec<PedestrianActionDim> =
+        PedestrianActionDim::gen_pedestrian_actions();
+    println!(
+        "      dim_pedestrian_action rows:   {}",
+        dim_pedestrian_actions.len()
+    );
+
+    let dim_severities: Vec<SeverityDim> = SeverityDim::gen_severities();
+    println!(
+        "      dim_severity rows:            {}",
+        dim_severities.len()
+    );
+
+    let (dim_factor_groups, bridge_factor_groups): (Vec<FactorGroup>, Vec<FactorGroupBridge>) =
+        FactorGroup::gen_groups(&bdb_crashes, &dim_factors);
+    println!(
+        "      dim_factor_group rows:        {}",
+        dim_factor_groups.len()
+    );
+    println!(
+        "      bridge_factor_group rows:     {}",
+        bridge_factor_groups.len()
+    );
+
+    // The crash-grain fact needs the crashes, which the person fact builder
+    // takes ownership of below.
+    let fact_crashes: Vec<FactCrash> = FactCrash::gen_crash_facts(
+        &bdb_crashes,
+        &dm_times,
+        &dim_factors,
+        &bridge_factor_groups,
+        &dim_locations,
+        &dim_severities,
+        config.severity,
+    );
+    println!("      fact_crash rows:              {}", fact_crashes.len());
+
     // -----------------------------------------------------------------------
-    // Stage 4: Build fact table
+    // Stage 4: Stream persons into the fact table
     // -----------------------------------------------------------------------
-    println!("[4/7] Building fact table...");
+    fs::create_dir_all("data/output").expect("failed to create data/output directory");
+
+    println!("[4/7] Streaming persons into the fact table...");
 
-    let facts: Vec<Fact> = Fact::gen_facts(
-        bdb_persons,
+    let fact_builder = FactBuilder::new(
         bdb_crashes,
         &dm_times,
         &dim_ages,
@@ -111,15 +342,64 @@ async fn main() {
         &dim_sexes,
         &dim_types,
         &dim_factors,
-    );
-    println!("      fact rows: {}", facts.len());
+        &bridge_factor_groups,
+        &dim_vehicle_types,
+        &dim_locations,
+        &dim_injuries,
+        &dim_bodily_injuries,
+        &dim_safety_equipment,
+        &dim_pedestrian_actions,
+        &dim_severities,
+    )
+    .with_severity_weights(config.severity);
+    // Without the vehicles file, vehicle positions are inferred from the
+    // vehicles the persons occupy.
+    let fact_builder = if vehicles.is_empty() {
+        fact_builder.with_vehicle_ordinals(vehicle_ordinals("data/persons.csv", &aliases.persons))
+    } else {
+        fact_builder.with_vehicles(vehicles)
+    };
+
+    let mut fact_csv = CsvSink::create(FACT_CSV);
+    let mut fact_json = JsonArraySink::create(FACT_JSON);
+    let (mut person_rows, mut fact_rows) = (0u32, 0u32);
+
+    for row in load_or_exit(RawPersonRecord::read_csv(
+        "data/persons.csv",
+        &aliases.persons,
+    )) {
+        let Some(raw) = load_or_exit(row).quarantine(&mut rejects) else {
+            continue;
+        };
+        if !fact_builder.contains_crash(raw.collision_id) {
+            let value = raw.collision_id.to_string();
+            let at = RejectLocation {
+                line: raw.line,
+                column: "COLLISION_ID",
+                value: &value,
+            };
+            rejects.reject_record(RejectSource::Persons, RejectReason::OrphanPerson, at, &raw);
+            continue;
+        }
+        let person = Person::from_raw(raw, &mappings, &config.age_plausibility, &mut rejects);
+        person_rows += 1;
+        // 1-indexed, 0 reserved as uninitialised sentinel
+        if let Some(fact) = fact_builder.build(person_rows, &person) {
+            fact_csv.push(&fact);
+            fact_json.push(&fact);
+            fact_rows += 1;
+        }
+    }
+    fact_csv.finish();
+    fact_json.finish();
+    println!("      crash index: {}", fact_builder.crash_count());
+    println!("      person rows: {person_rows}");
+    println!("      fact rows:   {fact_rows}");
 
     // -----------------------------------------------------------------------
     // Stage 5: Write output files
     // -----------------------------------------------------------------------
-    fs::create_dir_all("data/output").expect("failed to create data/output directory");
-
-    println!("[5/7] Writing JSON and CSV files to data/output/...");
+    println!("[5/7] Writing dimension JSON and CSV files to data/output/...");
 
     write_json("data/output/dim_time.json", &dm_times);
     write_json("data/output/dim_person_age.json", &dim_ages);
@@ -128,7 +408,24 @@ async fn main() {
     write_json("data/output/dim_person_sex.json", &dim_sexes);
     write_json("data/output/dim_person_type.json", &dim_types);
     write_json("data/output/dim_contributing_factor.json", &dim_factors);
-    write_json("data/output/fact.json", &facts);
+    write_json("data/output/dim_vehicle_type.json", &dim_vehicle_types);
+    write_json("data/output/dim_location.json", &dim_locations);
+    write_json("data/output/dim_person_injury.json", &dim_injuries);
+    write_json("data/output/dim_bodily_injury.json", &dim_bodily_injuries);
+    write_json(
+        "data/output/dim_safety_equipment.json",
+        &dim_safety_equipment,
+    );
+    write_json(
+        "data/output/dim_pedestrian_action.json",
+        &dim_pedestrian_actions,
+    );
+    write_json("data/output/dim_severity.json", &dim_severities);
+    write_json("data/output/dim_factor_group.json", &dim_factor_groups);
+    write_json(
+        "data/output/bridge_factor_group.json",
+        &bridge_factor_groups,
+    );
 
     // CSV — dim_time needs special treatment because `time::Date` and
     // `PrimitiveDateTime` do not serialise to a flat string by default.
@@ -151,26 +448,107 @@ async fn main() {
                         t.timestamp.minute(),
                         t.timestamp.second(),
                     ),
+                    utc_offset_minutes: t.utc_offset_minutes,
+                    dst_flag: t.dst_flag,
                     hier_def_day: t
                         .hier_def_day
                         .format(&date_fmt)
                         .expect("failed to format hier_def_day"),
                     hier_def_month: t.hier_def_month.clone(),
+                    hier_def_month_number: t.hier_def_month_number,
+                    hier_def_quarter: t.hier_def_quarter,
                     hier_def_year: t.hier_def_year,
+                    hour_of_day: t.hour_of_day,
+                    day_of_week: t.day_of_week,
+                    day_of_week_number: t.day_of_week_number,
+                    iso_week: t.iso_week,
+                    iso_week_year: t.iso_week_year,
+                    is_weekend: t.is_weekend,
+                    is_rush_hour: t.is_rush_hour,
+                    season: t.season,
+                    holiday: t.holiday,
+                    is_holiday: t.is_holiday,
                     hier_moon_phase: t.hier_moon_phase,
+                    moon_illumination: t.moon_illumination,
+                    moon_age_days: t.moon_age_days,
+                    light_condition: t.light_condition,
+                    sun_altitude: t.sun_altitude,
                     weather: t.weather,
+                    weather_rule_id: t.weather_rule_id.clone(),
+                    snow_risk: t.snow_risk,
+                    temperature_c: t.temperature_c,
+                    precipitation_mm: t.precipitation_mm,
+                    rain_mm: t.rain_mm,
+                    cloudcover_pct: t.cloudcover_pct,
+                    windspeed_kmh: t.windspeed_kmh,
                 }
             })
             .collect::<Vec<_>>()
             .as_slice(),
     );
-    write_csv("data/output/dim_person_age.csv", &dim_ages);
+    write_dim_person_age_csv("data/output/dim_person_age.csv", &dim_ages);
     write_csv("data/output/dim_person_position.csv", &dim_positions);
     write_csv("data/output/dim_person_role.csv", &dim_roles);
     write_csv("data/output/dim_person_sex.csv", &dim_sexes);
     write_csv("data/output/dim_person_type.csv", &dim_types);
     write_csv("data/output/dim_contributing_factor.csv", &dim_factors);
-    write_csv("data/output/fact.csv", &facts);
+    write_csv("data/output/dim_vehicle_type.csv", &dim_vehicle_types);
+    write_csv("data/output/dim_location.csv", &dim_locations);
+    write_csv("data/output/dim_person_injury.csv", &dim_injuries);
+    write_csv("data/output/dim_bodily_injury.csv", &dim_bodily_injuries);
+    write_csv(
+        "data/output/dim_safety_equipment.csv",
+        &dim_safety_equipment,
+    );
+    write_csv(
+        "data/output/dim_pedestrian_action.csv",
+        &dim_pedestrian_actions,
+    );
+    write_csv("data/output/dim_severity.csv", &dim_severities);
+    write_csv("data/output/dim_factor_group.csv", &dim_factor_groups);
+    write_csv("data/output/bridge_factor_group.csv", &bridge_factor_groups);
+    write_json("data/output/fact_crash.json", &fact_crashes);
+    write_csv("data/output/fact_crash.csv", &fact_crashes);
+
+    for path in rejects.flush() {
+        println!("      wrote {}", path.display());
+    }
+    println!("      rejected / degraded rows: {}", rejects.total());
+    for (source, reason, count) in rejects.summary() {
+        println!("        {:<8} {reason:<17} {count}", source.file_stem());
+    }
+
+    // Distinct unmapped category values, to notice new or misspelled ones.
+    let unmapped = rejects.unmapped().report(|source| match source {
+        RejectSource::Crashes => crash_rows,
+        RejectSource::Persons => u64::from(person_rows),
+        RejectSource::Vehicles => vehicle_rows,
+        RejectSource::Weather | RejectSource::Moon => 0,
+    });
+    write_csv("data/output/unmapped_values.csv", &unmapped);
+    let frequent: Vec<_> = unmapped
+        .iter()
+        .filter(|v| !v.explicit_unknown && v.share > config.unmapped_values.max_share)
+        .collect();
+    for v in &frequent {
+        eprintln!(
+            "      WARNING: unmapped {} {} {:?} in {} rows ({:.2}%)",
+            v.source,
+            v.column,
+            v.value,
+            v.count,
+            100.0 * v.share
+        );
+    }
+    if config.unmapped_values.strict && !frequent.is_empty() {
+        eprintln!(
+            "ERROR: {} unmapped values exceed {:.2}% of their rows; map them or raise \
+             unmapped_values.max_share in config.json",
+            frequent.len(),
+            100.0 * config.unmapped_values.max_share
+        );
+        std::process::exit(1);
+    }
 
     // -----------------------------------------------------------------------
     // Stage 6: Set up database schema (DDL)
@@ -179,8 +557,11 @@ async fn main() {
 
     let creds = db_credentials_from_env();
 
-    if let Err(e) =
-        datawarehousing_example_nyc_vehicle_incidents::ingestion::setup_data_mart(&creds).await
+    if let Err(e) = datawarehousing_example_nyc_vehicle_incidents::ingestion::setup_data_mart(
+        &creds,
+        &config.person_ages,
+    )
+    .await
     {
         eprintln!("      ERROR during DDL setup: {e:#}");
         eprintln!("      Skipping ingestion. Fix the error and re-run.");
@@ -204,7 +585,17 @@ async fn main() {
     //       DataMartTable::DimPersonSex,
     //       DataMartTable::DimPersonType,
     //       DataMartTable::DimContributingFactor,
+    //       DataMartTable::DimVehicleType,
+    //       DataMartTable::DimLocation,
+    //       DataMartTable::DimPersonInjury,
+    //       DataMartTable::DimBodilyInjury,
+    //       DataMartTable::DimSafetyEquipment,
+    //       DataMartTable::DimPedestrianAction,
+    //       DataMartTable::DimSeverity,
+    //       DataMartTable::DimFactorGroup,
+    //       DataMartTable::BridgeFactorGroup,
     //       DataMartTable::Fact,
+    //       DataMartTable::FactCrash,
     //   ]
     let tables_to_ingest: &[DataMartTable] = &[
         DataMartTable::DimTime,
@@ -214,7 +605,17 @@ async fn main() {
         DataMartTable::DimPersonSex,
         DataMartTable::DimPersonType,
         DataMartTable::DimContributingFactor,
+        DataMartTable::DimVehicleType,
+        DataMartTable::DimLocation,
+        DataMartTable::DimPersonInjury,
+        DataMartTable::DimBodilyInjury,
+        DataMartTable::DimSafetyEquipment,
+        DataMartTable::DimPedestrianAction,
+        DataMartTable::DimSeverity,
+        DataMartTable::DimFactorGroup,
+        DataMartTable::BridgeFactorGroup,
         DataMartTable::Fact,
+        DataMartTable::FactCrash,
     ];
 
     let data_mart = DataMart {
@@ -225,7 +626,17 @@ async fn main() {
         dim_person_sex: &dim_sexes,
         dim_person_type: &dim_types,
         dim_contributing_factor: &dim_factors,
-        fact: &facts,
+        dim_vehicle_type: &dim_vehicle_types,
+        dim_location: &dim_locations,
+        dim_person_injury: &dim_injuries,
+        dim_bodily_injury: &dim_bodily_injuries,
+        dim_safety_equipment: &dim_safety_equipment,
+        dim_pedestrian_action: &dim_pedestrian_actions,
+        dim_severity: &dim_severities,
+        dim_factor_group: &dim_factor_groups,
+        bridge_factor_group: &bridge_factor_groups,
+        fact_csv: FACT_CSV,
+        fact_crash: &fact_crashes,
     };
 
     if let Err(e) = datawarehousing_example_nyc_vehicle_incidents::ingestion::ingest_data_mart(
@@ -242,6 +653,25 @@ async fn main() {
     println!("Done.");
 }
 
+const REJECTS_DIR: &str = "data/output/rejects";
+const CRASHES_CSV: &str = "data/crashes.csv";
+const MOON_CSV: &str = "data/moon.csv";
+const VEHICLES_CSV: &str = "data/vehicles.csv";
+const BOROUGHS_GEOJSON: &str = "data/boroughs.geojson";
+const ZIP_CODES_GEOJSON: &str = "data/zip_codes.geojson";
+const FACT_CSV: &str = "data/output/fact.csv";
+const FACT_JSON: &str = "data/output/fact.json";
+
+/// Unwraps the result of opening or reading a raw file, or reports the failing file, line and
+/// column and terminates the run with a non-zero exit code.
+fn load_or_exit<T>(result: Result<T, RawLoadError>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("      ERROR while loading raw data: {e}");
+        eprintln!("      Fix the source file and re-run.");
+        std::process::exit(1);
+    })
+}
+
 fn write_json<T: serde::Serialize>(path: &str, data: &T) {
     let json = serde_json::to_string_pretty(data).expect("failed to serialize to JSON");
     fs::write(path, json).unwrap_or_else(|e| panic!("failed to write {path}: {e}"));
@@ -260,6 +690,161 @@ fn write_csv<T: serde::Serialize>(path: &str, rows: &[T]) {
     println!("      wrote {path}");
 }
 
+/// Writes `DimPersonAge`, whose configured hierarchy columns the `csv`
+/// serializer cannot flatten.
+fn write_dim_person_age_csv(path: &str, rows: &[PersonAge]) {
+    let mut wtr = csv::Writer::from_path(path)
+        .unwrap_or_else(|e| panic!("failed to create CSV writer for {path}: {e}"));
+    let hier_columns: Vec<&String> = rows
+        .first()
+        .map(|r| r.hier_bands.keys().collect())
+        .unwrap_or_default();
+    let header = [
+        "person_age_id",
+        "person_age",
+        "person_age_known",
+        "person_age_hier_def_group",
+    ]
+    .into_iter()
+    .chain(hier_columns.iter().map(|c| c.as_str()));
+    wtr.write_record(header)
+        .unwrap_or_else(|e| panic!("failed to write header to {path}: {e}"));
+    for r in rows {
+        let def_group = serde_json::to_value(r.person_age_hier_def_group)
+            .expect("PersonAgeGroup serializes to a string");
+        let record = [
+            r.person_age_id.to_string(),
+            r.person_age.to_string(),
+            r.person_age_known.to_string(),
+            def_group.as_str().unwrap_or_default().to_owned(),
+        ]
+        .into_iter()
+        .chain(hier_columns.iter().map(|c| r.hier_bands[*c].clone()));
+        wtr.write_record(record)
+            .unwrap_or_else(|e| panic!("failed to write row to {path}: {e}"));
+    }
+    wtr.flush()
+        .unwrap_or_else(|e| panic!("failed to flush CSV writer for {path}: {e}"));
+    println!("      wrote {path}");
+}
+
+/// First and last crash date in `path`, used as the time range when none is
+/// configured. Rows that do not parse are skipped here; they are quarantined
+/// when the file is read for real.
+fn crash_date_range(path: &str, aliases: &ColumnAliases) -> Option<(Date, Date)> {
+    let dates = load_or_exit(RawCrashRecord::read_csv(path, aliases))
+        .filter_map(|row| row.ok()?.parsed.ok())
+        .map(|raw| raw.crash_date);
+    match dates.minmax() {
+        MinMaxResult::MinMax(first, last) => Some((first, last)),
+        MinMaxResult::OneElement(only) => Some((only, only)),
+        MinMaxResult::NoElements => None,
+    }
+}
+
+/// The regions of the GeoJSON file at `path`, or `None` if it does not exist.
+fn load_boundaries(path: &str, name_properties: &[&str]) -> Option<Boundaries> {
+    if !Path::new(path).exists() {
+        return None;
+    }
+    let boundaries = Boundaries::load(path, name_properties).unwrap_or_else(|e| {
+        eprintln!("ERROR: {e:#}");
+        std::process::exit(1);
+    });
+    println!(
+        "      boundaries:  {} regions from {path}",
+        boundaries.regions.len()
+    );
+    Some(boundaries)
+}
+
+/// Vehicle positions within each crash, taken from the `VEHICLE_ID`s of the
+/// persons in `path`. Like [`crash_date_range`], a pre-pass that skips rows
+/// which do not parse.
+fn vehicle_ordinals(path: &str, aliases: &ColumnAliases) -> VehicleOrdinals {
+    let mut ordinals = VehicleOrdinals::default();
+    for raw in load_or_exit(RawPersonRecord::read_csv(path, aliases))
+        .filter_map(|row| row.ok()?.parsed.ok())
+    {
+        if let Some(vehicle_id) = raw.vehicle_id {
+            ordinals.insert(raw.collision_id, vehicle_id);
+        }
+    }
+    ordinals
+}
+
+/// A CSV file that is written row by row while the pipeline streams.
+struct CsvSink {
+    path: &'static str,
+    wtr: csv::Writer<fs::File>,
+}
+
+impl CsvSink {
+    fn create(path: &'static str) -> Self {
+        let wtr = csv::Writer::from_path(path)
+            .unwrap_or_else(|e| panic!("failed to create CSV writer for {path}: {e}"));
+        Self { path, wtr }
+    }
+
+    fn push<T: serde::Serialize>(&mut self, row: &T) {
+        let path = self.path;
+        self.wtr
+            .serialize(row)
+            .unwrap_or_else(|e| panic!("failed to serialize row to {path}: {e}"));
+    }
+
+    fn finish(mut self) {
+        let path = self.path;
+        self.wtr
+            .flush()
+            .unwrap_or_else(|e| panic!("failed to flush CSV writer for {path}: {e}"));
+        println!("      wrote {path}");
+    }
+}
+
+/// A JSON array that is written element by element, one object per line.
+struct JsonArraySink {
+    path: &'static str,
+    out: BufWriter<fs::File>,
+    empty: bool,
+}
+
+impl JsonArraySink {
+    fn create(path: &'static str) -> Self {
+        let mut out = BufWriter::new(
+            fs::File::create(path).unwrap_or_else(|e| panic!("failed to create {path}: {e}")),
+        );
+        out.write_all(b"[")
+            .unwrap_or_else(|e| panic!("failed to write {path}: {e}"));
+        Self {
+            path,
+            out,
+            empty: true,
+        }
+    }
+
+    fn push<T: serde::Serialize>(&mut self, value: &T) {
+        let path = self.path;
+        let sep: &[u8] = if self.empty { b"\n  " } else { b",\n  " };
+        self.empty = false;
+        self.out
+            .write_all(sep)
+            .map_err(serde_json::Error::io)
+            .and_then(|()| serde_json::to_writer(&mut self.out, value))
+            .unwrap_or_else(|e| panic!("failed to write {path}: {e}"));
+    }
+
+    fn finish(mut self) {
+        let path = self.path;
+        let end: &[u8] = if self.empty { b"]" } else { b"\n]" };
+        self.out
+            .write_all(end)
+            .and_then(|()| self.out.flush())
+            .unwrap_or_else(|e| panic!("failed to write {path}: {e}"));
+        println!("      wrote {path}");
+    }
+}
+
 /// A CSV-serialisable mirror of [`datawarehousing_example_nyc_vehicle_incidents::data_mart::time::Time`].
 ///
 /// The original struct contains `time::Date` and `time::PrimitiveDateTime` which
@@ -269,11 +854,36 @@ fn write_csv<T: serde::Serialize>(path: &str, rows: &[T]) {
 struct CsvTime {
     time_id: u32,
     timestamp: String,
+    utc_offset_minutes: i16,
+    dst_flag: datawarehousing_example_nyc_vehicle_incidents::data_mart::time::DstFlag,
     hier_def_day: String,
     hier_def_month: String,
+    hier_def_month_number: u8,
+    hier_def_quarter: u8,
     hier_def_year: u16,
+    hour_of_day: u8,
+    day_of_week: datawarehousing_example_nyc_vehicle_incidents::data_mart::time::DayOfWeek,
+    day_of_week_number: u8,
+    iso_week: u8,
+    iso_week_year: u16,
+    is_weekend: bool,
+    is_rush_hour: bool,
+    season: datawarehousing_example_nyc_vehicle_incidents::data_mart::time::Season,
+    holiday: datawarehousing_example_nyc_vehicle_incidents::data_mart::time::Holiday,
+    is_holiday: bool,
     hier_moon_phase: datawarehousing_example_nyc_vehicle_incidents::data_mart::time::MoonPhase,
+    moon_illumination: f64,
+    moon_age_days: f64,
+    light_condition: datawarehousing_example_nyc_vehicle_incidents::data_mart::time::LightCondition,
+    sun_altitude: f64,
     weather: datawarehousing_example_nyc_vehicle_incidents::data_mart::time::Weather,
+    weather_rule_id: Option<String>,
+    snow_risk: datawarehousing_example_nyc_vehicle_incidents::data_mart::time::SnowRisk,
+    temperature_c: Option<f64>,
+    precipitation_mm: Option<f64>,
+    rain_mm: Option<f64>,
+    cloudcover_pct: Option<f64>,
+    windspeed_kmh: Option<f64>,
 }
 
 /// Reads database credentials from environment variables.
@@ -0,0 +1,145 @@
+use crate::raw::RawLoadError;
+use csv::StringRecord;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// A column a raw loader reads, identified by its canonical header name.
+///
+/// Headers are matched case-insensitively with `_` and ` ` treated as equal,
+/// so `CRASH DATE` (CSV export) and `crash_date` (Socrata API) both resolve.
+#[derive(Debug, Clone, Copy)]
+pub struct Column {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub required: bool,
+}
+
+impl Column {
+    pub const fn required(name: &'static str, aliases: &'static [&'static str]) -> Self {
+        Self {
+            name,
+            aliases,
+            required: true,
+        }
+    }
+
+    pub const fn optional(name: &'static str, aliases: &'static [&'static str]) -> Self {
+        Self {
+            name,
+            aliases,
+            required: false,
+        }
+    }
+}
+
+/// Additional header names per canonical column, on top of the built-in aliases.
+///
+/// ```json
+/// { "windspeed": ["windspeed_100m (km/h)"] }
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub struct ColumnAliases(pub HashMap<String, Vec<String>>);
+
+/// Maps canonical column names to their position in one particular file.
+#[derive(Debug, Clone)]
+pub(crate) struct Header {
+    indices: HashMap<&'static str, usize>,
+}
+
+fn normalize(header: &str) -> String {
+    header.trim().to_lowercase().replace('_', " ")
+}
+
+impl Header {
+    /// Resolves `columns` against the header row of `path`.
+    ///
+    /// Unknown extra columns are ignored; a missing required column fails with
+    /// [`RawLoadError::MissingColumn`].
+    pub(crate) fn resolve(
+        path: &str,
+        headers: &StringRecord,
+        columns: &[Column],
+        aliases: &ColumnAliases,
+    ) -> Result<Self, RawLoadError> {
+        let positions: HashMap<String, usize> = headers
+            .iter()
+            .enumerate()
+            .map(|(i, h)| (normalize(h), i))
+            .collect();
+
+        let mut indices = HashMap::new();
+        for column in columns {
+            let configured = aliases.0.get(column.name).into_iter().flatten();
+            let candidates = std::iter::once(column.name)
+                .chain(column.aliases.iter().copied())
+                .chain(configured.map(String::as_str))
+                .collect::<Vec<_>>();
+
+            match candidates
+                .iter()
+                .find_map(|c| positions.get(&normalize(c)).copied())
+            {
+                Some(i) => {
+                    indices.insert(column.name, i);
+                }
+                None if column.required => {
+                    return Err(RawLoadError::MissingColumn {
+                        path: path.into(),
+                        column: column.name.into(),
+                        candidates: candidates.into_iter().map(Into::into).collect(),
+                    });
+                }
+                None => {}
+            }
+        }
+        Ok(Self { indices })
+    }
+
+    pub(crate) fn index(&self, column: &str) -> Option<usize> {
+        self.indices.get(column).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMNS: &[Column] = &[
+        Column::required("COLLISION_ID", &[]),
+        Column::required("windspeed", &["windspeed_10m (km/h)"]),
+        Column::optional("BOROUGH", &[]),
+    ];
+
+    #[test]
+    fn resolves_by_name_alias_and_normalization() {
+        let headers = StringRecord::from(vec!["extra", "windspeed_10m (km/h)", "collision id"]);
+        let header =
+            Header::resolve("f.csv", &headers, COLUMNS, &ColumnAliases::default()).unwrap();
+        assert_eq!(header.index("COLLISION_ID"), Some(2));
+        assert_eq!(header.index("windspeed"), Some(1));
+        assert_eq!(header.index("BOROUGH"), None);
+    }
+
+    #[test]
+    fn configured_alias_is_used() {
+        let headers = StringRecord::from(vec!["COLLISION_ID", "wind (km/h)"]);
+        let aliases = ColumnAliases(HashMap::from([(
+            "windspeed".to_string(),
+            vec!["wind (km/h)".to_string()],
+        )]));
+        let header = Header::resolve("f.csv", &headers, COLUMNS, &aliases).unwrap();
+        assert_eq!(header.index("windspeed"), Some(1));
+    }
+
+    #[test]
+    fn missing_required_column_is_reported() {
+        let headers = StringRecord::from(vec!["COLLISION_ID"]);
+        let err =
+            Header::resolve("f.csv", &headers, COLUMNS, &ColumnAliases::default()).unwrap_err();
+        assert!(matches!(
+            err,
+            RawLoadError::MissingColumn { ref column, .. } if column == "windspeed"
+        ));
+    }
+}
@@ -1,59 +1,134 @@
+use crate::data_quality::rejects::RejectSource;
+use crate::raw::{Column, ColumnAliases, ParseError, RawLoadError, RawReader, Row};
+use serde::Serialize;
 use time::{Date, Time, macros::format_description};
 
 /// https://dev.socrata.com/foundry/data.cityofnewyork.us/h9gi-nx95
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RawCrashRecord {
+    /// 1-based line in the source file.
+    #[serde(skip)]
+    pub line: u64,
     pub collision_id: u32,
     pub crash_date: Date,
     pub crash_time: Time,
-    pub number_of_persons_injured: u16,
-    pub number_of_persons_killed: u16,
-    pub number_of_pedestrians_injured: u16,
-    pub number_of_pedestrians_killed: u16,
-    pub number_of_cyclist_injured: u16,
-    pub number_of_cyclist_killed: u16,
-    pub number_of_motorist_injured: u16,
-    pub number_of_motorist_killed: u16,
+    pub borough: Option<String>,
+    pub zip_code: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub on_street_name: Option<String>,
+    pub cross_street_name: Option<String>,
+    pub off_street_name: Option<String>,
+    // wider than the crash columns, so that an implausibly large count can
+    // be quarantined instead of failing to parse
+    pub number_of_persons_injured: u32,
+    pub number_of_persons_killed: u32,
+    pub number_of_pedestrians_injured: u32,
+    pub number_of_pedestrians_killed: u32,
+    pub number_of_cyclist_injured: u32,
+    pub number_of_cyclist_killed: u32,
+    pub number_of_motorist_injured: u32,
+    pub number_of_motorist_killed: u32,
     pub contributing_factor_vehicle_1: String,
     pub contributing_factor_vehicle_2: String,
     pub contributing_factor_vehicle_3: String,
     pub contributing_factor_vehicle_4: String,
     pub contributing_factor_vehicle_5: String,
+    pub vehicle_type_code_1: String,
+    pub vehicle_type_code_2: String,
+    pub vehicle_type_code_3: String,
+    pub vehicle_type_code_4: String,
+    pub vehicle_type_code_5: String,
 }
 
 impl RawCrashRecord {
-    pub fn load_from_csv(path: &str) -> Vec<Self> {
-        let mut rdr = csv::ReaderBuilder::new()
-            .has_headers(true)
-            .trim(csv::Trim::All)
-            .from_path(path)
-            .unwrap();
-        let mut records = Vec::new();
+    pub const COLUMNS: &[Column] = &[
+        Column::required("COLLISION_ID", &[]),
+        Column::required("CRASH DATE", &[]),
+        Column::required("CRASH TIME", &[]),
+        Column::optional("BOROUGH", &[]),
+        Column::optional("ZIP CODE", &[]),
+        Column::optional("LATITUDE", &[]),
+        Column::optional("LONGITUDE", &[]),
+        Column::optional("ON STREET NAME", &[]),
+        Column::optional("CROSS STREET NAME", &[]),
+        Column::optional("OFF STREET NAME", &[]),
+        Column::required("NUMBER OF PERSONS INJURED", &[]),
+        Column::required("NUMBER OF PERSONS KILLED", &[]),
+        Column::required("NUMBER OF PEDESTRIANS INJURED", &[]),
+        Column::required("NUMBER OF PEDESTRIANS KILLED", &[]),
+        Column::required("NUMBER OF CYCLIST INJURED", &[]),
+        Column::required("NUMBER OF CYCLIST KILLED", &[]),
+        Column::required("NUMBER OF MOTORIST INJURED", &[]),
+        Column::required("NUMBER OF MOTORIST KILLED", &[]),
+        Column::optional("CONTRIBUTING FACTOR VEHICLE 1", &[]),
+        Column::optional("CONTRIBUTING FACTOR VEHICLE 2", &[]),
+        Column::optional("CONTRIBUTING FACTOR VEHICLE 3", &[]),
+        Column::optional("CONTRIBUTING FACTOR VEHICLE 4", &[]),
+        Column::optional("CONTRIBUTING FACTOR VEHICLE 5", &[]),
+        Column::optional("VEHICLE TYPE CODE 1", &[]),
+        Column::optional("VEHICLE TYPE CODE 2", &[]),
+        Column::optional("VEHICLE TYPE CODE 3", &[]),
+        Column::optional("VEHICLE TYPE CODE 4", &[]),
+        Column::optional("VEHICLE TYPE CODE 5", &[]),
+    ];
+
+    /// Opens the file for streaming; rows are parsed as they are read.
+    pub fn read_csv(path: &str, aliases: &ColumnAliases) -> Result<RawReader<Self>, RawLoadError> {
+        RawReader::open(
+            path,
+            RejectSource::Crashes,
+            Self::COLUMNS,
+            aliases,
+            Self::parse,
+        )
+    }
+
+    fn parse(row: &Row) -> Result<Self, ParseError> {
         let date_fmt = format_description!("[month]/[day]/[year]");
         let time_fmt = format_description!("[hour padding:none]:[minute]");
+        let count = "non-negative integer";
 
-        for result in rdr.records() {
-            let string_record = result.unwrap();
-            let record = RawCrashRecord {
-                collision_id: string_record[23].parse().unwrap(),
-                crash_date: time::Date::parse(&string_record[0], &date_fmt).unwrap(),
-                crash_time: time::Time::parse(&string_record[1], &time_fmt).unwrap(),
-                number_of_persons_injured: string_record[10].parse().unwrap_or(0),
-                number_of_persons_killed: string_record[11].parse().unwrap_or(0),
-                number_of_pedestrians_injured: string_record[12].parse().unwrap_or(0),
-                number_of_pedestrians_killed: string_record[13].parse().unwrap_or(0),
-                number_of_cyclist_injured: string_record[14].parse().unwrap_or(0),
-                number_of_cyclist_killed: string_record[15].parse().unwrap_or(0),
-                number_of_motorist_injured: string_record[16].parse().unwrap_or(0),
-                number_of_motorist_killed: string_record[17].parse().unwrap_or(0),
-                contributing_factor_vehicle_1: string_record[18].to_string(),
-                contributing_factor_vehicle_2: string_record[19].to_string(),
-                contributing_factor_vehicle_3: string_record[20].to_string(),
-                contributing_factor_vehicle_4: string_record[21].to_string(),
-                contributing_factor_vehicle_5: string_record[22].to_string(),
-            };
-            records.push(record);
-        }
-        records
+        Ok(RawCrashRecord {
+            line: row.line(),
+            collision_id: row.parse("COLLISION_ID", "unsigned integer")?,
+            crash_date: row.parse_with("CRASH DATE", "date as MM/DD/YYYY", |s| {
+                Date::parse(s, &date_fmt).ok()
+            })?,
+            crash_time: row.parse_with("CRASH TIME", "time as H:MM", |s| {
+                Time::parse(s, &time_fmt).ok()
+            })?,
+            borough: row.non_empty("BOROUGH"),
+            zip_code: row.lenient_with("ZIP CODE", "5-digit ZIP code", |s| {
+                Some(s.to_string())
+                    .filter(|s| s.len() == 5 && s.bytes().all(|b| b.is_ascii_digit()))
+            }),
+            latitude: row.lenient("LATITUDE", "decimal degrees"),
+            longitude: row.lenient("LONGITUDE", "decimal degrees"),
+            on_street_name: row.non_empty("ON STREET NAME"),
+            cross_street_name: row.non_empty("CROSS STREET NAME"),
+            off_street_name: row.non_empty("OFF STREET NAME"),
+            number_of_persons_injured: row.parse_or_default("NUMBER OF PERSONS INJURED", count)?,
+            number_of_persons_killed: row.parse_or_default("NUMBER OF PERSONS KILLED", count)?,
+            number_of_pedestrians_injured: row
+                .parse_or_default("NUMBER OF PEDESTRIANS INJURED", count)?,
+            number_of_pedestrians_killed: row
+                .parse_or_default("NUMBER OF PEDESTRIANS KILLED", count)?,
+            number_of_cyclist_injured: row.parse_or_default("NUMBER OF CYCLIST INJURED", count)?,
+            number_of_cyclist_killed: row.parse_or_default("NUMBER OF CYCLIST KILLED", count)?,
+            number_of_motorist_injured: row
+                .parse_or_default("NUMBER OF MOTORIST INJURED", count)?,
+            number_of_motorist_killed: row.parse_or_default("NUMBER OF MOTORIST KILLED", count)?,
+            contributing_factor_vehicle_1: row.get("CONTRIBUTING FACTOR VEHICLE 1").to_string(),
+            contributing_factor_vehicle_2: row.get("CONTRIBUTING FACTOR VEHICLE 2").to_string(),
+            contributing_factor_vehicle_3: row.get("CONTRIBUTING FACTOR VEHICLE 3").to_string(),
+            contributing_factor_vehicle_4: row.get("CONTRIBUTING FACTOR VEHICLE 4").to_string(),
+            contributing_factor_vehicle_5: row.get("CONTRIBUTING FACTOR VEHICLE 5").to_string(),
+            vehicle_type_code_1: row.get("VEHICLE TYPE CODE 1").to_string(),
+            vehicle_type_code_2: row.get("VEHICLE TYPE CODE 2").to_string(),
+            vehicle_type_code_3: row.get("VEHICLE TYPE CODE 3").to_string(),
+            vehicle_type_code_4: row.get("VEHICLE TYPE CODE 4").to_string(),
+            vehicle_type_code_5: row.get("VEHICLE TYPE CODE 5").to_string(),
+        })
     }
 }
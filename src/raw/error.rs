@@ -0,0 +1,92 @@
+use std::fmt;
+
+/// A single field of a raw CSV row that could not be parsed.
+///
+/// Carries everything needed to find and fix the offending value in the
+/// source export: file, 1-based line number, column name, the raw value and
+/// the format that was expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub path: String,
+    pub line: u64,
+    pub column: String,
+    pub value: String,
+    pub expected: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: column '{}' has value {:?}, expected {}",
+            self.path, self.line, self.column, self.value, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Everything that can go wrong while loading one of the raw CSV files.
+#[derive(Debug)]
+pub enum RawLoadError {
+    /// The file could not be opened.
+    Open { path: String, source: csv::Error },
+    /// The CSV reader failed on a row (I/O error, invalid UTF-8, ...).
+    Csv {
+        path: String,
+        line: Option<u64>,
+        source: csv::Error,
+    },
+    /// A column the loader needs is not in the header row under any known name.
+    MissingColumn {
+        path: String,
+        column: String,
+        candidates: Vec<String>,
+    },
+    /// A field was present but could not be parsed.
+    Parse(ParseError),
+}
+
+impl fmt::Display for RawLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RawLoadError::Open { path, source } => write!(f, "{path}: cannot open file: {source}"),
+            RawLoadError::Csv {
+                path,
+                line: Some(line),
+                source,
+            } => write!(f, "{path}:{line}: malformed CSV row: {source}"),
+            RawLoadError::Csv {
+                path,
+                line: None,
+                source,
+            } => write!(f, "{path}: malformed CSV: {source}"),
+            RawLoadError::MissingColumn {
+                path,
+                column,
+                candidates,
+            } => write!(
+                f,
+                "{path}: required column '{column}' not found (looked for: {})",
+                candidates.join(", ")
+            ),
+            RawLoadError::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for RawLoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RawLoadError::Open { source, .. } | RawLoadError::Csv { source, .. } => Some(source),
+            RawLoadError::MissingColumn { .. } => None,
+            RawLoadError::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<ParseError> for RawLoadError {
+    fn from(e: ParseError) -> Self {
+        RawLoadError::Parse(e)
+    }
+}
@@ -1,4 +1,344 @@
+pub mod columns;
 pub mod crashes;
+pub mod error;
 pub mod moon;
 pub mod persons;
+pub mod vehicles;
 pub mod weather;
+
+pub use columns::{Column, ColumnAliases};
+pub use error::{ParseError, RawLoadError};
+
+use crate::data_quality::rejects::{RejectLocation, RejectReason, RejectSource, Rejects};
+use columns::Header;
+use csv::{Reader, StringRecord};
+use std::{cell::RefCell, fs::File, str::FromStr};
+
+/// Opens one of the raw CSV exports with the settings shared by all loaders
+/// and resolves `columns` against its header row.
+pub(crate) fn open_csv(
+    path: &str,
+    columns: &[Column],
+    aliases: &ColumnAliases,
+) -> Result<(Reader<File>, Header), RawLoadError> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .trim(csv::Trim::All)
+        .from_path(path)
+        .map_err(|source| RawLoadError::Open {
+            path: path.into(),
+            source,
+        })?;
+    let headers = rdr.headers().map_err(|e| csv_error(path, e))?;
+    let header = Header::resolve(path, headers, columns, aliases)?;
+    Ok((rdr, header))
+}
+
+/// Streams the rows of one raw CSV file, parsing them one at a time so that
+/// arbitrarily large exports can be processed in bounded memory.
+pub struct RawReader<T> {
+    path: String,
+    source: RejectSource,
+    header: Header,
+    records: csv::StringRecordsIntoIter<File>,
+    parse: fn(&Row) -> Result<T, ParseError>,
+}
+
+impl<T> RawReader<T> {
+    pub(crate) fn open(
+        path: &str,
+        source: RejectSource,
+        columns: &[Column],
+        aliases: &ColumnAliases,
+        parse: fn(&Row) -> Result<T, ParseError>,
+    ) -> Result<Self, RawLoadError> {
+        let (rdr, header) = open_csv(path, columns, aliases)?;
+        Ok(Self {
+            path: path.into(),
+            source,
+            header,
+            records: rdr.into_records(),
+            parse,
+        })
+    }
+
+    /// Collects all records, failing on the first row that cannot be parsed.
+    pub fn strict(self) -> Result<Vec<T>, RawLoadError> {
+        self.map(|row| Ok(row?.parsed?)).collect()
+    }
+
+    /// Collects all records, quarantining rows that cannot be parsed.
+    pub fn quarantined(self, rejects: &mut Rejects) -> Result<Vec<T>, RawLoadError> {
+        let mut records = Vec::new();
+        for row in self {
+            records.extend(row?.quarantine(rejects));
+        }
+        Ok(records)
+    }
+}
+
+impl<T> Iterator for RawReader<T> {
+    type Item = Result<RawRow<T>, RawLoadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let fields = match self.records.next()? {
+            Ok(fields) => fields,
+            Err(e) => return Some(Err(csv_error(&self.path, e))),
+        };
+        let (parsed, degraded) = {
+            let row = Row::new(&self.path, &self.header, &fields);
+            let parsed = (self.parse)(&row);
+            (parsed, row.degraded.take())
+        };
+        Some(Ok(RawRow {
+            source: self.source,
+            fields,
+            parsed,
+            degraded,
+        }))
+    }
+}
+
+/// One row of a raw file: the parsed record (or why it failed) together
+/// with the original fields, which are needed to quarantine it.
+#[derive(Debug)]
+pub struct RawRow<T> {
+    pub source: RejectSource,
+    pub fields: StringRecord,
+    pub parsed: Result<T, ParseError>,
+    /// Optional fields that were malformed and read as unknown (see [`Row::lenient`]).
+    pub degraded: Vec<ParseError>,
+}
+
+impl<T> RawRow<T> {
+    /// Writes an unparseable row, or each degraded field of a parsed one, to
+    /// `rejects` and returns the record if there is one.
+    pub fn quarantine(self, rejects: &mut Rejects) -> Option<T> {
+        let (record, reason, errors) = match self.parsed {
+            Ok(record) => (Some(record), RejectReason::Degraded, self.degraded),
+            Err(e) => (None, RejectReason::Unparseable, vec![e]),
+        };
+        for e in &errors {
+            let at = RejectLocation {
+                line: e.line,
+                column: &e.column,
+                value: &e.value,
+            };
+            rejects.reject_fields(self.source, reason, at, &self.fields);
+        }
+        record
+    }
+}
+
+/// Wraps an error of the CSV reader itself with the file it occurred in.
+pub(crate) fn csv_error(path: &str, source: csv::Error) -> RawLoadError {
+    RawLoadError::Csv {
+        path: path.into(),
+        line: source.position().map(|p| p.line()),
+        source,
+    }
+}
+
+/// One CSV row together with its origin, so that every field access can
+/// report a precise [`ParseError`] instead of panicking.
+pub(crate) struct Row<'a> {
+    path: &'a str,
+    header: &'a Header,
+    record: &'a StringRecord,
+    degraded: RefCell<Vec<ParseError>>,
+}
+
+impl<'a> Row<'a> {
+    pub(crate) fn new(path: &'a str, header: &'a Header, record: &'a StringRecord) -> Self {
+        Self {
+            path,
+            header,
+            record,
+            degraded: RefCell::default(),
+        }
+    }
+
+    /// 1-based line number of the row in the source file.
+    pub(crate) fn line(&self) -> u64 {
+        self.record.position().map_or(0, |p| p.line())
+    }
+
+    /// The raw field; absent optional columns and missing trailing fields
+    /// read as empty.
+    pub(crate) fn get(&self, column: &str) -> &'a str {
+        self.header
+            .index(column)
+            .and_then(|i| self.record.get(i))
+            .unwrap_or("")
+    }
+
+    /// The raw field, or `None` if it is empty.
+    pub(crate) fn non_empty(&self, column: &str) -> Option<String> {
+        Some(self.get(column))
+            .filter(|s| !s.is_empty())
+            .map(Into::into)
+    }
+
+    pub(crate) fn error(&self, column: &str, expected: &'static str) -> ParseError {
+        ParseError {
+            path: self.path.into(),
+            line: self.line(),
+            column: column.into(),
+            value: self.get(column).into(),
+            expected,
+        }
+    }
+
+    /// Parses a required field with a custom parser.
+    pub(crate) fn parse_with<T>(
+        &self,
+        column: &str,
+        expected: &'static str,
+        parse: impl FnOnce(&str) -> Option<T>,
+    ) -> Result<T, ParseError> {
+        parse(self.get(column)).ok_or_else(|| self.error(column, expected))
+    }
+
+    /// Parses an optional field with a custom parser; empty fields are `None`.
+    pub(crate) fn parse_opt_with<T>(
+        &self,
+        column: &str,
+        expected: &'static str,
+        parse: impl FnOnce(&str) -> Option<T>,
+    ) -> Result<Option<T>, ParseError> {
+        match self.get(column) {
+            "" => Ok(None),
+            s => parse(s)
+                .map(Some)
+                .ok_or_else(|| self.error(column, expected)),
+        }
+    }
+
+    /// Parses an optional field that is allowed to be malformed: a value that
+    /// does not parse becomes `None` and is remembered as degraded.
+    pub(crate) fn lenient<T: FromStr>(&self, column: &str, expected: &'static str) -> Option<T> {
+        self.lenient_with(column, expected, |s| s.parse().ok())
+    }
+
+    /// Like [`Row::lenient`], with a custom parser.
+    pub(crate) fn lenient_with<T>(
+        &self,
+        column: &str,
+        expected: &'static str,
+        parse: impl FnOnce(&str) -> Option<T>,
+    ) -> Option<T> {
+        self.parse_opt_with(column, expected, parse)
+            .unwrap_or_else(|e| {
+                self.degraded.borrow_mut().push(e);
+                None
+            })
+    }
+
+    /// Parses a required field via [`FromStr`].
+    pub(crate) fn parse<T: FromStr>(
+        &self,
+        column: &str,
+        expected: &'static str,
+    ) -> Result<T, ParseError> {
+        self.parse_with(column, expected, |s| s.parse().ok())
+    }
+
+    /// Parses a field via [`FromStr`], falling back to `T::default()` if it is empty.
+    pub(crate) fn parse_or_default<T: FromStr + Default>(
+        &self,
+        column: &str,
+        expected: &'static str,
+    ) -> Result<T, ParseError> {
+        self.parse_opt_with(column, expected, |s| s.parse().ok())
+            .map(Option::unwrap_or_default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw::{crashes::RawCrashRecord, persons::RawPersonRecord};
+
+    #[test]
+    fn preview_files_load() {
+        assert!(
+            !RawCrashRecord::read_csv("data/crashes_preview.csv", &ColumnAliases::default())
+                .and_then(RawReader::strict)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            !RawPersonRecord::read_csv("data/persons_preview.csv", &ColumnAliases::default())
+                .and_then(RawReader::strict)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn malformed_field_reports_line_and_column() {
+        let path =
+            std::env::temp_dir().join(format!("raw_weather_malformed_{}.csv", std::process::id()));
+        std::fs::write(
+            &path,
+            "time,temperature_2m (°C),precipitation (mm),rain (mm),cloudcover (%),\
+             cloudcover_low (%),cloudcover_mid (%),cloudcover_high (%),\
+             windspeed_10m (km/h),winddirection_10m (°)\n\
+             2016-01-01T00:00:00Z,7.6,0.00,0.00,69,53,0,72,10.0,296\n\
+             2016-01-01T01:00:00Z,7.5,n/a,0.00,20,4,0,56,9.8,287\n",
+        )
+        .unwrap();
+        let loaded =
+            weather::RawWeatherRecord::read_csv(path.to_str().unwrap(), &ColumnAliases::default())
+                .and_then(RawReader::strict);
+        std::fs::remove_file(&path).unwrap();
+
+        match loaded {
+            Err(RawLoadError::Parse(e)) => {
+                assert_eq!(e.line, 3);
+                assert_eq!(e.column, "precipitation");
+                assert_eq!(e.value, "n/a");
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn malformed_person_id_is_a_parse_error() {
+        let path = std::env::temp_dir().join(format!(
+            "raw_persons_malformed_id_{}.csv",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            "UNIQUE_ID,COLLISION_ID,CRASH_DATE,CRASH_TIME,PERSON_ID,PERSON_TYPE,PERSON_INJURY,\
+             VEHICLE_ID,PERSON_AGE,EJECTION,EMOTIONAL_STATUS,BODILY_INJURY,POSITION_IN_VEHICLE,\
+             SAFETY_EQUIPMENT,PED_LOCATION,PED_ACTION,COMPLAINT,PED_ROLE,CONTRIBUTING_FACTOR_1,\
+             CONTRIBUTING_FACTOR_2,PERSON_SEX\n\
+             10249006-A,4229554,10/26/2019,9:43,31aa2bc0,Occupant,Unspecified,19141108,,,,,,,,,,\
+             Registrant,,,U\n",
+        )
+        .unwrap();
+        let loaded = RawPersonRecord::read_csv(path.to_str().unwrap(), &ColumnAliases::default())
+            .and_then(RawReader::strict);
+        std::fs::remove_file(&path).unwrap();
+
+        match loaded {
+            Err(RawLoadError::Parse(e)) => {
+                assert_eq!(e.line, 2);
+                assert_eq!(e.column, "UNIQUE_ID");
+                assert_eq!(e.value, "10249006-A");
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn missing_file_is_an_open_error() {
+        assert!(matches!(
+            moon::RawMoonRecord::read_csv("data/does_not_exist.csv", &ColumnAliases::default())
+                .and_then(RawReader::strict),
+            Err(RawLoadError::Open { .. })
+        ));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::data_quality::rejects::RejectSource;
+use crate::raw::{Column, ColumnAliases, ParseError, RawLoadError, RawReader, Row};
 use time::{Date, macros::format_description};
 
 /// https://www.kaggle.com/datasets/jodiemullins/1900-2022-primary-moon-phases-utc7-timezone
@@ -10,25 +12,34 @@ pub struct RawMoonRecord {
 }
 
 impl RawMoonRecord {
-    pub fn load_from_csv(path: &str) -> Vec<Self> {
-        let mut rdr = csv::ReaderBuilder::new()
-            .has_headers(true)
-            .trim(csv::Trim::All)
-            .from_path(path)
-            .unwrap();
-        let mut records = Vec::new();
+    pub const COLUMNS: &[Column] = &[
+        Column::required("new_moon", &[]),
+        Column::required("first_quarter_moon", &[]),
+        Column::required("full_moon", &[]),
+        Column::required("third_quarter_moon", &["last_quarter_moon"]),
+    ];
+
+    /// Opens the file for streaming; rows are parsed as they are read.
+    pub fn read_csv(path: &str, aliases: &ColumnAliases) -> Result<RawReader<Self>, RawLoadError> {
+        RawReader::open(
+            path,
+            RejectSource::Moon,
+            Self::COLUMNS,
+            aliases,
+            Self::parse,
+        )
+    }
+
+    fn parse(row: &Row) -> Result<Self, ParseError> {
         let date_fmt = format_description!("[month padding:none]/[day padding:none]/[year]");
+        let date = |s: &str| Date::parse(s, &date_fmt).ok();
+        let expected = "date as M/D/YYYY";
 
-        for result in rdr.records() {
-            let string_record = result.unwrap();
-            let record = RawMoonRecord {
-                new_moon: time::Date::parse(&string_record[0], &date_fmt).ok(),
-                first_quarter_moon: time::Date::parse(&string_record[1], &date_fmt).ok(),
-                full_moon: time::Date::parse(&string_record[2], &date_fmt).ok(),
-                third_quarter_moon: time::Date::parse(&string_record[3], &date_fmt).ok(),
-            };
-            records.push(record);
-        }
-        records
+        Ok(RawMoonRecord {
+            new_moon: row.parse_opt_with("new_moon", expected, date)?,
+            first_quarter_moon: row.parse_opt_with("first_quarter_moon", expected, date)?,
+            full_moon: row.parse_opt_with("full_moon", expected, date)?,
+            third_quarter_moon: row.parse_opt_with("third_quarter_moon", expected, date)?,
+        })
     }
 }
@@ -1,9 +1,15 @@
+use crate::data_quality::rejects::RejectSource;
+use crate::raw::{Column, ColumnAliases, ParseError, RawLoadError, RawReader, Row};
+use serde::Serialize;
 use time::{Date, Time, macros::format_description};
 
 /// https://dev.socrata.com/foundry/data.cityofnewyork.us/f55k-p6yu
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct RawPersonRecord {
-    pub unique_id: String,
+    /// 1-based line in the source file.
+    #[serde(skip)]
+    pub line: u64,
+    pub unique_id: u32,
     pub collision_id: u32,
     pub crash_date: Date,
     pub crash_time: Time,
@@ -12,49 +18,81 @@ pub struct RawPersonRecord {
     pub person_sex: Option<char>,
     pub person_position_in_vehicle: Option<String>,
     pub person_ped_role: Option<String>,
+    pub person_injury: Option<String>,
+    pub ejection: Option<String>,
+    pub emotional_status: Option<String>,
+    pub bodily_injury: Option<String>,
+    pub safety_equipment: Option<String>,
+    pub ped_location: Option<String>,
+    pub ped_action: Option<String>,
+    pub vehicle_id: Option<u32>,
+    pub contributing_factor_1: Option<String>,
+    pub contributing_factor_2: Option<String>,
 }
 
 impl RawPersonRecord {
-    pub fn load_from_csv(path: &str) -> Vec<Self> {
-        let mut rdr = csv::ReaderBuilder::new()
-            .has_headers(true)
-            .trim(csv::Trim::All)
-            .from_path(path)
-            .unwrap();
-        let mut records = Vec::new();
+    pub const COLUMNS: &[Column] = &[
+        Column::required("UNIQUE_ID", &[]),
+        Column::required("COLLISION_ID", &[]),
+        Column::required("CRASH_DATE", &[]),
+        Column::required("CRASH_TIME", &[]),
+        Column::optional("PERSON_TYPE", &[]),
+        Column::optional("PERSON_AGE", &[]),
+        Column::optional("PERSON_SEX", &[]),
+        Column::optional("POSITION_IN_VEHICLE", &[]),
+        Column::optional("PED_ROLE", &[]),
+        Column::optional("PERSON_INJURY", &[]),
+        Column::optional("EJECTION", &[]),
+        Column::optional("EMOTIONAL_STATUS", &[]),
+        Column::optional("BODILY_INJURY", &[]),
+        Column::optional("SAFETY_EQUIPMENT", &[]),
+        Column::optional("PED_LOCATION", &[]),
+        Column::optional("PED_ACTION", &[]),
+        Column::optional("VEHICLE_ID", &[]),
+        Column::optional("CONTRIBUTING_FACTOR_1", &[]),
+        Column::optional("CONTRIBUTING_FACTOR_2", &[]),
+    ];
+
+    /// Opens the file for streaming; rows are parsed as they are read.
+    pub fn read_csv(path: &str, aliases: &ColumnAliases) -> Result<RawReader<Self>, RawLoadError> {
+        RawReader::open(
+            path,
+            RejectSource::Persons,
+            Self::COLUMNS,
+            aliases,
+            Self::parse,
+        )
+    }
+
+    fn parse(row: &Row) -> Result<Self, ParseError> {
         let date_fmt = format_description!("[month]/[day]/[year]");
         let time_fmt = format_description!("[hour padding:none]:[minute]");
 
-        for result in rdr.records() {
-            let string_record = result.unwrap();
-            let record = RawPersonRecord {
-                unique_id: string_record[0].into(),
-                collision_id: string_record[1].parse().unwrap(),
-                crash_date: time::Date::parse(&string_record[2], &date_fmt).unwrap(),
-                crash_time: time::Time::parse(&string_record[3], &time_fmt).unwrap(),
-                person_type: if string_record[5].is_empty() {
-                    None
-                } else {
-                    Some(string_record[5].into())
-                },
-                person_age: string_record[8]
-                    .parse::<u8>()
-                    .map(Into::into)
-                    .unwrap_or(None),
-                person_sex: string_record[20].parse().ok(),
-                person_position_in_vehicle: if string_record[12].is_empty() {
-                    None
-                } else {
-                    Some(string_record[12].into())
-                },
-                person_ped_role: if string_record[17].is_empty() {
-                    None
-                } else {
-                    Some(string_record[17].into())
-                },
-            };
-            records.push(record);
-        }
-        records
+        Ok(RawPersonRecord {
+            line: row.line(),
+            unique_id: row.parse("UNIQUE_ID", "unsigned integer")?,
+            collision_id: row.parse("COLLISION_ID", "unsigned integer")?,
+            crash_date: row.parse_with("CRASH_DATE", "date as MM/DD/YYYY", |s| {
+                Date::parse(s, &date_fmt).ok()
+            })?,
+            crash_time: row.parse_with("CRASH_TIME", "time as H:MM", |s| {
+                Time::parse(s, &time_fmt).ok()
+            })?,
+            person_type: row.non_empty("PERSON_TYPE"),
+            person_age: row.lenient("PERSON_AGE", "age in years (0-255)"),
+            person_sex: row.lenient("PERSON_SEX", "single letter"),
+            person_position_in_vehicle: row.non_empty("POSITION_IN_VEHICLE"),
+            person_ped_role: row.non_empty("PED_ROLE"),
+            person_injury: row.non_empty("PERSON_INJURY"),
+            ejection: row.non_empty("EJECTION"),
+            emotional_status: row.non_empty("EMOTIONAL_STATUS"),
+            bodily_injury: row.non_empty("BODILY_INJURY"),
+            safety_equipment: row.non_empty("SAFETY_EQUIPMENT"),
+            ped_location: row.non_empty("PED_LOCATION"),
+            ped_action: row.non_empty("PED_ACTION"),
+            vehicle_id: row.lenient("VEHICLE_ID", "unsigned integer"),
+            contributing_factor_1: row.non_empty("CONTRIBUTING_FACTOR_1"),
+            contributing_factor_2: row.non_empty("CONTRIBUTING_FACTOR_2"),
+        })
     }
 }
@@ -0,0 +1,44 @@
+use crate::data_quality::rejects::RejectSource;
+use crate::raw::{Column, ColumnAliases, ParseError, RawLoadError, RawReader, Row};
+use serde::Serialize;
+
+/// https://dev.socrata.com/foundry/data.cityofnewyork.us/bm4k-52h4
+///
+/// `UNIQUE_ID` is what the persons file references as `VEHICLE_ID`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct RawVehicleRecord {
+    /// 1-based line in the source file.
+    #[serde(skip)]
+    pub line: u64,
+    pub unique_id: u32,
+    pub collision_id: u32,
+    pub vehicle_type: Option<String>,
+}
+
+impl RawVehicleRecord {
+    pub const COLUMNS: &[Column] = &[
+        Column::required("UNIQUE_ID", &[]),
+        Column::required("COLLISION_ID", &[]),
+        Column::optional("VEHICLE_TYPE", &[]),
+    ];
+
+    /// Opens the file for streaming; rows are parsed as they are read.
+    pub fn read_csv(path: &str, aliases: &ColumnAliases) -> Result<RawReader<Self>, RawLoadError> {
+        RawReader::open(
+            path,
+            RejectSource::Vehicles,
+            Self::COLUMNS,
+            aliases,
+            Self::parse,
+        )
+    }
+
+    fn parse(row: &Row) -> Result<Self, ParseError> {
+        Ok(RawVehicleRecord {
+            line: row.line(),
+            unique_id: row.parse("UNIQUE_ID", "unsigned integer")?,
+            collision_id: row.parse("COLLISION_ID", "unsigned integer")?,
+            vehicle_type: row.non_empty("VEHICLE_TYPE"),
+        })
+    }
+}
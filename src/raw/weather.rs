@@ -1,3 +1,5 @@
+use crate::data_quality::rejects::RejectSource;
+use crate::raw::{Column, ColumnAliases, ParseError, RawLoadError, RawReader, Row};
 use time::{OffsetDateTime, format_description::well_known};
 
 /// https://www.kaggle.com/datasets/aadimator/nyc-weather-2016-to-2022
@@ -12,25 +14,40 @@ pub struct RawWeatherRecord {
 }
 
 impl RawWeatherRecord {
-    pub fn load_from_csv(path: &str) -> Vec<Self> {
-        let mut rdr = csv::ReaderBuilder::new()
-            .has_headers(true)
-            .trim(csv::Trim::All)
-            .from_path(path)
-            .unwrap();
-        let mut records = Vec::new();
-        for result in rdr.records() {
-            let string_record = result.unwrap();
-            let record = RawWeatherRecord {
-                time: time::OffsetDateTime::parse(&string_record[0], &well_known::Rfc3339).unwrap(),
-                temperature: string_record[1].parse().unwrap(),
-                precipitation: string_record[2].parse().unwrap(),
-                rain: string_record[3].parse().unwrap(),
-                cloudcover: string_record[4].parse().unwrap(),
-                windspeed: string_record[8].parse().unwrap(),
-            };
-            records.push(record);
-        }
-        records
+    pub const COLUMNS: &[Column] = &[
+        Column::required("time", &[]),
+        Column::required("temperature", &["temperature_2m (°C)", "temperature_2m"]),
+        Column::required("precipitation", &["precipitation (mm)"]),
+        Column::required("rain", &["rain (mm)"]),
+        Column::required("cloudcover", &["cloudcover (%)", "cloud_cover (%)"]),
+        Column::required(
+            "windspeed",
+            &["windspeed_10m (km/h)", "wind_speed_10m (km/h)"],
+        ),
+    ];
+
+    /// Opens the file for streaming; rows are parsed as they are read.
+    pub fn read_csv(path: &str, aliases: &ColumnAliases) -> Result<RawReader<Self>, RawLoadError> {
+        RawReader::open(
+            path,
+            RejectSource::Weather,
+            Self::COLUMNS,
+            aliases,
+            Self::parse,
+        )
+    }
+
+    fn parse(row: &Row) -> Result<Self, ParseError> {
+        let number = "decimal number";
+        Ok(RawWeatherRecord {
+            time: row.parse_with("time", "RFC3339 timestamp", |s| {
+                OffsetDateTime::parse(s, &well_known::Rfc3339).ok()
+            })?,
+            temperature: row.parse("temperature", number)?,
+            precipitation: row.parse("precipitation", number)?,
+            rain: row.parse("rain", number)?,
+            cloudcover: row.parse("cloudcover", number)?,
+            windspeed: row.parse("windspeed", number)?,
+        })
     }
 }